@@ -19,11 +19,10 @@ pub struct Author {
     #[default(fn = String::new(), sql = r#"''"#)]
     pub email: String,
     pub bio: String,
-    // // since this is always eager (and also always O(n) queries for n returned entries),
-    // //  consider not including in most structs; here just for demo purposes
-    // #[serde(skip_deserializing)]
-    // #[relation(inverse = book::author)]
-    // pub books: Vec<book::Model>,
+    // lazy, so only loaded where needed (include_many in authors::get_one), null otherwise
+    #[serde(skip_deserializing)]
+    #[relation(inverse = book::author, lazy)]
+    pub books: Vec<book::Model>,
 }
 
 #[gas::model(table_name = "categories")]
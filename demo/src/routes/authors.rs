@@ -3,6 +3,7 @@ use crate::models::author;
 use axum::extract::Path;
 use axum::routing::get;
 use axum::{Json, Router};
+use gas::eq::PgEq;
 use gas::extra::axum::Transaction;
 use gas::ModelOps;
 
@@ -59,7 +60,10 @@ async fn get_one(
     Transaction(tx): Transaction,
     Path(id): Path<i64>,
 ) -> DemoResult<Json<author::Model>> {
-    let author = author::Model::find_by_key(&tx, id)
+    let author = author::Model::query()
+        .filter(|| author::id.eq(id))
+        .include_many(author::books)
+        .find_one(&tx)
         .await?
         .ok_or(HttpError::NotFound)?;
 
@@ -2,16 +2,22 @@ use crate::binary::{BinaryFields, TableSpec};
 use crate::error::{GasCliError, GasCliResult};
 use crate::manifest::GasManifest;
 use crate::sync::variants::add_column::AddColumnModelActor;
+use crate::sync::variants::add_composite_foreign_key::AddCompositeForeignKeyModelActor;
 use crate::sync::variants::add_default::AddDefaultModelActor;
+use crate::sync::variants::add_enum_values::AddEnumValuesModelActor;
 use crate::sync::variants::add_foreign_key_constraint::AddForeignKeyModelActor;
+use crate::sync::variants::add_index::AddIndexModelActor;
 use crate::sync::variants::add_nullable::AddNullableModelActor;
 use crate::sync::variants::add_primary_key_constraint::AddPrimaryKeyModelActor;
 use crate::sync::variants::add_serial::AddSerialModelActor;
 use crate::sync::variants::add_unique_constraint::AddUniqueModelActor;
+use crate::sync::variants::alter_column_type::AlterColumnTypeModelActor;
+use crate::sync::variants::create_enum_type::CreateEnumTypeModelActor;
 use crate::sync::variants::create_table::CreateTableModelActor;
 use crate::sync::variants::rename_column::RenameColumnModelActor;
 use crate::sync::variants::rename_table::RenameTableModelActor;
 use crate::sync::variants::update_column_type::UpdateColumnTypeModelActor;
+use crate::sync::variants::update_foreign_key_actions::UpdateForeignKeyActionsModelActor;
 use crate::sync::{helpers, ModelChangeActor};
 use crate::util::styles::{STYLE_WARN, STYLE_WARN_SOFT};
 use crate::{sync, util};
@@ -19,7 +25,7 @@ use gas_shared::link::{FixedStr, PortableFieldMeta, PortablePgType};
 use gas_shared::FieldFlag;
 use itertools::{Either, Itertools};
 use std::borrow::Cow;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
 #[derive(Debug)]
 struct ColumnSplit<'a> {
@@ -41,6 +47,14 @@ fn try_type<'a>(
         return false;
     }
 
+    // new values of an enum type are added to the type itself, see handle_enum_types
+    if let (PortablePgType::Enum { name: old_name, .. }, PortablePgType::Enum { name, .. }) =
+        (&old.pg_type, &new.pg_type)
+        && old_name == name
+    {
+        return false;
+    }
+
     // promotion to a foreign key
     if let PortablePgType::ForeignKey { key_sql_type, .. } = &new.pg_type
         && key_sql_type.as_ref() == old.pg_type.as_sql_type(false)
@@ -75,6 +89,69 @@ fn try_type<'a>(
     true
 }
 
+// postgres can't change the expression of a generated column in place (before 17 at least),
+//  so it's the same as changing the type
+fn try_generated<'a>(
+    diffs: &mut Vec<Box<dyn ModelChangeActor + 'a>>,
+    old: &'a PortableFieldMeta,
+    new: &'a PortableFieldMeta,
+) -> bool {
+    if old.generated_sql == new.generated_sql {
+        return false;
+    }
+
+    diffs.push(UpdateColumnTypeModelActor::new_boxed(old, new));
+    true
+}
+
+fn try_foreign_key_actions<'a>(
+    diffs: &mut Vec<Box<dyn ModelChangeActor + 'a>>,
+    old: &'a PortableFieldMeta,
+    new: &'a PortableFieldMeta,
+) {
+    if old.foreign_key_actions == new.foreign_key_actions {
+        return;
+    }
+
+    // promotions to (and demotions from) a foreign key are handled by try_type
+    let (PortablePgType::ForeignKey { .. }, PortablePgType::ForeignKey { .. }) =
+        (&old.pg_type, &new.pg_type)
+    else {
+        return;
+    };
+
+    diffs.push(UpdateForeignKeyActionsModelActor::new_boxed(old, new));
+}
+
+fn try_index<'a>(
+    diffs: &mut Vec<Box<dyn ModelChangeActor + 'a>>,
+    old: &'a PortableFieldMeta,
+    new: &'a PortableFieldMeta,
+) {
+    if old.index_type == new.index_type {
+        return;
+    }
+
+    if old.index_type.is_some() {
+        diffs.push(helpers::diff::invert(AddIndexModelActor::new_boxed(old)));
+    }
+
+    if new.index_type.is_some() {
+        diffs.push(AddIndexModelActor::new_boxed(new));
+    }
+}
+
+// only reached when the type itself stayed the same, otherwise the column is re-created anyway
+fn try_type_modifiers<'a>(
+    diffs: &mut Vec<Box<dyn ModelChangeActor + 'a>>,
+    old: &'a PortableFieldMeta,
+    new: &'a PortableFieldMeta,
+) {
+    if old.type_modifiers != new.type_modifiers {
+        diffs.push(AlterColumnTypeModelActor::new_boxed(old, new));
+    }
+}
+
 fn try_default<'a>(
     diffs: &mut Vec<Box<dyn ModelChangeActor + 'a>>,
     old: &'a PortableFieldMeta,
@@ -204,14 +281,17 @@ fn handle_common_column<'a>(
 ) {
     assert_eq!(old_column.name, new_column.name);
 
-    if try_type(diffs, old_column, new_column) {
+    if try_type(diffs, old_column, new_column) || try_generated(diffs, old_column, new_column) {
         return;
     }
 
+    try_type_modifiers(diffs, old_column, new_column);
     try_default(diffs, old_column, new_column);
     try_nullable(diffs, old_column, new_column);
     try_serial(diffs, old_column, new_column);
     try_unique(diffs, old_column, new_column);
+    try_foreign_key_actions(diffs, old_column, new_column);
+    try_index(diffs, old_column, new_column);
 }
 
 fn try_column_rename<'a>(
@@ -254,6 +334,51 @@ fn try_column_rename<'a>(
     );
 }
 
+// composite foreign keys are virtual fields, there's no column to alter so changes re-create them
+fn try_composite_foreign_keys<'a>(
+    diffs: &mut Vec<Box<dyn ModelChangeActor + 'a>>,
+    old_table: &TableSpec<'a>,
+    new_table: &TableSpec<'a>,
+) {
+    let old_keys = old_table
+        .fields
+        .iter()
+        .filter(|field| field.flags.has_flag(FieldFlag::Virtual));
+
+    for old in old_keys {
+        let new = new_table
+            .fields
+            .iter()
+            .find(|other| other.flags.has_flag(FieldFlag::Virtual) && other.name == old.name);
+
+        if new == Some(old) {
+            continue;
+        }
+
+        diffs.push(helpers::diff::invert(
+            AddCompositeForeignKeyModelActor::new_boxed(old),
+        ));
+    }
+
+    let new_keys = new_table
+        .fields
+        .iter()
+        .filter(|field| field.flags.has_flag(FieldFlag::Virtual));
+
+    for new in new_keys {
+        let old = old_table
+            .fields
+            .iter()
+            .find(|other| other.flags.has_flag(FieldFlag::Virtual) && other.name == new.name);
+
+        if old == Some(new) {
+            continue;
+        }
+
+        diffs.push(AddCompositeForeignKeyModelActor::new_boxed(new));
+    }
+}
+
 fn handle_common_table<'a>(
     diffs: &mut Vec<Box<dyn ModelChangeActor + 'a>>,
     old_table: TableSpec<'a>,
@@ -261,22 +386,30 @@ fn handle_common_table<'a>(
 ) -> GasCliResult<()> {
     assert_eq!(old_table.name, new_table.name);
 
+    let is_column = |field: &&PortableFieldMeta| !field.flags.has_flag(FieldFlag::Virtual);
+
     let new_columns: Vec<_> = new_table
         .fields
         .iter()
+        .filter(is_column)
         .filter(|field| {
             !old_table
                 .fields
                 .iter()
+                .filter(is_column)
                 .any(|other| other.name == field.name)
         })
         .collect();
 
-    let (old_columns, common_columns): (Vec<_>, Vec<_>) =
-        old_table.fields.iter().partition_map(|field| {
+    let (old_columns, common_columns): (Vec<_>, Vec<_>) = old_table
+        .fields
+        .iter()
+        .filter(is_column)
+        .partition_map(|field| {
             match new_table
                 .fields
                 .iter()
+                .filter(is_column)
                 .find(|other| other.name == field.name)
             {
                 Some(new_field) => Either::Right((field, new_field)),
@@ -307,6 +440,7 @@ fn handle_common_table<'a>(
             .map(helpers::diff::invert),
     );
 
+    try_composite_foreign_keys(diffs, &old_table, &new_table);
     try_primary_key(diffs, old_table.clone(), new_table)?;
 
     for (old, new) in column_split.common {
@@ -431,12 +565,127 @@ fn handle_tables<'a>(
         .collect()
 }
 
+// enum types come from the columns using them, a type used by several tables has to match
+fn collect_enum_types(fields: &BinaryFields) -> GasCliResult<BTreeMap<&str, &PortablePgType>> {
+    let mut enum_types: BTreeMap<&str, &PortablePgType> = BTreeMap::new();
+
+    for field in fields.values().flatten() {
+        let PortablePgType::Enum { ref name, .. } = field.pg_type else {
+            continue;
+        };
+
+        match enum_types.get(name.as_ref()) {
+            Some(existing) if **existing != field.pg_type => {
+                return Err(GasCliError::MigrationsGenerationError {
+                    reason: Cow::from(format!(
+                        "enum type {} is declared with different values",
+                        name.as_ref()
+                    )),
+                });
+            }
+            Some(_) => {}
+            None => {
+                enum_types.insert(name.as_ref(), &field.pg_type);
+            }
+        }
+    }
+
+    Ok(enum_types)
+}
+
+// postgres can only add values to an enum type, the old ones have to stay in the same order
+fn try_enum_values<'a>(
+    diffs: &mut Vec<Box<dyn ModelChangeActor + 'a>>,
+    name: &'a str,
+    old: &'a PortablePgType,
+    new: &'a PortablePgType,
+) -> GasCliResult<()> {
+    let (Some(old_values), Some(new_values)) = (old.enum_variants(), new.enum_variants()) else {
+        return Ok(());
+    };
+
+    let old_values: Vec<_> = old_values.collect();
+    let new_values: Vec<_> = new_values.collect();
+
+    let kept: Vec<_> = new_values
+        .iter()
+        .filter(|value| old_values.contains(value))
+        .collect();
+
+    if kept.len() != old_values.len() || kept.into_iter().zip(&old_values).any(|(a, b)| a != b) {
+        return Err(GasCliError::MigrationsGenerationError {
+            reason: Cow::from(format!(
+                "values of enum type {} can only be added, postgres can't remove or reorder them",
+                name
+            )),
+        });
+    }
+
+    let added: Vec<_> = new_values
+        .iter()
+        .enumerate()
+        .filter(|(_, value)| !old_values.contains(value))
+        .map(|(index, value)| {
+            let before = new_values[index..]
+                .iter()
+                .find(|other| old_values.contains(other))
+                .copied();
+
+            (*value, before)
+        })
+        .collect();
+
+    if added.is_empty() {
+        return Ok(());
+    }
+
+    // ADD VALUE runs in the transaction of the migration, postgres only allows that since 12
+    //  and the new values can't be used before it commits
+    println!(
+        "{} {}: Values added to an enum type can't be removed by the backward migration, \
+         they need PostgreSQL 12+ and can only be used (defaults, updates, ...) from the next migration on",
+        STYLE_WARN.apply_to("WARNING"),
+        STYLE_WARN_SOFT.apply_to(format!("({})", name))
+    );
+
+    diffs.push(AddEnumValuesModelActor::new_boxed(name, added));
+    Ok(())
+}
+
+fn handle_enum_types<'a>(
+    diffs: &mut Vec<Box<dyn ModelChangeActor + 'a>>,
+    state_fields: &'a BinaryFields,
+    manifest: &'a GasManifest,
+) -> GasCliResult<()> {
+    let new_types = collect_enum_types(state_fields)?;
+    let old_types = collect_enum_types(&manifest.state)?;
+
+    for (name, pg_type) in &new_types {
+        match old_types.get(name) {
+            Some(old) => try_enum_values(diffs, name, old, pg_type)?,
+            None => diffs.push(CreateEnumTypeModelActor::new_boxed(name, pg_type)),
+        }
+    }
+
+    for (name, pg_type) in &old_types {
+        if !new_types.contains_key(name) {
+            diffs.push(helpers::diff::invert(CreateEnumTypeModelActor::new_boxed(
+                name, pg_type,
+            )));
+        }
+    }
+
+    Ok(())
+}
+
 pub fn find_diffs<'a>(
     state_fields: &'a BinaryFields,
     manifest: &'a GasManifest,
 ) -> GasCliResult<Vec<Box<dyn ModelChangeActor + 'a>>> {
     let mut result: Vec<Box<dyn ModelChangeActor>> = Vec::new();
 
+    handle_enum_types(&mut result, state_fields, manifest)?;
+
     let common_tables = handle_tables(&mut result, state_fields, manifest);
 
     for (old, new) in common_tables {
@@ -445,3 +694,390 @@ pub fn find_diffs<'a>(
 
     Ok(result)
 }
+
+#[cfg(test)]
+mod test {
+    use crate::binary::BinaryFields;
+    use crate::manifest::GasManifest;
+    use crate::sync::helpers::diff::{find_and_collect_diffs, invert};
+    use crate::sync::variants::update_foreign_key_actions::UpdateForeignKeyActionsModelActor;
+    use crate::sync::{FieldState, MigrationScript};
+    use gas_shared::link::PortableFieldMeta;
+
+    // fields as the binary reports them, see derive.rs
+    macro_rules! state {
+        ($($field:expr),* $(,)?) => {{
+            let mut fields = BinaryFields::new();
+            $(
+                let field = PortableFieldMeta::from_unchecked($field.meta);
+                fields
+                    .entry(field.table_name.as_ref().to_string())
+                    .or_default()
+                    .push(field);
+            )*
+            fields
+        }};
+    }
+
+    fn migration(old: BinaryFields, new: BinaryFields) -> MigrationScript {
+        find_and_collect_diffs(&new, &GasManifest::new(old), |_| {})
+            .unwrap()
+            .expect("there should be changes")
+    }
+
+    mod cascade {
+        use gas::Relation;
+
+        #[gas::model(table_name = "documents", exclude_link_meta = true)]
+        pub struct Document {
+            #[primary_key]
+            #[serial]
+            pub id: i64,
+            pub title: String,
+        }
+
+        #[gas::model(table_name = "comments", exclude_link_meta = true)]
+        pub struct Comment {
+            #[primary_key]
+            #[serial]
+            pub id: i64,
+            #[relation(field = document::id, on_delete = "cascade")]
+            pub document: Relation<i64, document::Model>,
+        }
+    }
+
+    mod restrict {
+        use gas::Relation;
+
+        #[gas::model(table_name = "documents", exclude_link_meta = true)]
+        pub struct Document {
+            #[primary_key]
+            #[serial]
+            pub id: i64,
+            pub title: String,
+        }
+
+        #[gas::model(table_name = "comments", exclude_link_meta = true)]
+        pub struct Comment {
+            #[primary_key]
+            #[serial]
+            pub id: i64,
+            #[relation(field = document::id, on_delete = "restrict")]
+            pub document: Relation<i64, document::Model>,
+        }
+    }
+
+    #[test]
+    fn test_foreign_key_actions() {
+        let script = migration(
+            state![
+                cascade::document::id,
+                cascade::document::title,
+                cascade::comment::id,
+                cascade::comment::document
+            ],
+            state![
+                restrict::document::id,
+                restrict::document::title,
+                restrict::comment::id,
+                restrict::comment::document
+            ],
+        );
+
+        assert_eq!(
+            script.forward,
+            "ALTER TABLE comments DROP CONSTRAINT comments_document_fkey;\n\
+             ALTER TABLE comments ADD FOREIGN KEY(document) REFERENCES documents(id) ON DELETE RESTRICT;\n"
+        );
+        assert_eq!(
+            script.backward,
+            "ALTER TABLE comments DROP CONSTRAINT comments_document_fkey;\n\
+             ALTER TABLE comments ADD FOREIGN KEY(document) REFERENCES documents(id) ON DELETE CASCADE;\n"
+        );
+
+        // both directions need the columns
+        let old = PortableFieldMeta::from_unchecked(cascade::comment::document.meta);
+        let new = PortableFieldMeta::from_unchecked(restrict::comment::document.meta);
+        let inverted = invert(UpdateForeignKeyActionsModelActor::new_boxed(&old, &new));
+        assert_eq!(inverted.depends_on().len(), 2);
+        assert!(inverted
+            .depends_on()
+            .iter()
+            .all(|it| it.state == FieldState::Existing));
+    }
+
+    mod loose_lines {
+        #[gas::model(table_name = "orders", exclude_link_meta = true)]
+        pub struct Order {
+            #[primary_key]
+            pub tenant_id: i64,
+            #[primary_key]
+            pub order_no: i64,
+        }
+
+        #[gas::model(table_name = "order_lines", exclude_link_meta = true)]
+        pub struct OrderLine {
+            #[primary_key]
+            #[serial]
+            pub id: i64,
+            pub tenant_id: i64,
+            pub order_no: i64,
+        }
+    }
+
+    mod linked_lines {
+        use gas::Relation;
+
+        #[gas::model(table_name = "orders", exclude_link_meta = true)]
+        pub struct Order {
+            #[primary_key]
+            pub tenant_id: i64,
+            #[primary_key]
+            pub order_no: i64,
+        }
+
+        #[gas::model(table_name = "order_lines", exclude_link_meta = true)]
+        pub struct OrderLine {
+            #[primary_key]
+            #[serial]
+            pub id: i64,
+            pub tenant_id: i64,
+            pub order_no: i64,
+            #[relation(
+                fields = (tenant_id, order_no),
+                references = (order::tenant_id, order::order_no),
+                on_delete = "cascade"
+            )]
+            pub order: Relation<(i64, i64), order::Model>,
+        }
+    }
+
+    #[test]
+    fn test_composite_foreign_key() {
+        let loose = || {
+            state![
+                loose_lines::order::tenant_id,
+                loose_lines::order::order_no,
+                loose_lines::order_line::id,
+                loose_lines::order_line::tenant_id,
+                loose_lines::order_line::order_no,
+            ]
+        };
+        let linked = || {
+            state![
+                linked_lines::order::tenant_id,
+                linked_lines::order::order_no,
+                linked_lines::order_line::id,
+                linked_lines::order_line::tenant_id,
+                linked_lines::order_line::order_no,
+                linked_lines::order_line::order,
+            ]
+        };
+
+        let script = migration(loose(), linked());
+        assert_eq!(
+            script.forward,
+            "ALTER TABLE order_lines ADD CONSTRAINT order_lines_order_fkey \
+             FOREIGN KEY (tenant_id, order_no) REFERENCES orders(tenant_id, order_no) ON DELETE CASCADE;\n"
+        );
+        assert_eq!(
+            script.backward,
+            "ALTER TABLE order_lines DROP CONSTRAINT order_lines_order_fkey;\n"
+        );
+
+        let script = migration(linked(), loose());
+        assert_eq!(
+            script.forward,
+            "ALTER TABLE order_lines DROP CONSTRAINT order_lines_order_fkey;\n"
+        );
+
+        // created inline, after the table it points to
+        let script = migration(BinaryFields::new(), linked());
+        let orders = script.forward.find("CREATE TABLE IF NOT EXISTS orders(");
+        let lines = script
+            .forward
+            .find("CREATE TABLE IF NOT EXISTS order_lines(");
+        assert!(orders.is_some() && orders < lines);
+        assert!(script.forward.contains(
+            "\tCONSTRAINT order_lines_order_fkey FOREIGN KEY (tenant_id, order_no) \
+             REFERENCES orders(tenant_id, order_no) ON DELETE CASCADE,\n"
+        ));
+    }
+
+    mod two_statuses {
+        #[derive(gas::PgEnum, Clone, Copy, Debug, Default, PartialEq)]
+        #[pg_enum(name = "order_status")]
+        pub enum OrderStatus {
+            #[default]
+            Pending,
+            Delivered,
+        }
+
+        #[gas::model(table_name = "orders", exclude_link_meta = true)]
+        pub struct Order {
+            #[primary_key]
+            #[serial]
+            pub id: i64,
+            pub status: OrderStatus,
+        }
+    }
+
+    mod four_statuses {
+        #[derive(gas::PgEnum, Clone, Copy, Debug, Default, PartialEq)]
+        #[pg_enum(name = "order_status")]
+        pub enum OrderStatus {
+            #[default]
+            Pending,
+            #[pg_enum(rename = "sent")]
+            Shipped,
+            Delivered,
+            #[pg_enum(rename = "customer's")]
+            Returned,
+        }
+
+        #[gas::model(table_name = "orders", exclude_link_meta = true)]
+        pub struct Order {
+            #[primary_key]
+            #[serial]
+            pub id: i64,
+            pub status: OrderStatus,
+        }
+    }
+
+    #[test]
+    fn test_enum_values() {
+        let script = migration(
+            state![two_statuses::order::id, two_statuses::order::status],
+            state![four_statuses::order::id, four_statuses::order::status],
+        );
+
+        // in order, a statement each, the runner splits on ;
+        assert_eq!(
+            script.forward,
+            "ALTER TYPE order_status ADD VALUE 'sent' BEFORE 'delivered';\n\
+             ALTER TYPE order_status ADD VALUE 'customer''s';\n"
+        );
+        assert!(script
+            .backward
+            .starts_with("-- postgres can't remove values"));
+
+        // values can't be removed
+        let old = state![four_statuses::order::id, four_statuses::order::status];
+        let new = state![two_statuses::order::id, two_statuses::order::status];
+        assert!(find_and_collect_diffs(&new, &GasManifest::new(old), |_| {}).is_err());
+    }
+
+    mod short_codes {
+        #[gas::model(table_name = "ledger_entries", exclude_link_meta = true)]
+        pub struct LedgerEntry {
+            #[primary_key]
+            #[serial]
+            pub id: i64,
+            #[column(max_length = 32)]
+            pub code: String,
+            #[column(precision = 18, scale = 4)]
+            pub amount: gas::types::Decimal,
+        }
+    }
+
+    mod long_codes {
+        #[gas::model(table_name = "ledger_entries", exclude_link_meta = true)]
+        pub struct LedgerEntry {
+            #[primary_key]
+            #[serial]
+            pub id: i64,
+            #[column(max_length = 255)]
+            pub code: String,
+            #[column(precision = 20, scale = 6)]
+            pub amount: gas::types::Decimal,
+        }
+    }
+
+    #[test]
+    fn test_type_modifiers() {
+        let short = || {
+            state![
+                short_codes::ledger_entry::id,
+                short_codes::ledger_entry::code,
+                short_codes::ledger_entry::amount,
+            ]
+        };
+        let long = || {
+            state![
+                long_codes::ledger_entry::id,
+                long_codes::ledger_entry::code,
+                long_codes::ledger_entry::amount,
+            ]
+        };
+
+        // altered in place, the data stays
+        let script = migration(short(), long());
+        assert_eq!(
+            script.forward,
+            "ALTER TABLE ledger_entries ALTER COLUMN code TYPE VARCHAR(255);\n\
+             ALTER TABLE ledger_entries ALTER COLUMN amount TYPE NUMERIC(20, 6);\n"
+        );
+        assert_eq!(
+            script.backward,
+            "ALTER TABLE ledger_entries ALTER COLUMN amount TYPE NUMERIC(18, 4);\n\
+             ALTER TABLE ledger_entries ALTER COLUMN code TYPE VARCHAR(32);\n"
+        );
+    }
+
+    mod unindexed {
+        #[gas::model(table_name = "articles", exclude_link_meta = true)]
+        pub struct Article {
+            #[primary_key]
+            #[serial]
+            pub id: i64,
+            pub search: Option<gas::types::TsVector>,
+        }
+    }
+
+    mod indexed {
+        #[gas::model(table_name = "articles", exclude_link_meta = true)]
+        pub struct Article {
+            #[primary_key]
+            #[serial]
+            pub id: i64,
+            #[index(using = "gin")]
+            pub search: Option<gas::types::TsVector>,
+        }
+    }
+
+    #[test]
+    fn test_indexes() {
+        let unindexed = || state![unindexed::article::id, unindexed::article::search];
+        let indexed = || state![indexed::article::id, indexed::article::search];
+
+        let script = migration(unindexed(), indexed());
+        assert_eq!(
+            script.forward,
+            "CREATE INDEX articles_search_idx ON articles USING GIN (search);\n"
+        );
+        assert_eq!(script.backward, "DROP INDEX articles_search_idx;\n");
+
+        let script = migration(indexed(), unindexed());
+        assert_eq!(script.forward, "DROP INDEX articles_search_idx;\n");
+
+        // created right after the column or table it's on
+        let script = migration(state![indexed::article::id], indexed());
+        assert_eq!(
+            script.forward,
+            "ALTER TABLE articles ADD COLUMN search TSVECTOR;\n\
+             CREATE INDEX articles_search_idx ON articles USING GIN (search);\n"
+        );
+
+        let script = migration(BinaryFields::new(), indexed());
+        assert_eq!(
+            script.forward,
+            "CREATE TABLE IF NOT EXISTS articles(\n\
+             \tid BIGSERIAL NOT NULL,\n\
+             \tsearch TSVECTOR,\n\
+             \tPRIMARY KEY (id)\n\
+             );\n\
+             CREATE INDEX articles_search_idx ON articles USING GIN (search);\n"
+        );
+        assert_eq!(script.backward, "DROP TABLE articles;\n");
+    }
+}
@@ -30,6 +30,10 @@ fn make_graph<'a>(
     // make sure all previous fields are here
     for (table_name, fields) in &manifest.state {
         for field in fields {
+            if let Some(enum_type) = FieldDependency::of_enum_column(field) {
+                provides_map.insert(enum_type, vec![]);
+            }
+
             provides_map.insert(
                 FieldDependency {
                     table_name,
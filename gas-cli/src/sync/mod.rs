@@ -1,5 +1,6 @@
 use crate::error::GasCliResult;
 use crate::util::sql_query::SqlQuery;
+use gas_shared::link::{PortableFieldMeta, PortablePgType};
 use std::fmt::Display;
 
 pub mod diff;
@@ -35,6 +36,27 @@ pub struct FieldDependency<'a> {
     pub state: FieldState,
 }
 
+impl<'a> FieldDependency<'a> {
+    // enum types aren't columns, they're tracked under their own name without one
+    //  (types and tables share a namespace in postgres, so it can't clash with a table)
+    pub fn enum_type(name: &'a str, state: FieldState) -> Self {
+        FieldDependency {
+            table_name: name,
+            name: "",
+            state,
+        }
+    }
+
+    // the enum type a column needs, if it has one
+    pub fn of_enum_column(field: &'a PortableFieldMeta) -> Option<Self> {
+        let PortablePgType::Enum { ref name, .. } = field.pg_type else {
+            return None;
+        };
+
+        Some(Self::enum_type(name.as_ref(), FieldState::Existing))
+    }
+}
+
 pub trait ModelChangeActor: Display {
     fn forward_sql(&self) -> GasCliResult<SqlQuery>;
 
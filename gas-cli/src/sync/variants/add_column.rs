@@ -30,7 +30,10 @@ impl<'a> Display for AddColumnModelActor<'a> {
 
 impl<'a> ModelChangeActor for AddColumnModelActor<'a> {
     fn forward_sql(&self) -> GasCliResult<SqlQuery> {
-        if !self.field.flags.has_flag(FieldFlag::Nullable) && self.field.default_sql.is_none() {
+        if !self.field.flags.has_flag(FieldFlag::Nullable)
+            && self.field.default_sql.is_none()
+            && self.field.generated_sql.is_none()
+        {
             return Err(GasCliError::MigrationsGenerationError {
                 reason: Cow::from(
                     "can not add a not null column without a defined default behaviour",
@@ -47,6 +50,12 @@ impl<'a> ModelChangeActor for AddColumnModelActor<'a> {
 
         sql.push_str(&util::sql_query::gen_column_descriptor_sql(field));
 
+        // dropping the column drops the index as well, so backward_sql doesn't care
+        if let Some(index_sql) = util::sql_query::gen_create_index_sql(field) {
+            sql.push_str(";\n");
+            sql.push_str(&index_sql);
+        }
+
         Ok(sql)
     }
 
@@ -71,6 +80,10 @@ impl<'a> ModelChangeActor for AddColumnModelActor<'a> {
     }
 
     fn depends_on(&self) -> Box<[FieldDependency<'_>]> {
+        if let Some(enum_type) = FieldDependency::of_enum_column(self.field) {
+            return Box::from([enum_type]);
+        }
+
         if !self.field.flags.has_flag(FieldFlag::ForeignKey) {
             return Box::from([]);
         }
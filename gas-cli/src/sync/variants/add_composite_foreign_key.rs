@@ -0,0 +1,83 @@
+use crate::error::GasCliResult;
+use crate::sync::{FieldDependency, FieldState, ModelChangeActor};
+use crate::util::sql_query::{gen_constraint_name, SqlQuery};
+use gas_shared::link::{PortableFieldMeta, PortablePgType};
+use std::fmt::{Display, Formatter};
+
+// the field is the virtual relation field, its name is used for the constraint name
+pub struct AddCompositeForeignKeyModelActor<'a> {
+    field: &'a PortableFieldMeta,
+}
+
+impl<'a> AddCompositeForeignKeyModelActor<'a> {
+    pub fn new_boxed(field: &'a PortableFieldMeta) -> Box<dyn ModelChangeActor + 'a> {
+        Box::new(AddCompositeForeignKeyModelActor { field })
+    }
+}
+
+impl<'a> Display for AddCompositeForeignKeyModelActor<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "AddCompositeForeignKey[{}.{}]",
+            self.field.table_name.as_ref(),
+            self.field.name.as_ref()
+        )
+    }
+}
+
+impl<'a> ModelChangeActor for AddCompositeForeignKeyModelActor<'a> {
+    fn forward_sql(&self) -> GasCliResult<SqlQuery> {
+        let Some(constraint_sql) = self.field.pg_type.as_constraint_sql() else {
+            unreachable!("field should be a composite foreign key but is not")
+        };
+
+        Ok(format!(
+            "ALTER TABLE {} ADD CONSTRAINT {} {}{}",
+            self.field.table_name.as_ref(),
+            gen_constraint_name(self.field),
+            constraint_sql,
+            self.field.foreign_key_actions.as_sql(),
+        ))
+    }
+
+    fn backward_sql(&self) -> GasCliResult<SqlQuery> {
+        Ok(format!(
+            "ALTER TABLE {} DROP CONSTRAINT {}",
+            self.field.table_name.as_ref(),
+            gen_constraint_name(self.field),
+        ))
+    }
+
+    fn depends_on(&self) -> Box<[FieldDependency<'_>]> {
+        let PortablePgType::CompositeForeignKey {
+            ref columns,
+            ref target_table_name,
+            ref target_columns,
+        } = self.field.pg_type
+        else {
+            unreachable!("field should be a composite foreign key but is not")
+        };
+
+        let own = columns.as_ref().split(", ").map(|name| FieldDependency {
+            table_name: self.field.table_name.as_ref(),
+            name,
+            state: FieldState::Existing,
+        });
+
+        let target = target_columns
+            .as_ref()
+            .split(", ")
+            .map(|name| FieldDependency {
+                table_name: target_table_name.as_ref(),
+                name,
+                state: FieldState::Existing,
+            });
+
+        own.chain(target).collect()
+    }
+
+    fn depends_on_inverted(&self) -> Box<[FieldDependency<'_>]> {
+        Box::from([])
+    }
+}
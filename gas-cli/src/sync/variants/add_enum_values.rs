@@ -0,0 +1,71 @@
+use crate::error::GasCliResult;
+use crate::sync::{FieldDependency, FieldState, ModelChangeActor};
+use crate::util::sql_query::SqlQuery;
+use gas_shared::internals::enum_label_sql;
+use itertools::Itertools;
+use std::fmt::{Display, Formatter};
+
+// label, and the existing label it goes before (None appends it)
+pub type EnumValuePosition<'a> = (&'a str, Option<&'a str>);
+
+pub struct AddEnumValuesModelActor<'a> {
+    name: &'a str,
+    values: Vec<EnumValuePosition<'a>>,
+}
+
+impl<'a> AddEnumValuesModelActor<'a> {
+    pub fn new_boxed(
+        name: &'a str,
+        values: Vec<EnumValuePosition<'a>>,
+    ) -> Box<dyn ModelChangeActor + 'a> {
+        Box::new(AddEnumValuesModelActor { name, values })
+    }
+}
+
+impl<'a> Display for AddEnumValuesModelActor<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "AddEnumValues[{}: {}]",
+            self.name,
+            self.values.iter().map(|(label, _)| label).join(", ")
+        )
+    }
+}
+
+impl<'a> ModelChangeActor for AddEnumValuesModelActor<'a> {
+    // one statement per value, in order, so appended values keep their order
+    fn forward_sql(&self) -> GasCliResult<SqlQuery> {
+        Ok(self
+            .values
+            .iter()
+            .map(|(label, before)| {
+                let mut sql = format!(
+                    "ALTER TYPE {} ADD VALUE {}",
+                    self.name,
+                    enum_label_sql(label)
+                );
+
+                if let Some(before) = before {
+                    sql.push_str(" BEFORE ");
+                    sql.push_str(&enum_label_sql(before));
+                }
+
+                sql
+            })
+            .join(";\n"))
+    }
+
+    // NOTE: postgres can't remove a value from an enum type, rows may be using it by then anyway
+    fn backward_sql(&self) -> GasCliResult<SqlQuery> {
+        Ok(format!(
+            "-- postgres can't remove values from an enum type, {} stay in {}",
+            self.values.iter().map(|(label, _)| label).join(", "),
+            self.name
+        ))
+    }
+
+    fn depends_on(&self) -> Box<[FieldDependency<'_>]> {
+        Box::from([FieldDependency::enum_type(self.name, FieldState::Existing)])
+    }
+}
@@ -37,11 +37,12 @@ impl<'a> ModelChangeActor for AddForeignKeyModelActor<'a> {
         };
 
         Ok(format!(
-            "ALTER TABLE {} ADD FOREIGN KEY({}) REFERENCES {}({})",
+            "ALTER TABLE {} ADD FOREIGN KEY({}) REFERENCES {}({}){}",
             self.field.table_name.as_ref(),
             self.field.name.as_ref(),
             target_table_name.as_ref(),
             target_column_name.as_ref(),
+            self.field.foreign_key_actions.as_sql(),
         ))
     }
 
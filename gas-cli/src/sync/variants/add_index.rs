@@ -0,0 +1,59 @@
+use crate::error::{GasCliError, GasCliResult};
+use crate::sync::{FieldDependency, FieldState, ModelChangeActor};
+use crate::util;
+use crate::util::sql_query::SqlQuery;
+use gas_shared::link::PortableFieldMeta;
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+
+pub struct AddIndexModelActor<'a> {
+    field: &'a PortableFieldMeta,
+}
+
+impl<'a> AddIndexModelActor<'a> {
+    pub fn new_boxed(field: &'a PortableFieldMeta) -> Box<dyn ModelChangeActor + 'a> {
+        Box::new(AddIndexModelActor { field })
+    }
+}
+
+impl<'a> Display for AddIndexModelActor<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "AddIndex[{}.{}]",
+            self.field.table_name.as_ref(),
+            self.field.name.as_ref()
+        )
+    }
+}
+
+impl<'a> ModelChangeActor for AddIndexModelActor<'a> {
+    fn forward_sql(&self) -> GasCliResult<SqlQuery> {
+        util::sql_query::gen_create_index_sql(self.field).ok_or_else(|| {
+            GasCliError::MigrationsGenerationError {
+                reason: Cow::from("can not create an index for a field without an index type"),
+            }
+        })
+    }
+
+    // NOTE: the index name is derived from the table and column name,
+    //  renaming either of them keeps the old index name
+    fn backward_sql(&self) -> GasCliResult<SqlQuery> {
+        Ok(format!(
+            "DROP INDEX {}",
+            util::sql_query::gen_index_name(self.field)
+        ))
+    }
+
+    fn depends_on(&self) -> Box<[FieldDependency<'_>]> {
+        Box::from([FieldDependency {
+            table_name: self.field.table_name.as_ref(),
+            name: self.field.name.as_ref(),
+            state: FieldState::Existing,
+        }])
+    }
+
+    fn depends_on_inverted(&self) -> Box<[FieldDependency<'_>]> {
+        self.depends_on()
+    }
+}
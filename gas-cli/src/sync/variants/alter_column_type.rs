@@ -0,0 +1,67 @@
+use crate::error::GasCliResult;
+use crate::sync::{FieldDependency, FieldState, ModelChangeActor};
+use crate::util::sql_query::SqlQuery;
+use gas_shared::link::PortableFieldMeta;
+use std::fmt::{Display, Formatter};
+
+// same type with different modifiers (e.g. VARCHAR(64) -> VARCHAR(255)), altered in place
+//  unlike UpdateColumnType this keeps the data, postgres casts it
+pub struct AlterColumnTypeModelActor<'a> {
+    old_field: &'a PortableFieldMeta,
+    field: &'a PortableFieldMeta,
+}
+
+impl<'a> AlterColumnTypeModelActor<'a> {
+    pub fn new_boxed(
+        old_field: &'a PortableFieldMeta,
+        field: &'a PortableFieldMeta,
+    ) -> Box<dyn ModelChangeActor + 'a> {
+        Box::new(AlterColumnTypeModelActor { old_field, field })
+    }
+
+    fn alter_sql(field: &PortableFieldMeta) -> SqlQuery {
+        format!(
+            "ALTER TABLE {} ALTER COLUMN {} TYPE {}",
+            field.table_name.as_ref(),
+            field.name.as_ref(),
+            field.column_sql_type()
+        )
+    }
+}
+
+impl<'a> Display for AlterColumnTypeModelActor<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "AlterColumnType[{}.{}]",
+            self.field.table_name.as_ref(),
+            self.field.name.as_ref()
+        )
+    }
+}
+
+impl<'a> ModelChangeActor for AlterColumnTypeModelActor<'a> {
+    fn forward_sql(&self) -> GasCliResult<SqlQuery> {
+        Ok(Self::alter_sql(self.field))
+    }
+
+    fn backward_sql(&self) -> GasCliResult<SqlQuery> {
+        Ok(Self::alter_sql(self.old_field))
+    }
+
+    fn provides(&self) -> Box<[FieldDependency<'_>]> {
+        Box::from([])
+    }
+
+    fn depends_on(&self) -> Box<[FieldDependency<'_>]> {
+        Box::from([FieldDependency {
+            table_name: self.field.table_name.as_ref(),
+            name: self.field.name.as_ref(),
+            state: FieldState::Existing,
+        }])
+    }
+
+    fn depends_on_inverted(&self) -> Box<[FieldDependency<'_>]> {
+        self.depends_on()
+    }
+}
@@ -0,0 +1,41 @@
+use crate::error::{GasCliError, GasCliResult};
+use crate::sync::{FieldDependency, FieldState, ModelChangeActor};
+use crate::util::sql_query::SqlQuery;
+use gas_shared::link::PortablePgType;
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+
+pub struct CreateEnumTypeModelActor<'a> {
+    name: &'a str,
+    pg_type: &'a PortablePgType,
+}
+
+impl<'a> CreateEnumTypeModelActor<'a> {
+    pub fn new_boxed(name: &'a str, pg_type: &'a PortablePgType) -> Box<dyn ModelChangeActor + 'a> {
+        Box::new(CreateEnumTypeModelActor { name, pg_type })
+    }
+}
+
+impl<'a> Display for CreateEnumTypeModelActor<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CreateEnumType[{}]", self.name)
+    }
+}
+
+impl<'a> ModelChangeActor for CreateEnumTypeModelActor<'a> {
+    fn forward_sql(&self) -> GasCliResult<SqlQuery> {
+        self.pg_type
+            .as_create_type_sql()
+            .ok_or_else(|| GasCliError::MigrationsGenerationError {
+                reason: Cow::from("can not create an enum type out of a non enum column"),
+            })
+    }
+
+    fn backward_sql(&self) -> GasCliResult<SqlQuery> {
+        Ok(format!("DROP TYPE {}", self.name))
+    }
+
+    fn provides(&self) -> Box<[FieldDependency<'_>]> {
+        Box::from([FieldDependency::enum_type(self.name, FieldState::Existing)])
+    }
+}
@@ -49,18 +49,36 @@ impl<'a> ModelChangeActor for CreateTableModelActor<'a> {
 
         let mut primary_keys: Vec<String> = Vec::new();
 
-        for (index, field) in self.fields.iter().enumerate() {
+        // virtual fields are composite foreign keys, those go after the columns
+        let (constraints, columns): (Vec<_>, Vec<_>) = self
+            .fields
+            .iter()
+            .partition(|field| field.flags.has_flag(FieldFlag::Virtual));
+
+        for (index, field) in columns.iter().enumerate() {
             if field.flags.has_flag(FieldFlag::PrimaryKey) {
                 primary_keys.push(String::from(&field.name))
             }
 
             sql.push_str(&util::sql_query::gen_column_descriptor_sql(field));
 
-            if index < self.fields.len() - 1 {
+            if index < columns.len() - 1 {
                 sql.push_str(",\n\t");
             }
         }
 
+        for field in constraints {
+            let Some(constraint_sql) = field.pg_type.as_constraint_sql() else {
+                continue;
+            };
+
+            sql.push_str(",\n\tCONSTRAINT ");
+            sql.push_str(&util::sql_query::gen_constraint_name(field));
+            sql.push(' ');
+            sql.push_str(&constraint_sql);
+            sql.push_str(&field.foreign_key_actions.as_sql());
+        }
+
         if !primary_keys.is_empty() {
             sql.push_str(",\n\t");
 
@@ -74,6 +92,15 @@ impl<'a> ModelChangeActor for CreateTableModelActor<'a> {
 
         sql.push(')');
 
+        for index_sql in self
+            .fields
+            .iter()
+            .filter_map(util::sql_query::gen_create_index_sql)
+        {
+            sql.push_str(";\n");
+            sql.push_str(&index_sql);
+        }
+
         Ok(sql)
     }
 
@@ -96,6 +123,24 @@ impl<'a> ModelChangeActor for CreateTableModelActor<'a> {
         let mut dependencies = Vec::new();
 
         for field in self.fields.iter() {
+            dependencies.extend(FieldDependency::of_enum_column(field));
+
+            if let PortablePgType::CompositeForeignKey {
+                ref target_table_name,
+                ref target_columns,
+                ..
+            } = field.pg_type
+            {
+                dependencies.extend(target_columns.as_ref().split(", ").map(|name| {
+                    FieldDependency {
+                        table_name: target_table_name.as_ref(),
+                        name,
+                        state: FieldState::Existing,
+                    }
+                }));
+                continue;
+            }
+
             if !field.flags.has_flag(FieldFlag::ForeignKey) {
                 continue;
             }
@@ -1,11 +1,17 @@
 pub mod add_column;
+pub mod add_composite_foreign_key;
 pub mod add_default;
+pub mod add_enum_values;
 pub mod add_foreign_key_constraint;
+pub mod add_index;
 pub mod add_nullable;
 pub mod add_primary_key_constraint;
 pub mod add_serial;
 pub mod add_unique_constraint;
+pub mod alter_column_type;
+pub mod create_enum_type;
 pub mod create_table;
 pub mod rename_column;
 pub mod rename_table;
 pub mod update_column_type;
+pub mod update_foreign_key_actions;
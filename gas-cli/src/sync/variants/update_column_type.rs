@@ -57,7 +57,12 @@ impl<'a> ModelChangeActor for UpdateColumnTypeModelActor<'a> {
         self.add_column_actor.provides()
     }
 
+    // the old column has to be gone before whatever it needed is (e.g. its enum type)
     fn depends_on(&self) -> Box<[FieldDependency<'_>]> {
-        self.add_column_actor.depends_on()
+        self.add_column_actor
+            .depends_on()
+            .into_iter()
+            .chain(self.drop_column_actor.depends_on())
+            .collect()
     }
 }
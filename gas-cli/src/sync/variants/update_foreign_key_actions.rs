@@ -0,0 +1,67 @@
+use crate::error::GasCliResult;
+use crate::sync::variants::add_foreign_key_constraint::AddForeignKeyModelActor;
+use crate::sync::{helpers, FieldDependency, ModelChangeActor};
+use crate::util::sql_query::SqlQuery;
+use gas_shared::link::PortableFieldMeta;
+use std::fmt::{Display, Formatter};
+
+// referential actions can't be altered in place, the constraint is dropped and added again
+pub struct UpdateForeignKeyActionsModelActor<'a> {
+    add_constraint_actor: Box<dyn ModelChangeActor + 'a>,
+    drop_constraint_actor: Box<dyn ModelChangeActor + 'a>,
+}
+
+impl<'a> UpdateForeignKeyActionsModelActor<'a> {
+    pub fn new_boxed(
+        old_field: &'a PortableFieldMeta,
+        field: &'a PortableFieldMeta,
+    ) -> Box<dyn ModelChangeActor + 'a> {
+        Box::new(UpdateForeignKeyActionsModelActor {
+            add_constraint_actor: AddForeignKeyModelActor::new_boxed(field),
+            drop_constraint_actor: helpers::diff::invert(AddForeignKeyModelActor::new_boxed(
+                old_field,
+            )),
+        })
+    }
+}
+
+impl<'a> Display for UpdateForeignKeyActionsModelActor<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "UpdateForeignKeyActions[{} then {}]",
+            self.drop_constraint_actor, self.add_constraint_actor,
+        )
+    }
+}
+
+impl<'a> ModelChangeActor for UpdateForeignKeyActionsModelActor<'a> {
+    fn forward_sql(&self) -> GasCliResult<SqlQuery> {
+        let mut sql = SqlQuery::new();
+
+        sql.push_str(&self.drop_constraint_actor.forward_sql()?);
+        sql.push_str(";\n");
+        sql.push_str(&self.add_constraint_actor.forward_sql()?);
+
+        Ok(sql)
+    }
+
+    fn backward_sql(&self) -> GasCliResult<SqlQuery> {
+        let mut sql = SqlQuery::new();
+
+        sql.push_str(&self.add_constraint_actor.backward_sql()?);
+        sql.push_str(";\n");
+        sql.push_str(&self.drop_constraint_actor.backward_sql()?);
+
+        Ok(sql)
+    }
+
+    fn depends_on(&self) -> Box<[FieldDependency<'_>]> {
+        self.add_constraint_actor.depends_on()
+    }
+
+    // the columns stay either way, only the constraint is swapped
+    fn depends_on_inverted(&self) -> Box<[FieldDependency<'_>]> {
+        self.add_constraint_actor.depends_on()
+    }
+}
@@ -8,13 +8,12 @@ pub type SqlQuery = String;
 pub fn gen_column_descriptor_sql(field: &PortableFieldMeta) -> SqlQuery {
     let mut sql = SqlQuery::new();
 
-    let sql_type: Cow<'_, str> = field
-        .pg_type
-        .as_sql_type(field.flags.has_flag(FieldFlag::Serial));
+    let sql_type: Cow<'_, str> = field.column_sql_type();
 
     sql.push_str(field.name.as_ref());
     sql.push(' ');
     sql.push_str(&sql_type);
+    sql.push_str(&field.foreign_key_actions.as_sql());
 
     if !field.flags.has_flag(FieldFlag::Nullable) {
         sql.push_str(" NOT NULL");
@@ -24,6 +23,12 @@ pub fn gen_column_descriptor_sql(field: &PortableFieldMeta) -> SqlQuery {
         sql.push_str(" UNIQUE");
     }
 
+    if let Some(ref generated_sql) = field.generated_sql {
+        sql.push_str(" GENERATED ALWAYS AS (");
+        sql.push_str(generated_sql.as_ref());
+        sql.push_str(") STORED");
+    }
+
     if let Some(ref default_sql) = field.default_sql {
         sql.push_str(" DEFAULT (");
         sql.push_str(default_sql.as_ref());
@@ -32,3 +37,24 @@ pub fn gen_column_descriptor_sql(field: &PortableFieldMeta) -> SqlQuery {
 
     sql
 }
+
+// composite foreign keys are named explicitly, single ones get the same name from postgres
+pub fn gen_constraint_name(field: &PortableFieldMeta) -> String {
+    format!("{}_{}_fkey", field.table_name.as_ref(), field.name.as_ref())
+}
+
+pub fn gen_index_name(field: &PortableFieldMeta) -> String {
+    format!("{}_{}_idx", field.table_name.as_ref(), field.name.as_ref())
+}
+
+pub fn gen_create_index_sql(field: &PortableFieldMeta) -> Option<SqlQuery> {
+    let index_type = field.index_type?;
+
+    Some(format!(
+        "CREATE INDEX {} ON {} USING {} ({})",
+        gen_index_name(field),
+        field.table_name.as_ref(),
+        index_type.as_sql(),
+        field.name.as_ref()
+    ))
+}
@@ -20,7 +20,7 @@ struct ModelArgs {
 #[derive(Debug, FromMeta)]
 pub struct DefaultArgs {
     #[darling(rename = "fn")]
-    expression: syn::Expr,
+    pub(crate) expression: Option<syn::Expr>,
 
     pub(crate) sql: Option<syn::LitStr>,
 }
@@ -28,7 +28,19 @@ pub struct DefaultArgs {
 #[derive(Debug, FromMeta)]
 struct RelationArgs {
     field: Option<syn::Path>,
+    fields: Option<syn::Expr>,
+    references: Option<syn::Expr>,
     inverse: Option<syn::Path>,
+    through: Option<syn::Path>,
+    from: Option<syn::Path>,
+    to: Option<syn::Path>,
+    count_of: Option<syn::Path>,
+    sort: Option<syn::Expr>,
+    limit: Option<syn::Expr>,
+    #[darling(default)]
+    lazy: bool,
+    on_delete: Option<String>,
+    on_update: Option<String>,
 }
 
 #[inline(always)]
@@ -50,7 +62,7 @@ pub(crate) fn model_impl(
 
     let mut original_struct = input.clone();
     original_struct.ident = Ident::new("Model", Span::call_site());
-    apply_relation_type_changes(&mut original_struct)?;
+    let relation_items = apply_relation_type_changes(&mut original_struct)?;
 
     let default_impl_tokens = gen_default_impl(&original_struct.fields)?;
 
@@ -63,6 +75,8 @@ pub(crate) fn model_impl(
             #[__gas_meta(#args_tokens)]
             #original_struct
 
+            #(#relation_items)*
+
             #default_impl_tokens
 
             pub fn default() -> Model {
@@ -108,44 +122,109 @@ fn apply_forward_relation(field: &mut Field, path: syn::Path) -> Result<(), syn:
     field
         .attrs
         .push(parse_quote! { #[__gas_foreign_key(<#ty as gas::RelationTypeOps>::ToNaive)] });
+    field
+        .attrs
+        .push(parse_quote! { #[__gas_foreign_key_target(#path)] });
 
     Ok(())
 }
 
-fn apply_inverse_relation(field: &mut Field, path: syn::Path) -> Result<(), syn::Error> {
-    let ty = &field.ty;
-    let (path_index, path_flags, path_sidecar, path_fk_type_alias, path_fk_remote_field_index) = {
-        let append = |mut path: syn::Path, value: &str, append: bool| {
-            let last = path.segments.last_mut();
-            let Some(last) = last else {
-                return Err(syn::Error::new(
-                    Span::call_site(),
-                    "invalid inverse relation path",
-                ));
+fn parse_referential_action(
+    field: &Field,
+    value: Option<String>,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let variant = match value.as_deref().map(str::to_lowercase).as_deref() {
+        None | Some("no_action") => "NoAction",
+        Some("restrict") => "Restrict",
+        Some("cascade") => "Cascade",
+        Some("set_default") => "SetDefault",
+        Some("set_null") => {
+            let is_optional = match &field.ty {
+                syn::Type::Path(path) => path
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|it| it.ident == "Option"),
+                _ => false,
             };
 
-            if append {
-                last.ident = Ident::new(&format!("{}_{}", last.ident, value), Span::call_site());
-            } else {
-                last.ident = Ident::new(value, Span::call_site());
+            if !is_optional {
+                Err(syn::Error::new(
+                    field.ty.span(),
+                    "set_null needs an optional relation, use Option<Relation<..>>",
+                ))?
             }
-            path.segments.insert(
-                path.segments.len() - 1,
-                PathSegment::from(Ident::new("__", Span::call_site())),
-            );
 
-            Ok(path)
-        };
+            "SetNull"
+        }
+        Some(other) => Err(syn::Error::new(
+            field.span(),
+            format!(
+                "unknown referential action `{other}`, expected one of no_action, restrict, cascade, set_null or set_default"
+            ),
+        ))?,
+    };
 
-        (
-            append(path.clone(), "index", true)?,
-            append(path.clone(), "flags", true)?,
-            append(path.clone(), "Inner", false)?,
-            append(path.clone(), "fk_type", true)?,
-            append(path.clone(), "fk_remote_index", true)?,
-        )
+    let variant = Ident::new(variant, Span::call_site());
+    Ok(quote! { gas::ReferentialAction::#variant })
+}
+
+fn apply_foreign_key_actions(
+    field: &mut Field,
+    on_delete: Option<String>,
+    on_update: Option<String>,
+) -> Result<(), syn::Error> {
+    if on_delete.is_none() && on_update.is_none() {
+        return Ok(());
+    }
+
+    let on_delete = parse_referential_action(field, on_delete)?;
+    let on_update = parse_referential_action(field, on_update)?;
+
+    field.attrs.push(parse_quote! {
+        #[__gas_foreign_key_actions(gas::ForeignKeyActions {
+            on_delete: #on_delete,
+            on_update: #on_update,
+        })]
+    });
+
+    Ok(())
+}
+
+// model::field -> model::__::field_{value} (or model::__::{value})
+fn sidecar_path(mut path: syn::Path, value: &str, append: bool) -> Result<syn::Path, syn::Error> {
+    let last = path.segments.last_mut();
+    let Some(last) = last else {
+        return Err(syn::Error::new(Span::call_site(), "invalid relation path"));
     };
 
+    if append {
+        last.ident = Ident::new(&format!("{}_{}", last.ident, value), Span::call_site());
+    } else {
+        last.ident = Ident::new(value, Span::call_site());
+    }
+    path.segments.insert(
+        path.segments.len() - 1,
+        PathSegment::from(Ident::new("__", Span::call_site())),
+    );
+
+    Ok(path)
+}
+
+fn apply_inverse_relation(
+    field: &mut Field,
+    path: syn::Path,
+    sort: Option<syn::Expr>,
+    limit: Option<syn::Expr>,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let (spec, spec_items) = gen_inverse_spec(field, sort, limit)?;
+    let ty = &field.ty;
+    let path_index = sidecar_path(path.clone(), "index", true)?;
+    let path_flags = sidecar_path(path.clone(), "flags", true)?;
+    let path_sidecar = sidecar_path(path.clone(), "Inner", false)?;
+    let path_fk_type_alias = sidecar_path(path.clone(), "fk_type", true)?;
+    let path_fk_remote_field_index = sidecar_path(path.clone(), "fk_remote_index", true)?;
+
     let _ = &path_flags;
 
     field.ty = parse_quote! { gas::InverseRelation<Model, #path_fk_type_alias, <#ty as gas::InverseRelationTypeOps>::Inner, {
@@ -171,14 +250,248 @@ fn apply_inverse_relation(field: &mut Field, path: syn::Path) -> Result<(), syn:
         }
 
         #path_index
-    }, { #path_fk_remote_field_index }> };
+    }, { #path_fk_remote_field_index }, #spec> };
 
     field.attrs.push(parse_quote! { #[__gas_virtual] });
 
+    Ok(spec_items)
+}
+
+// sort and limit go into a generated spec type, same as the columns of composite relations
+fn gen_inverse_spec(
+    field: &Field,
+    sort: Option<syn::Expr>,
+    limit: Option<syn::Expr>,
+) -> Result<(syn::Type, proc_macro2::TokenStream), syn::Error> {
+    if sort.is_none() && limit.is_none() {
+        return Ok((parse_quote! { () }, quote! {}));
+    }
+
+    let Some(ident) = field.ident.as_ref() else {
+        Err(syn::Error::new(
+            field.span(),
+            "relation must be a named field",
+        ))?
+    };
+    let spec = Ident::new(&format!("__{}_inverse_query", ident), ident.span());
+
+    let sort_fn = sort.map(|sort| {
+        quote! {
+            fn sort() -> Option<gas::sort::SortDefinition> {
+                Some(#sort)
+            }
+        }
+    });
+    let limit_const = limit.map(|limit| {
+        quote! {
+            const LIMIT: Option<usize> = Some(#limit);
+        }
+    });
+
+    Ok((
+        parse_quote! { #spec },
+        quote! {
+            #[doc(hidden)]
+            #[allow(non_camel_case_types)]
+            #[derive(Debug, Clone, Copy, Default)]
+            pub struct #spec;
+
+            impl gas::InverseRelationSpec for #spec {
+                #limit_const
+                #sort_fn
+            }
+        },
+    ))
+}
+
+fn apply_many_to_many_relation(
+    field: &mut Field,
+    through: syn::Path,
+    from: syn::Path,
+    to: syn::Path,
+) -> Result<(), syn::Error> {
+    let ty = &field.ty;
+
+    let from_index = sidecar_path(from.clone(), "index", true)?;
+    let from_flags = sidecar_path(from.clone(), "flags", true)?;
+    let from_sidecar = sidecar_path(from.clone(), "Inner", false)?;
+    let from_fk_type_alias = sidecar_path(from.clone(), "fk_type", true)?;
+
+    let to_index = sidecar_path(to.clone(), "index", true)?;
+    let to_flags = sidecar_path(to.clone(), "flags", true)?;
+    let to_sidecar = sidecar_path(to.clone(), "Inner", false)?;
+    let to_fk_type_alias = sidecar_path(to.clone(), "fk_type", true)?;
+
+    // same as with inverse relations, the paths themselves can't be used in the constants,
+    //  `from` has to point to this model and `to` to the one in the Vec
+    field.ty = parse_quote! { gas::ManyToMany<
+        Model,
+        #from_fk_type_alias,
+        #through,
+        #to_fk_type_alias,
+        <#ty as gas::ManyToManyTypeOps>::Model,
+        {
+            gas::internals::assert_relation_target::<_, _, __::Inner>(&#from);
+
+            if !#from_flags.has_flag(gas::FieldFlag::ForeignKey) {
+                panic!("`from` is not a foreign key");
+            }
+
+            gas::internals::assert_types_param::<<#through as gas::ModelMeta>::Id, #from_sidecar>();
+
+            #from_index
+        },
+        {
+            gas::internals::assert_relation_target::<
+                _,
+                _,
+                <<#ty as gas::ManyToManyTypeOps>::Model as gas::ModelMeta>::Id,
+            >(&#to);
+
+            if !#to_flags.has_flag(gas::FieldFlag::ForeignKey) {
+                panic!("`to` is not a foreign key");
+            }
+
+            gas::internals::assert_types_param::<<#through as gas::ModelMeta>::Id, #to_sidecar>();
+
+            #to_index
+        }
+    > };
+
+    field.attrs.push(parse_quote! { #[__gas_virtual] });
+    field.attrs.push(parse_quote! { #[__gas_many_to_many] });
+
     Ok(())
 }
 
-fn apply_relation_type_changes(target: &mut syn::ItemStruct) -> Result<(), syn::Error> {
+// (a, b) -> [a, b]
+fn expr_paths(expr: &syn::Expr) -> Result<Vec<syn::Path>, syn::Error> {
+    let elems: Vec<&syn::Expr> = match expr {
+        syn::Expr::Tuple(tuple) => tuple.elems.iter().collect(),
+        syn::Expr::Paren(paren) => vec![&paren.expr],
+        expr => vec![expr],
+    };
+
+    elems
+        .into_iter()
+        .map(|elem| match elem {
+            syn::Expr::Path(path) => Ok(path.path.clone()),
+            elem => Err(syn::Error::new(elem.span(), "expected a field")),
+        })
+        .collect()
+}
+
+// the columns go into a generated spec type, the relation is only a view over regular fields
+fn apply_composite_relation(
+    field: &mut Field,
+    fields: syn::Expr,
+    references: syn::Expr,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let ty = field.ty.clone();
+    let locals = expr_paths(&fields)?
+        .into_iter()
+        .map(|path| {
+            path.get_ident()
+                .cloned()
+                .ok_or_else(|| syn::Error::new(path.span(), "expected a field of this model"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let targets = expr_paths(&references)?;
+
+    if locals.len() != targets.len() {
+        Err(syn::Error::new(
+            references.span(),
+            "`fields` and `references` must have the same amount of fields",
+        ))?
+    }
+
+    if !(2..=4).contains(&locals.len()) {
+        Err(syn::Error::new(
+            fields.span(),
+            "composite relations support 2 to 4 fields, use `field` for a single one",
+        ))?
+    }
+
+    let local_metas = locals
+        .iter()
+        .map(|ident| Ident::new(&format!("{}_meta", ident), ident.span()));
+    let target_metas = targets
+        .iter()
+        .map(|path| sidecar_path(path.clone(), "meta", true))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let Some(ident) = field.ident.as_ref() else {
+        Err(syn::Error::new(
+            field.span(),
+            "relation must be a named field",
+        ))?
+    };
+    let spec = Ident::new(&format!("__{}_composite_key", ident), ident.span());
+
+    field.ty = parse_quote! { <#ty as gas::CompositeRelationTypeOps>::ToComposite<#spec> };
+    field.attrs.push(parse_quote! { #[__gas_virtual] });
+    field
+        .attrs
+        .push(parse_quote! { #[__gas_composite_relation] });
+
+    Ok(quote! {
+        #[doc(hidden)]
+        #[allow(non_camel_case_types)]
+        #[derive(Debug, Clone, Copy, Default)]
+        pub struct #spec;
+
+        impl gas::CompositeKeySpec for #spec {
+            const COLUMNS: &'static [&'static gas::FieldMeta] = &[#(&__::#local_metas),*];
+            const TARGET_COLUMNS: &'static [&'static gas::FieldMeta] = &[#(&#target_metas),*];
+        }
+
+        const _: () = {
+            gas::internals::assert_type::<<#ty as gas::CompositeRelationTypeOps>::LocalFields<__::Inner>>(
+                &(#(#locals,)*)
+            );
+            gas::internals::assert_type::<<#ty as gas::CompositeRelationTypeOps>::TargetFields>(
+                &(#(#targets,)*)
+            );
+
+            assert!(
+                gas::internals::is_full_primary_key(
+                    <#spec as gas::CompositeKeySpec>::TARGET_COLUMNS,
+                    <<#ty as gas::CompositeRelationTypeOps>::Model as gas::ModelMeta>::FIELDS,
+                ),
+                "composite relation must point to the whole primary key of the model"
+            );
+        };
+    })
+}
+
+// the field stays an i64, it's filled in by a subquery whenever the model is selected
+fn apply_relation_count(
+    field: &mut Field,
+    path: syn::Path,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let ty = &field.ty;
+    let path_meta = sidecar_path(path.clone(), "meta", true)?;
+
+    let assertions = quote! {
+        const _: () = {
+            gas::internals::assert_types_param::<i64, #ty>();
+            gas::internals::assert_relation_target::<_, _, __::Inner>(&#path);
+        };
+    };
+
+    field.attrs.push(parse_quote! { #[__gas_virtual] });
+    field
+        .attrs
+        .push(parse_quote! { #[__gas_relation_count(#path_meta)] });
+
+    Ok(assertions)
+}
+
+fn apply_relation_type_changes(
+    target: &mut syn::ItemStruct,
+) -> Result<Vec<proc_macro2::TokenStream>, syn::Error> {
+    let mut items = Vec::new();
+
     let fields = target.fields.iter_mut().filter_map(|field| {
         let attr = field
             .attrs
@@ -192,30 +505,95 @@ fn apply_relation_type_changes(target: &mut syn::ItemStruct) -> Result<(), syn::
     for (field, relation_attr) in fields {
         let args: RelationArgs = FromMeta::from_meta(&relation_attr.meta)?;
 
-        if args.field.is_some() && args.inverse.is_some() {
+        let kinds = [
+            args.field.is_some(),
+            args.fields.is_some(),
+            args.inverse.is_some(),
+            args.through.is_some(),
+            args.count_of.is_some(),
+        ];
+        if kinds.iter().filter(|&&it| it).count() > 1 {
+            Err(syn::Error::new(
+                field.span(),
+                "relation must be either field, fields, inverse, through or count_of",
+            ))?
+        }
+
+        let is_forward = args.field.is_some() || args.fields.is_some();
+
+        if args.lazy && (is_forward || args.count_of.is_some()) {
+            Err(syn::Error::new(
+                field.span(),
+                "only inverse and many to many relations can be lazy",
+            ))?
+        }
+
+        if args.lazy {
+            field.attrs.push(parse_quote! { #[__gas_lazy] });
+        }
+
+        if (args.sort.is_some() || args.limit.is_some()) && args.inverse.is_none() {
+            Err(syn::Error::new(
+                field.span(),
+                "sort and limit can only be used on inverse relations",
+            ))?
+        }
+
+        if (args.on_delete.is_some() || args.on_update.is_some()) && !is_forward {
             Err(syn::Error::new(
                 field.span(),
-                "relation must be either field or inverse",
+                "on_delete and on_update can only be used on forward relations",
             ))?
         }
 
         if let Some(path) = args.field {
+            apply_foreign_key_actions(field, args.on_delete, args.on_update)?;
             apply_forward_relation(field, path)?;
             continue;
         }
 
+        if let Some(fields) = args.fields {
+            let Some(references) = args.references else {
+                Err(syn::Error::new(
+                    field.span(),
+                    "composite relations need both `fields` and `references`",
+                ))?
+            };
+
+            apply_foreign_key_actions(field, args.on_delete, args.on_update)?;
+            items.push(apply_composite_relation(field, fields, references)?);
+            continue;
+        }
+
         if let Some(path) = args.inverse {
-            apply_inverse_relation(field, path)?;
+            items.push(apply_inverse_relation(field, path, args.sort, args.limit)?);
+            continue;
+        }
+
+        if let Some(path) = args.count_of {
+            items.push(apply_relation_count(field, path)?);
+            continue;
+        }
+
+        if let Some(through) = args.through {
+            let (Some(from), Some(to)) = (args.from, args.to) else {
+                Err(syn::Error::new(
+                    field.span(),
+                    "many to many relations need both `from` and `to`",
+                ))?
+            };
+
+            apply_many_to_many_relation(field, through, from, to)?;
             continue;
         }
 
         Err(syn::Error::new(
             field.span(),
-            "missing field: `field` or `inverse`",
+            "missing field: `field`, `fields`, `inverse`, `through` or `count_of`",
         ))?
     }
 
-    Ok(())
+    Ok(items)
 }
 
 fn gen_default_impl(fields: &Fields) -> Result<proc_macro2::TokenStream, syn::Error> {
@@ -238,7 +616,24 @@ fn gen_default_impl(fields: &Fields) -> Result<proc_macro2::TokenStream, syn::Er
             };
 
             let expr = match attribute {
-                Ok(DefaultArgs { expression, .. }) => expression,
+                Ok(DefaultArgs {
+                    expression: Some(expression),
+                    ..
+                }) => quote! { #expression },
+                // only the database knows the value, filled in on insert
+                Ok(DefaultArgs {
+                    expression: None,
+                    sql: Some(_),
+                }) => quote! { <#ty as Default>::default() },
+                Ok(DefaultArgs {
+                    expression: None,
+                    sql: None,
+                }) => {
+                    return Some(Err(syn::Error::new(
+                        ident.span(),
+                        "default needs at least one of `fn` or `sql`",
+                    )));
+                }
                 Err(err) => return Some(Err(err.into())),
             };
 
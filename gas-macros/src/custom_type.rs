@@ -0,0 +1,84 @@
+use darling::ast::Data;
+use darling::util::Ignored;
+use darling::{FromDeriveInput, FromField};
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Span};
+use quote::quote;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(gas), supports(struct_newtype, struct_named))]
+struct CustomTypeArgs {
+    ident: Ident,
+    generics: syn::Generics,
+    data: Data<Ignored, InnerField>,
+    #[darling(default)]
+    transparent: bool,
+}
+
+#[derive(Debug, FromField)]
+struct InnerField {
+    ident: Option<Ident>,
+    ty: syn::Type,
+}
+
+#[inline(always)]
+pub fn custom_type_impl(input: TokenStream) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let derive_input = syn::parse::<syn::DeriveInput>(input)?;
+    let args: CustomTypeArgs = FromDeriveInput::from_derive_input(&derive_input)?;
+
+    let ident = &args.ident;
+
+    // NOTE: transparent is the only representation for now, it's required so others can be added later
+    if !args.transparent {
+        return Err(syn::Error::new(
+            ident.span(),
+            "only #[gas(transparent)] newtypes can be derived, implement gas::types::PgCustomType for anything else",
+        ));
+    }
+
+    if !args.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            ident.span(),
+            "generic types are not supported",
+        ));
+    }
+
+    let Data::Struct(fields) = args.data else {
+        unreachable!("darling only accepts structs")
+    };
+
+    let [field] = &fields.fields[..] else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "a transparent type must have exactly one field",
+        ));
+    };
+
+    let inner_type = &field.ty;
+    let (wrap, unwrap) = match &field.ident {
+        Some(name) => (quote! { Self { #name: inner } }, quote! { it.#name }),
+        None => (quote! { Self(inner) }, quote! { it.0 }),
+    };
+
+    Ok(quote! {
+        impl gas::types::PgCustomType for #ident {
+            const PG_TYPE: gas::internals::PgType =
+                <#inner_type as gas::internals::AsPgType>::PG_TYPE;
+
+            fn to_param(value: Option<Self>) -> gas::internals::PgParam {
+                gas::internals::PgParam::from(value.map(|it| #unwrap))
+            }
+
+            fn from_row(
+                ctx: &gas::row::ResponseCtx,
+                row: &gas::row::Row,
+                name: &str,
+            ) -> gas::GasResult<Option<Self>> {
+                Ok(
+                    <Option<#inner_type> as gas::row::FromRowNamed>::from_row_named(ctx, row, name)?
+                        .map(|inner| #wrap),
+                )
+            }
+        }
+    })
+}
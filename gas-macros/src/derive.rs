@@ -8,7 +8,7 @@ use darling::{FromDeriveInput, FromMeta};
 use itertools::Itertools;
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span};
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::spanned::Spanned;
 use syn::{Field, Index, Meta, MetaList};
 
@@ -23,7 +23,21 @@ struct ModelArgs {
 
 #[derive(Debug, FromMeta)]
 struct ColumnArgs {
-    name: String,
+    name: Option<String>,
+    max_length: Option<u32>,
+    precision: Option<u16>,
+    scale: Option<u16>,
+}
+
+#[derive(Debug, FromMeta)]
+struct IndexArgs {
+    using: Option<String>,
+}
+
+#[derive(Debug, FromMeta)]
+struct TsVectorArgs {
+    language: String,
+    from: darling::util::PathList,
 }
 
 #[inline(always)]
@@ -36,6 +50,15 @@ pub fn model_impl(_input: TokenStream) -> Result<proc_macro2::TokenStream, syn::
     // TODO (low priority): extract these magic string constants to some module
     let virtuals =
         find_fields_with_attr(&input.fields.iter().cloned().collect_vec(), "__gas_virtual");
+    let lazy = find_fields_with_attr(&input.fields.iter().cloned().collect_vec(), "__gas_lazy");
+    let composites = find_fields_with_attr(
+        &input.fields.iter().cloned().collect_vec(),
+        "__gas_composite_relation",
+    );
+    let counts = find_fields_with_attr(
+        &input.fields.iter().cloned().collect_vec(),
+        "__gas_relation_count",
+    );
 
     let real_fields = input
         .fields
@@ -53,12 +76,13 @@ pub fn model_impl(_input: TokenStream) -> Result<proc_macro2::TokenStream, syn::
     let primary_keys = find_fields_with_attr(&real_fields, "primary_key");
     let serials = find_fields_with_attr(&real_fields, "serial");
     let uniques = find_fields_with_attr(&real_fields, "unique");
+    let generated = find_fields_with_attr(&real_fields, "tsvector");
+    let database_defaults = find_database_defaults(&real_fields);
 
-    // TODO: fields with only primary keys will error here
-    if primary_keys.len() == real_fields.len() {
+    if real_fields.is_empty() {
         return Err(syn::Error::new(
             Span::call_site(),
-            "The struct is either empty or only made from primary keys, not supported fow now",
+            "The struct is empty, not supported",
         ));
     }
 
@@ -66,11 +90,16 @@ pub fn model_impl(_input: TokenStream) -> Result<proc_macro2::TokenStream, syn::
 
     let ctx = ModelCtx {
         virtuals: &virtuals,
+        lazy: &lazy,
+        composites: &composites,
+        counts: &counts,
         table_name: &table_name,
         primary_keys: &primary_keys,
         serials: &serials,
         uniques: &uniques,
         foreign_keys: &parse_foreign_keys(&real_fields),
+        generated: &generated,
+        database_defaults: &database_defaults,
         field_columns: &parse_col_names(&table_name, &real_fields)?,
     };
 
@@ -86,7 +115,12 @@ pub fn model_impl(_input: TokenStream) -> Result<proc_macro2::TokenStream, syn::
         .iter()
         .filter_map(|field| field.ident.as_ref())
         .collect::<Vec<_>>();
-    let field_list_len = field_list.len();
+
+    let all_fields = field_list
+        .iter()
+        .copied()
+        .chain(virtuals.iter())
+        .collect::<Vec<_>>();
 
     let key_tokens = gen_key_tokens(&ctx, &real_fields);
 
@@ -98,14 +132,23 @@ pub fn model_impl(_input: TokenStream) -> Result<proc_macro2::TokenStream, syn::
     let delete_fn = gen_delete_sql_fn_tokens(&ctx)?;
 
     let from_row_impl = generate_from_row(&ctx)?;
+    let insert_graph_fn = gen_insert_graph(&ctx);
+
+    // composite foreign keys are migrated as table constraints
+    let link_fields = field_list
+        .iter()
+        .copied()
+        .chain(composites.iter())
+        .collect::<Vec<_>>();
+    let link_fields_len = link_fields.len();
 
     let exclude_link_meta = meta.exclude_link_meta.unwrap_or(false);
     let link_meta_fields = (!exclude_link_meta).then(|| {
         quote! {
             #[unsafe(link_section = ".__gas_internals,__fields")]
             #[used]
-            static FIELDS: [gas::link::PortableFieldMeta; #field_list_len] =
-                [#(gas::link::PortableFieldMeta::from_unchecked(#field_list.meta)),*];
+            static FIELDS: [gas::link::PortableFieldMeta; #link_fields_len] =
+                [#(gas::link::PortableFieldMeta::from_unchecked(#link_fields.meta)),*];
         }
     });
 
@@ -155,6 +198,22 @@ pub fn model_impl(_input: TokenStream) -> Result<proc_macro2::TokenStream, syn::
                     _ => None
                 }
             }
+
+            fn get_param_by_field(&self, field: &gas::FieldMeta) -> Option<gas::internals::PgParam> {
+                match field.struct_name {
+                    #(stringify!(#field_list) => Some(gas::internals::PgParam::from(self.#field_list.clone())),)*
+                    _ => None
+                }
+            }
+
+            fn get_mut_by_field<T: 'static>(&mut self, field: &gas::FieldMeta) -> Option<&mut T> {
+                match field.struct_name {
+                    #(stringify!(#all_fields) => (&mut self.#all_fields as &mut dyn std::any::Any).downcast_mut::<T>(),)*
+                    _ => None
+                }
+            }
+
+            #insert_graph_fn
         }
 
         pub mod __ {
@@ -194,6 +253,37 @@ fn find_fields_with_attr(fields: &[Field], target_attr: &'static str) -> Vec<Ide
         .collect()
 }
 
+fn find_database_defaults(fields: &[Field]) -> Vec<Ident> {
+    fields
+        .iter()
+        .filter_map(|field| {
+            let attr = field
+                .attrs
+                .iter()
+                .find(|attr| attr.path().is_ident("default"))?;
+
+            match DefaultArgs::from_meta(&attr.meta) {
+                Ok(DefaultArgs {
+                    expression: None,
+                    sql: Some(_),
+                }) => field.ident.clone(),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn find_attr_tokens(field: &Field, target_attr: &'static str) -> Option<proc_macro2::TokenStream> {
+    field
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident(target_attr))
+        .and_then(|attr| match &attr.meta {
+            Meta::List(MetaList { tokens, .. }) => Some(tokens.clone()),
+            _ => None,
+        })
+}
+
 fn parse_foreign_keys(fields: &[Field]) -> Vec<(Ident, syn::Type)> {
     fields
         .iter()
@@ -235,7 +325,7 @@ fn gen_key_tokens(ctx: &ModelCtx, fields: &[Field]) -> proc_macro2::TokenStream
                 #(self.#field_idents = key;)*
             },
             quote! {
-                #(#primary_key_field_idents.eq(key))*
+                #(gas::internals::key_condition(&#primary_key_field_idents, key))*
             },
         )
     } else {
@@ -251,7 +341,7 @@ fn gen_key_tokens(ctx: &ModelCtx, fields: &[Field]) -> proc_macro2::TokenStream
                         self.#ident = key.#index;
                     },
                     quote! {
-                        #ident.eq(key.#index)
+                        gas::internals::key_condition(&#ident, key.#index)
                     },
                 )
             })
@@ -277,8 +367,6 @@ fn gen_key_tokens(ctx: &ModelCtx, fields: &[Field]) -> proc_macro2::TokenStream
         quote! {
 
             fn filter_with_key(key: Self::Key) -> gas::condition::EqExpression {
-                use gas::eq::PgEq;
-
                 #condition_fn
             }
         }
@@ -307,12 +395,16 @@ fn generate_from_row(ctx: &ModelCtx) -> Result<proc_macro2::TokenStream, syn::Er
             }
         });
 
+    // the alias is what joins of composite relations are looked up by
     let virtual_defs = ctx.virtuals.iter().map(|ident| {
+        let alias_name = virtual_alias_name(ctx, ident);
         quote! {
-            #ident: gas::row::FromRowNamed::from_row_named(ctx, row, stringify!(#ident))?,
+            #ident: gas::row::FromRowNamed::from_row_named(ctx, row, #alias_name)?,
         }
     });
 
+    let load_relations_fn = gen_load_relations(ctx);
+
     Ok(quote! {
         impl gas::row::FromRow for Model {
             fn from_row(ctx: &gas::row::ResponseCtx, row: &gas::row::Row) -> gas::GasResult<Model> {
@@ -321,6 +413,156 @@ fn generate_from_row(ctx: &ModelCtx) -> Result<proc_macro2::TokenStream, syn::Er
                     #(#virtual_defs)*
                 })
             }
+
+            #load_relations_fn
+        }
+    })
+}
+
+// inverse relations (except lazy ones) are loaded for all items at once,
+//  models that were eagerly loaded through a forward relation (include) get the same treatment
+fn gen_load_relations(ctx: &ModelCtx) -> Option<proc_macro2::TokenStream> {
+    if ctx.virtuals.is_empty() && ctx.foreign_keys.is_empty() {
+        return None;
+    }
+
+    let inverse_loads = ctx
+        .virtuals
+        .iter()
+        .filter(|it| !ctx.composites.contains(it) && !ctx.counts.contains(it))
+        .map(|ident| {
+            let load = quote! {
+                gas::InverseRelationOps::load_batch(
+                    items.iter_mut().map(|it| &mut it.#ident).collect(),
+                    ctx
+                ).await?;
+            };
+
+            // lazy ones only when asked for with include_many
+            if ctx.lazy.contains(ident) {
+                quote! {
+                    if ctx.is_included(&#ident.meta) {
+                        #load
+                    }
+                }
+            } else {
+                load
+            }
+        });
+
+    let forward_loads = ctx.foreign_keys.iter().map(|(ident, _)| ident).chain(ctx.composites).map(|ident| {
+        quote! {
+            gas::row::FromRow::load_relations(
+                items.iter_mut().filter_map(|it| gas::RelationOps::model_mut(&mut it.#ident)).collect(),
+                ctx
+            ).await?;
+        }
+    });
+
+    Some(quote! {
+        fn load_relations<'a>(
+            mut items: Vec<&'a mut Self>,
+            ctx: &'a gas::row::RelationCtx
+        ) -> gas::row::RelationFuture<'a> {
+            Box::pin(async move {
+                #(#inverse_loads)*
+                #(#forward_loads)*
+
+                Ok(())
+            })
+        }
+    })
+}
+
+// insert_with_relations, parents are inserted first so the model can point to them,
+//  children after it so they can point to the model
+fn gen_insert_graph(ctx: &ModelCtx) -> Option<proc_macro2::TokenStream> {
+    if ctx.virtuals.is_empty() && ctx.foreign_keys.is_empty() {
+        return None;
+    }
+
+    let forwards = ctx
+        .foreign_keys
+        .iter()
+        .map(|(ident, _)| ident)
+        .chain(ctx.composites)
+        .map(|ident| (ident, format_ident!("__forward_{}", ident)))
+        .collect::<Vec<_>>();
+    let inverses = ctx
+        .virtuals
+        .iter()
+        .filter(|it| !ctx.composites.contains(it) && !ctx.counts.contains(it))
+        .map(|ident| (ident, format_ident!("__inverse_{}", ident)))
+        .collect::<Vec<_>>();
+
+    // the loaded parents are put back after the insert, it rereads the model with keys only
+    let insert_parents = forwards.iter().map(|(ident, _)| {
+        quote! {
+            gas::ForwardGraphOps::insert_parent(&mut self.#ident, ctx).await?;
+        }
+    });
+    let take_parents = forwards.iter().map(|(ident, tmp)| {
+        quote! {
+            let key_only = gas::ForwardGraphOps::key_only(&self.#ident);
+            let #tmp = std::mem::replace(&mut self.#ident, key_only);
+            gas::ForwardGraphOps::write_columns(&#tmp, self);
+        }
+    });
+    let restore_parents = forwards.iter().map(|(ident, tmp)| {
+        quote! {
+            self.#ident = #tmp;
+        }
+    });
+    let take_children = inverses.iter().map(|(ident, tmp)| {
+        quote! {
+            let #tmp = std::mem::take(&mut self.#ident);
+        }
+    });
+    let restore_children = inverses.iter().map(|(ident, tmp)| {
+        quote! {
+            self.#ident = #tmp;
+        }
+    });
+    let check_inserted = if inverses.is_empty() {
+        quote! { inserted?; }
+    } else {
+        quote! {
+            if let Err(err) = inserted {
+                #(#restore_children)*
+                return Err(err);
+            }
+        }
+    };
+    // once one of them fails, the rest just get their children back
+    let insert_children = inverses.iter().map(|(ident, tmp)| {
+        quote! {
+            if result.is_ok() {
+                result = gas::InverseGraphOps::insert_children(&mut self.#ident, #tmp, ctx).await;
+            } else {
+                self.#ident = #tmp;
+            }
+        }
+    });
+
+    Some(quote! {
+        fn insert_graph<'a>(
+            &'a mut self,
+            ctx: &'a gas::row::RelationCtx
+        ) -> gas::row::RelationFuture<'a> {
+            Box::pin(async move {
+                #(#insert_parents)*
+                #(#take_parents)*
+                #(#take_children)*
+
+                // the model keeps what it was given, even if the insert fails
+                let inserted = gas::insert_model(self, ctx).await;
+                #(#restore_parents)*
+                #check_inserted
+
+                let mut result: gas::GasResult<()> = Ok(());
+                #(#insert_children)*
+                result
+            })
         }
     })
 }
@@ -364,7 +606,10 @@ fn get_col_name(field: &Field) -> Option<Result<String, syn::Error>> {
 
     let column_args: Result<ColumnArgs, _> = FromMeta::from_meta(&attr.meta);
     match column_args {
-        Ok(ColumnArgs { name }) => Some(Ok(name)),
+        Ok(ColumnArgs {
+            name: Some(name), ..
+        }) => Some(Ok(name)),
+        Ok(_) => field.ident.as_ref().map(|it| Ok(it.to_string())),
         Err(err) => Some(Err(err.into())),
     }
 }
@@ -389,7 +634,7 @@ fn process_field(
     let virtual_names = FieldNames {
         column_name: ident.to_string(),
         full_name: format!("virtual.{}", ident),
-        alias_name: format!("virtual_{}", ident),
+        alias_name: virtual_alias_name(ctx, ident),
     };
     let field_names = ctx
         .field_columns
@@ -398,10 +643,14 @@ fn process_field(
         .map(|(_, v)| v)
         .unwrap_or(&virtual_names);
 
-    let pg_type_tokens = proc_type_to_pg_type(&ty);
-    let pg_type_tokens = match pg_type_tokens {
-        Ok(tokens) => tokens,
-        Err(err) => return Some(Err(err)),
+    let pg_type_tokens = match find_attr_tokens(field, "__gas_relation_count") {
+        Some(foreign_key) => {
+            quote! { gas::internals::PgType::RELATION_COUNT { foreign_key: &#foreign_key } }
+        }
+        None => match proc_type_to_pg_type(&ty) {
+            Ok(tokens) => tokens,
+            Err(err) => return Some(Err(err)),
+        },
     };
 
     let mut flags: Vec<proc_macro2::TokenStream> = Vec::new();
@@ -468,25 +717,58 @@ fn process_field(
         }
     };
 
+    let generated_sql = match gen_tsvector_sql(ctx, field) {
+        Some(Ok(sql)) => quote! { Option::Some({
+            gas::internals::assert_tsvector::<#ty>();
+            #sql
+        }) },
+        Some(Err(err)) => return Some(Err(err)),
+        None => quote! { Option::None },
+    };
+
+    let type_modifiers = match gen_type_modifiers(field, &pg_type_tokens) {
+        Ok(tokens) => tokens,
+        Err(err) => return Some(Err(err)),
+    };
+
+    let index_type = match get_index_type(field) {
+        Some(Ok(index_type)) => quote! { Option::Some(gas::IndexType::#index_type) },
+        Some(Err(err)) => return Some(Err(err)),
+        None => quote! { Option::None },
+    };
+
+    let foreign_key_actions = find_attr_tokens(field, "__gas_foreign_key_actions")
+        .unwrap_or_else(|| quote! { gas::ForeignKeyActions::NONE });
+
     let fk_extra_vars = if let Some((_, fk_type)) = maybe_foreign_key {
+        // index of the referenced field in the other model
+        let fk_remote_index = match find_attr_tokens(field, "__gas_foreign_key_target") {
+            Some(path) => quote! { #path.meta.index },
+            None => quote! { 0 },
+        };
+
         quote! {
             #[allow(non_camel_case_types)]
             pub type #ident_fk_type_alias = #fk_type;
-            pub const #ident_fk_remote_index: usize = 0;
+            pub const #ident_fk_remote_index: usize = #fk_remote_index;
         }
     } else {
         quote! {}
     };
 
     Some(Ok((
-        if !is_virtual {
+        if ctx.composites.contains(ident) {
+            quote! {
+                pub const #ident: gas::CompositeField<#ty, __::Inner> = gas::CompositeField::new(__::#ident_meta);
+            }
+        } else if !is_virtual {
             quote! {
                 pub const #ident: gas::Field<#ty, __::Inner> = gas::Field::new(__::#ident_meta);
             }
         } else {
-            let virtual_field_type = get_virtual_field_type()?;
+            let virtual_field_type = get_virtual_field_type(field);
             quote! {
-                pub const #ident: gas::VirtualField<__::Inner> = gas::VirtualField::new(#virtual_field_type, __::#ident_meta);
+                pub const #ident: gas::VirtualField<__::Inner, #ty> = gas::VirtualField::new(#virtual_field_type, __::#ident_meta);
             }
         },
         quote! {
@@ -500,7 +782,11 @@ fn process_field(
                 alias_name: #alias_name,
                 struct_name: stringify!(#ident),
                 pg_type: #pg_type_tokens,
+                type_modifiers: #type_modifiers,
                 default_sql: #default_sql,
+                generated_sql: #generated_sql,
+                index_type: #index_type,
+                foreign_key_actions: #foreign_key_actions,
                 flags: #ident_flags,
                 index: #ident_index,
             };
@@ -508,9 +794,198 @@ fn process_field(
     )))
 }
 
-fn get_virtual_field_type() -> Option<proc_macro2::TokenStream> {
-    // currently the only one supported, the function is not that useful lol
-    Some(quote! {
-        gas::VirtualFieldType::InverseRelation
-    })
+// #[column(max_length = 255)], #[column(precision = 18, scale = 4)]
+//  whether the type takes them is only known once the type is resolved, so that's a const assert
+fn gen_type_modifiers(
+    field: &Field,
+    pg_type_tokens: &proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let none = quote! { gas::TypeModifiers::NONE };
+
+    let Some(attr) = field
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("column"))
+    else {
+        return Ok(none);
+    };
+
+    let ColumnArgs {
+        max_length,
+        precision,
+        scale,
+        ..
+    } = ColumnArgs::from_meta(&attr.meta)?;
+
+    if max_length.is_none() && precision.is_none() && scale.is_none() {
+        return Ok(none);
+    }
+
+    if scale.is_some() && precision.is_none() {
+        return Err(syn::Error::new(
+            attr.span(),
+            "`scale` needs a `precision` as well",
+        ));
+    }
+
+    let to_tokens = |value: Option<proc_macro2::TokenStream>| match value {
+        Some(value) => quote! { Option::Some(#value) },
+        None => quote! { Option::None },
+    };
+
+    let max_length = to_tokens(max_length.map(|it| quote! { #it }));
+    let precision = to_tokens(precision.map(|it| quote! { #it }));
+    let scale = to_tokens(scale.map(|it| quote! { #it }));
+
+    let message = format!(
+        "unsupported #[column(..)] type modifiers on `{}`: max_length is for strings, precision and scale for decimals, precision up to 6 for timestamps, times and intervals",
+        field.ident.as_ref().map(|it| it.to_string()).unwrap_or_default()
+    );
+
+    Ok(quote! { {
+        const MODIFIERS: gas::TypeModifiers = gas::TypeModifiers {
+            max_length: #max_length,
+            precision: #precision,
+            scale: #scale,
+        };
+        assert!(MODIFIERS.supports(&#pg_type_tokens), #message);
+        MODIFIERS
+    } })
+}
+
+fn get_index_type(field: &Field) -> Option<Result<Ident, syn::Error>> {
+    let attr = field
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("index"))?;
+
+    // plain #[index] is a btree, same as postgres
+    let using = match &attr.meta {
+        Meta::Path(_) => None,
+        meta => match IndexArgs::from_meta(meta) {
+            Ok(IndexArgs { using }) => using,
+            Err(err) => return Some(Err(err.into())),
+        },
+    };
+
+    let variant = match using.as_deref().map(str::to_lowercase).as_deref() {
+        None | Some("btree") => "BTree",
+        Some("hash") => "Hash",
+        Some("gist") => "Gist",
+        Some("gin") => "Gin",
+        Some("brin") => "Brin",
+        Some(other) => {
+            return Some(Err(syn::Error::new(
+                attr.span(),
+                format!("unsupported index type `{other}`, expected one of btree, hash, gist, gin, brin"),
+            )));
+        }
+    };
+
+    Some(Ok(Ident::new(variant, Span::call_site())))
+}
+
+// #[tsvector(language = "english", from(title, body))]
+//  becomes GENERATED ALWAYS AS (to_tsvector('english'::regconfig, ...)) STORED
+fn gen_tsvector_sql(ctx: &ModelCtx, field: &Field) -> Option<Result<String, syn::Error>> {
+    let attr = field
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("tsvector"))?;
+
+    let args = match TsVectorArgs::from_meta(&attr.meta) {
+        Ok(args) => args,
+        Err(err) => return Some(Err(err.into())),
+    };
+
+    // ends up in the ddl as is, so better be safe
+    if args.language.is_empty()
+        || !args
+            .language
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+    {
+        return Some(Err(syn::Error::new(
+            attr.span(),
+            "invalid text search language",
+        )));
+    }
+
+    if args.from.is_empty() {
+        return Some(Err(syn::Error::new(
+            attr.span(),
+            "tsvector must be generated from at least one field",
+        )));
+    }
+
+    if field
+        .attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("default"))
+    {
+        return Some(Err(syn::Error::new(
+            attr.span(),
+            "generated tsvector column can not have a default",
+        )));
+    }
+
+    let columns = args
+        .from
+        .iter()
+        .map(|path| {
+            let column = path.get_ident().and_then(|ident| {
+                ctx.field_columns
+                    .iter()
+                    .find(|(name, _)| ident == name)
+                    .filter(|_| !ctx.generated.contains(ident))
+            });
+
+            match column {
+                Some((_, FieldNames { column_name, .. })) => {
+                    Ok(format!("coalesce({}::text, '')", column_name))
+                }
+                None => Err(syn::Error::new(
+                    path.span(),
+                    "tsvector source must be a regular field of this model",
+                )),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>();
+
+    Some(columns.map(|columns| {
+        format!(
+            "to_tsvector('{}'::regconfig, {})",
+            args.language,
+            columns.join(" || ' ' || ")
+        )
+    }))
+}
+
+// relation counts are selected like columns, so they get a unique alias the same way
+fn virtual_alias_name(ctx: &ModelCtx, ident: &Ident) -> String {
+    if ctx.counts.contains(ident) {
+        format!("{}_{}", ctx.table_name, ident)
+    } else {
+        format!("virtual_{}", ident)
+    }
+}
+
+fn get_virtual_field_type(field: &Field) -> proc_macro2::TokenStream {
+    let is_many_to_many = field
+        .attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("__gas_many_to_many"));
+
+    let is_relation_count = field
+        .attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("__gas_relation_count"));
+
+    if is_many_to_many {
+        quote! { gas::VirtualFieldType::ManyToMany }
+    } else if is_relation_count {
+        quote! { gas::VirtualFieldType::RelationCount }
+    } else {
+        quote! { gas::VirtualFieldType::InverseRelation }
+    }
 }
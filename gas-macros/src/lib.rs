@@ -1,7 +1,9 @@
 mod attribute;
+mod custom_type;
 mod derive;
 mod load_migrations;
 mod ops;
+mod pg_enum;
 mod text_util;
 
 use proc_macro::TokenStream;
@@ -17,6 +19,12 @@ struct FieldNames {
 
 struct ModelCtx<'a> {
     virtuals: &'a [Ident],
+    // inverse relations that are only loaded on demand
+    lazy: &'a [Ident],
+    // virtual forward relations over several columns
+    composites: &'a [Ident],
+    // read-only counts of the rows pointing at the model
+    counts: &'a [Ident],
 
     // all the other fields assume that they're derived from non-virtual fields
     table_name: &'a str,
@@ -24,6 +32,10 @@ struct ModelCtx<'a> {
     serials: &'a [Ident],
     uniques: &'a [Ident],
     foreign_keys: &'a [(Ident, syn::Type)],
+    // generated columns, never written by insert or update
+    generated: &'a [Ident],
+    // #[default(sql = ..)] without a rust side value, left out of insert and read back
+    database_defaults: &'a [Ident],
 
     // field.ident -> names
     field_columns: &'a [(String, FieldNames)],
@@ -48,9 +60,17 @@ pub fn model(args: TokenStream, input: TokenStream) -> TokenStream {
         default,
         column,
         relation,
+        index,
+        tsvector,
         __gas_meta,
         __gas_virtual,
         __gas_foreign_key,
+        __gas_foreign_key_target,
+        __gas_foreign_key_actions,
+        __gas_composite_relation,
+        __gas_lazy,
+        __gas_many_to_many,
+        __gas_relation_count,
     )
 )]
 pub fn derive_model(input: TokenStream) -> TokenStream {
@@ -59,6 +79,26 @@ pub fn derive_model(input: TokenStream) -> TokenStream {
         .into()
 }
 
+// #[derive(gas::PgEnum, Clone, Copy, Default)]
+//  #[pg_enum(name = "order_status")]
+//  enum OrderStatus { #[default] Pending, #[pg_enum(rename = "sent")] Shipped }
+#[proc_macro_derive(PgEnum, attributes(pg_enum))]
+pub fn derive_pg_enum(input: TokenStream) -> TokenStream {
+    pg_enum::pg_enum_impl(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+// #[derive(gas::Type, Clone, Default)]
+//  #[gas(transparent)]
+//  struct UserId(i64);
+#[proc_macro_derive(Type, attributes(gas))]
+pub fn derive_custom_type(input: TokenStream) -> TokenStream {
+    custom_type::custom_type_impl(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
 #[proc_macro]
 pub fn load_migrations(input: TokenStream) -> TokenStream {
     load_migrations::load_migrations_impl(input)
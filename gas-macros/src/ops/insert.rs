@@ -1,4 +1,4 @@
-use crate::ops::{make_all_returning, make_params};
+use crate::ops::{make_all_returning, make_counts_returning, make_params};
 use crate::{FieldNames, ModelCtx};
 use quote::quote;
 
@@ -10,6 +10,8 @@ pub(crate) fn gen_insert_parts_sql_fn_tokens(
     let fields = ctx.field_columns.iter().filter(|(field_name, _)| {
         !ctx.serials
             .iter()
+            .chain(ctx.generated)
+            .chain(ctx.database_defaults)
             .map(|it| it.to_string())
             .any(|it| *field_name == it)
     });
@@ -20,6 +22,7 @@ pub(crate) fn gen_insert_parts_sql_fn_tokens(
         .reduce(|acc, curr| format!("{}, {}", acc, curr));
 
     let all_returning = make_all_returning(ctx);
+    let counts_returning = make_counts_returning(ctx, "returning");
 
     let table_name = ctx.table_name;
 
@@ -34,6 +37,7 @@ pub(crate) fn gen_insert_parts_sql_fn_tokens(
         let mut returning = SqlQuery::from(concat!(
             " RETURNING ", #all_returning
         ));
+        #counts_returning
 
         (insert, returning)
     })
@@ -45,6 +49,8 @@ pub(crate) fn gen_insert_values_sql_fn_tokens(
     let fields = ctx.field_columns.iter().filter(|(field_name, _)| {
         !ctx.serials
             .iter()
+            .chain(ctx.generated)
+            .chain(ctx.database_defaults)
             .map(|it| it.to_string())
             .any(|it| *field_name == it)
     });
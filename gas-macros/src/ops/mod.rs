@@ -20,6 +20,20 @@ fn make_params(fields: &[&(String, FieldNames)]) -> Vec<proc_macro2::TokenStream
         .collect()
 }
 
+// relation counts aren't columns, they're built at runtime from the metas of the counted models
+fn make_counts_returning(ctx: &ModelCtx, target: &str) -> Option<proc_macro2::TokenStream> {
+    if ctx.counts.is_empty() {
+        return None;
+    }
+
+    let target = Ident::new(target, Span::call_site());
+    let table_name = ctx.table_name;
+
+    Some(quote! {
+        #target.append_str(&gas::internals::relation_counts_returning(Self::VIRTUAL_FIELDS, #table_name));
+    })
+}
+
 fn make_all_returning(ctx: &ModelCtx) -> Option<String> {
     ctx.field_columns
         .iter()
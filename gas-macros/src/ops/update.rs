@@ -1,4 +1,4 @@
-use crate::ops::{make_all_returning, make_params};
+use crate::ops::{make_all_returning, make_counts_returning, make_params};
 use crate::{FieldNames, ModelCtx};
 use quote::quote;
 
@@ -13,6 +13,11 @@ pub(crate) fn gen_update_sql_fn_tokens(
                 .any(|it| *field_name == it)
         });
 
+    let normal_fields: Vec<_> = normal_fields
+        .into_iter()
+        .filter(|(field_name, _)| !ctx.generated.iter().any(|it| it == field_name))
+        .collect();
+
     let where_statement: Option<String> = pk_fields
         .iter()
         .map(|(_, FieldNames { column_name, .. })| format!("{}=?", column_name))
@@ -20,12 +25,21 @@ pub(crate) fn gen_update_sql_fn_tokens(
 
     let where_statement: String = where_statement.unwrap_or_else(|| "1=1".to_string());
 
-    let set_statement: Option<String> = normal_fields
+    // a model of only primary keys (e.g. a join table) has nothing to set,
+    //  the no-op assignment keeps the statement valid and still returns the row
+    let set_statement: String = normal_fields
         .iter()
         .map(|(_, FieldNames { column_name, .. })| format!("{}=?", column_name))
-        .reduce(|acc, curr| format!("{}, {}", acc, curr));
+        .reduce(|acc, curr| format!("{}, {}", acc, curr))
+        .or_else(|| {
+            pk_fields
+                .first()
+                .map(|(_, FieldNames { column_name, .. })| format!("{0}={0}", column_name))
+        })
+        .unwrap_or_default();
 
     let all_returning = make_all_returning(ctx);
+    let counts_returning = make_counts_returning(ctx, "sql");
 
     let field_params = make_params(
         &normal_fields
@@ -46,6 +60,7 @@ pub(crate) fn gen_update_sql_fn_tokens(
             " WHERE ", #where_statement,
             " RETURNING ", #all_returning
         ));
+        #counts_returning
 
         (sql, std::boxed::Box::new([#(#field_params),*]))
     })
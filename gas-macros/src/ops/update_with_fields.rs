@@ -1,4 +1,4 @@
-use crate::ops::make_all_returning;
+use crate::ops::{make_all_returning, make_counts_returning};
 use crate::{FieldNames, ModelCtx};
 use proc_macro2::{Ident, Span};
 use quote::quote;
@@ -14,6 +14,11 @@ pub(crate) fn gen_update_with_fields_sql_fn_tokens(
                 .any(|it| *field_name == it)
         });
 
+    let normal_fields: Vec<_> = normal_fields
+        .into_iter()
+        .filter(|(field_name, _)| !ctx.generated.iter().any(|it| it == field_name))
+        .collect();
+
     let where_statement: Option<String> = pk_fields
         .iter()
         .map(|(_, FieldNames { column_name, .. })| format!("{}=?", column_name))
@@ -26,6 +31,7 @@ pub(crate) fn gen_update_with_fields_sql_fn_tokens(
     };
 
     let all_returning = make_all_returning(ctx);
+    let counts_returning = make_counts_returning(ctx, "sql");
 
     let field_params = normal_fields.into_iter().map(|(field_path, _)| {
         let ident = Ident::new(field_path, Span::call_site());
@@ -62,6 +68,7 @@ pub(crate) fn gen_update_with_fields_sql_fn_tokens(
             " WHERE ", #where_statement,
             " RETURNING ", #all_returning
         ));
+        #counts_returning
 
         let mut params: Vec<PgParam> = Vec::with_capacity(fields.len() + #pk_count);
         #(#field_params)*;
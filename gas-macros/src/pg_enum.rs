@@ -0,0 +1,129 @@
+use crate::text_util;
+use darling::ast::Data;
+use darling::util::Ignored;
+use darling::{FromDeriveInput, FromVariant};
+use proc_macro::TokenStream;
+use proc_macro2::Ident;
+use quote::quote;
+use syn::spanned::Spanned;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(pg_enum), supports(enum_unit))]
+struct PgEnumArgs {
+    ident: Ident,
+    generics: syn::Generics,
+    data: Data<VariantArgs, Ignored>,
+    name: Option<String>,
+}
+
+#[derive(Debug, FromVariant)]
+#[darling(attributes(pg_enum))]
+struct VariantArgs {
+    ident: Ident,
+    rename: Option<String>,
+}
+
+// postgres truncates longer names and labels
+const MAX_LABEL_LENGTH: usize = 63;
+
+#[inline(always)]
+pub fn pg_enum_impl(input: TokenStream) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let derive_input = syn::parse::<syn::DeriveInput>(input)?;
+    let args: PgEnumArgs = FromDeriveInput::from_derive_input(&derive_input)?;
+
+    if !args.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            args.generics.span(),
+            "generic enums are not supported",
+        ));
+    }
+
+    let ident = &args.ident;
+    let name = args
+        .name
+        .unwrap_or_else(|| text_util::pascal_to_snake_case(&ident.to_string()));
+
+    // the name goes into CREATE/ALTER/DROP TYPE as it is, unquoted
+    if !is_plain_identifier(&name) {
+        return Err(syn::Error::new(
+            ident.span(),
+            format!(
+                "enum type name {:?} must be 1 to {} bytes of lowercase letters, digits and underscores, not starting with a digit",
+                name, MAX_LABEL_LENGTH
+            ),
+        ));
+    }
+
+    let Data::Enum(variants) = args.data else {
+        unreachable!("darling only accepts unit enums")
+    };
+
+    if variants.is_empty() {
+        return Err(syn::Error::new(
+            ident.span(),
+            "postgres enums need at least one variant",
+        ));
+    }
+
+    let mut variant_idents = Vec::with_capacity(variants.len());
+    let mut labels: Vec<String> = Vec::with_capacity(variants.len());
+
+    for variant in variants {
+        let label = variant
+            .rename
+            .unwrap_or_else(|| text_util::pascal_to_snake_case(&variant.ident.to_string()));
+
+        // labels are stored comma separated in the link section,
+        //  and migrations are split into statements on semicolons (quotes are escaped)
+        if label.is_empty() || label.len() > MAX_LABEL_LENGTH || label.contains([',', ';']) {
+            return Err(syn::Error::new(
+                variant.ident.span(),
+                format!(
+                    "enum labels must be 1 to {} bytes long and can't contain a comma or a semicolon",
+                    MAX_LABEL_LENGTH
+                ),
+            ));
+        }
+
+        if labels.contains(&label) {
+            return Err(syn::Error::new(
+                variant.ident.span(),
+                format!("duplicate enum label {:?}", label),
+            ));
+        }
+
+        variant_idents.push(variant.ident);
+        labels.push(label);
+    }
+
+    Ok(quote! {
+        impl gas::types::PgEnum for #ident {
+            const NAME: &'static str = #name;
+            const VARIANTS: &'static [&'static str] = &[#(#labels),*];
+
+            fn as_label(&self) -> &'static str {
+                match self {
+                    #(Self::#variant_idents => #labels,)*
+                }
+            }
+
+            fn from_label(label: &str) -> Option<Self> {
+                match label {
+                    #(#labels => Some(Self::#variant_idents),)*
+                    _ => None,
+                }
+            }
+        }
+    })
+}
+
+// [a-z_][a-z0-9_]*, what postgres takes without quotes and keeps as it is
+fn is_plain_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    name.len() <= MAX_LABEL_LENGTH
+        && chars
+            .next()
+            .is_some_and(|first| first.is_ascii_lowercase() || first == '_')
+        && chars.all(|char| char.is_ascii_lowercase() || char.is_ascii_digit() || char == '_')
+}
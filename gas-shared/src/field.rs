@@ -1,4 +1,5 @@
 use crate::internals::PgType;
+use std::borrow::Cow;
 use std::fmt::{Debug, Formatter};
 
 #[repr(u8)]
@@ -30,6 +31,145 @@ impl Debug for FieldFlags {
     }
 }
 
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IndexType {
+    BTree,
+    Hash,
+    Gist,
+    Gin,
+    Brin,
+}
+
+impl IndexType {
+    pub const fn as_sql(&self) -> &'static str {
+        match self {
+            IndexType::BTree => "BTREE",
+            IndexType::Hash => "HASH",
+            IndexType::Gist => "GIST",
+            IndexType::Gin => "GIN",
+            IndexType::Brin => "BRIN",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReferentialAction {
+    #[default]
+    NoAction,
+    Restrict,
+    Cascade,
+    SetNull,
+    SetDefault,
+}
+
+impl ReferentialAction {
+    pub const fn as_sql(&self) -> &'static str {
+        match self {
+            ReferentialAction::NoAction => "NO ACTION",
+            ReferentialAction::Restrict => "RESTRICT",
+            ReferentialAction::Cascade => "CASCADE",
+            ReferentialAction::SetNull => "SET NULL",
+            ReferentialAction::SetDefault => "SET DEFAULT",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ForeignKeyActions {
+    pub on_delete: ReferentialAction,
+    pub on_update: ReferentialAction,
+}
+
+impl ForeignKeyActions {
+    pub const NONE: ForeignKeyActions = ForeignKeyActions {
+        on_delete: ReferentialAction::NoAction,
+        on_update: ReferentialAction::NoAction,
+    };
+
+    // goes right after REFERENCES table(column), NO ACTION is the default so it's left out
+    pub fn as_sql(&self) -> String {
+        let mut sql = String::new();
+
+        if self.on_delete != ReferentialAction::NoAction {
+            sql.push_str(" ON DELETE ");
+            sql.push_str(self.on_delete.as_sql());
+        }
+
+        if self.on_update != ReferentialAction::NoAction {
+            sql.push_str(" ON UPDATE ");
+            sql.push_str(self.on_update.as_sql());
+        }
+
+        sql
+    }
+}
+
+// #[column(max_length = 255)] or #[column(precision = 18, scale = 4)]
+//  VARCHAR(n) for text, NUMERIC(p, s) for decimals and fractional seconds for TIMESTAMP(p), TIME(p), INTERVAL(p)
+#[derive(Clone, Copy, Debug, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypeModifiers {
+    pub max_length: Option<u32>,
+    pub precision: Option<u16>,
+    pub scale: Option<u16>,
+}
+
+impl TypeModifiers {
+    pub const NONE: TypeModifiers = TypeModifiers {
+        max_length: None,
+        precision: None,
+        scale: None,
+    };
+
+    // checked at compile time by the model macro, postgres would refuse the rest
+    pub const fn supports(&self, pg_type: &PgType) -> bool {
+        match (self.max_length, self.precision, self.scale) {
+            (None, None, None) => true,
+            (Some(length), None, None) => length > 0 && matches!(pg_type, PgType::TEXT),
+            (None, Some(precision), scale) if matches!(pg_type, PgType::DECIMAL) => {
+                let scale_fits = match scale {
+                    Some(scale) => scale <= precision,
+                    None => true,
+                };
+
+                precision >= 1 && precision <= 1000 && scale_fits
+            }
+            (None, Some(precision), None) => {
+                precision <= 6
+                    && matches!(
+                        pg_type,
+                        PgType::TIMESTAMP | PgType::TIMESTAMP_TZ | PgType::TIME | PgType::INTERVAL
+                    )
+            }
+            _ => false,
+        }
+    }
+
+    // None when there's nothing to add to the plain type
+    pub fn apply(&self, pg_type: &PgType) -> Option<String> {
+        match pg_type {
+            PgType::TEXT => self.max_length.map(|length| format!("VARCHAR({length})")),
+            PgType::DECIMAL => {
+                let precision = self.precision?;
+                Some(match self.scale {
+                    Some(scale) => format!("NUMERIC({precision}, {scale})"),
+                    None => format!("NUMERIC({precision})"),
+                })
+            }
+            PgType::TIMESTAMP => self.precision.map(|it| format!("TIMESTAMP({it})")),
+            PgType::TIMESTAMP_TZ => self
+                .precision
+                .map(|it| format!("TIMESTAMP({it}) WITH TIME ZONE")),
+            PgType::TIME => self.precision.map(|it| format!("TIME({it})")),
+            PgType::INTERVAL => self.precision.map(|it| format!("INTERVAL({it})")),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Hash, Eq, PartialEq)]
 pub struct FieldMeta {
     // a lot of names
@@ -39,7 +179,20 @@ pub struct FieldMeta {
     pub alias_name: &'static str,  // table_column
     pub struct_name: &'static str, // table_column
     pub pg_type: PgType,
+    pub type_modifiers: TypeModifiers,
     pub default_sql: Option<&'static str>,
+    // GENERATED ALWAYS AS (...) STORED, the column is never written to by the ORM
+    pub generated_sql: Option<&'static str>,
+    pub index_type: Option<IndexType>,
+    pub foreign_key_actions: ForeignKeyActions,
     pub flags: FieldFlags,
     pub index: usize,
 }
+
+impl FieldMeta {
+    // the type as it goes into CREATE TABLE, modifiers included
+    pub fn column_sql_type(&self) -> Cow<'static, str> {
+        self.pg_type
+            .as_column_sql_type(self.flags.has_flag(FieldFlag::Serial), &self.type_modifiers)
+    }
+}
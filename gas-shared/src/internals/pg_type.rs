@@ -1,4 +1,4 @@
-use crate::field::FieldMeta;
+use crate::field::{FieldMeta, TypeModifiers};
 use std::borrow::Cow;
 
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
@@ -17,7 +17,22 @@ pub enum PgType {
     TIMESTAMP_TZ,
     DATE,
     TIME,
+    INTERVAL,
     JSONB,
+    TSVECTOR,
+    UUID,
+    BYTEA,
+    INET,
+    CIDR,
+    MACADDR,
+    // a CREATE TYPE .. AS ENUM of its own, see #[derive(PgEnum)] (skipped for serde like FOREIGN_KEY)
+    #[cfg_attr(feature = "serde", serde(skip))]
+    ENUM {
+        // serde wants to borrow &str fields from the input, even in a skipped variant
+        #[cfg_attr(feature = "serde", serde(skip_deserializing))]
+        name: &'static str,
+        variants: &'static [&'static str],
+    },
     // we can skip this for serde
     //  every time serde is needed, it should go through the PortablePgType type
     //  which handles the FOREIGN_KEY variant differently
@@ -27,10 +42,34 @@ pub enum PgType {
         key_type: &'static PgType,
         target_field: &'static FieldMeta,
     },
+    // not a column, a table constraint over columns of the model (skipped for serde like FOREIGN_KEY)
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[allow(nonstandard_style)]
+    COMPOSITE_FOREIGN_KEY {
+        columns: &'static [&'static FieldMeta],
+        target_columns: &'static [&'static FieldMeta],
+    },
+    // not a column either, a read-only count of the rows pointing at the model through foreign_key
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[allow(nonstandard_style)]
+    RELATION_COUNT {
+        foreign_key: &'static FieldMeta,
+    },
     IGNORED,
 }
 
 impl PgType {
+    pub fn as_column_sql_type(
+        &self,
+        is_serial: bool,
+        modifiers: &TypeModifiers,
+    ) -> Cow<'static, str> {
+        match modifiers.apply(self) {
+            Some(sql_type) => sql_type.into(),
+            None => self.as_sql_type(is_serial),
+        }
+    }
+
     pub fn as_sql_type(&self, is_serial: bool) -> Cow<'static, str> {
         match self {
             PgType::FOREIGN_KEY {
@@ -43,14 +82,52 @@ impl PgType {
                 target_field.name
             )
             .into(),
+            PgType::COMPOSITE_FOREIGN_KEY { .. } | PgType::RELATION_COUNT { .. } => "".into(),
             _ => self.as_sql_type_const(is_serial).into(),
         }
     }
 
+    // FOREIGN KEY (a, b) REFERENCES table(x, y), only for composite foreign keys
+    pub fn as_constraint_sql(&self) -> Option<String> {
+        let PgType::COMPOSITE_FOREIGN_KEY {
+            columns,
+            target_columns,
+        } = self
+        else {
+            return None;
+        };
+
+        let join = |fields: &[&FieldMeta]| {
+            fields
+                .iter()
+                .map(|field| field.name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        Some(format!(
+            "FOREIGN KEY ({}) REFERENCES {}({})",
+            join(columns),
+            target_columns.first()?.table_name,
+            join(target_columns)
+        ))
+    }
+
+    // CREATE TYPE name AS ENUM ('a', 'b'), only for enum types
+    pub fn as_create_type_sql(&self) -> Option<String> {
+        let PgType::ENUM { name, variants } = self else {
+            return None;
+        };
+
+        Some(create_enum_type_sql(name, variants.iter().copied()))
+    }
+
     // NOTE: panics
     pub const fn as_sql_type_const(&self, is_serial: bool) -> &'static str {
         match self {
             PgType::FOREIGN_KEY { .. } => panic!("can not evaluate foreign key at const time"),
+            PgType::COMPOSITE_FOREIGN_KEY { .. } => "",
+            PgType::RELATION_COUNT { .. } => "",
 
             PgType::TEXT => "TEXT",
             PgType::BOOLEAN => "BOOLEAN",
@@ -67,8 +144,29 @@ impl PgType {
             PgType::TIMESTAMP_TZ => "TIMESTAMP WITH TIME ZONE",
             PgType::DATE => "DATE",
             PgType::TIME => "TIME",
+            PgType::INTERVAL => "INTERVAL",
             PgType::JSONB => "JSONB",
+            PgType::TSVECTOR => "TSVECTOR",
+            PgType::UUID => "UUID",
+            PgType::BYTEA => "BYTEA",
+            PgType::INET => "INET",
+            PgType::CIDR => "CIDR",
+            PgType::MACADDR => "MACADDR",
+            PgType::ENUM { name, .. } => name,
             PgType::IGNORED => "",
         }
     }
 }
+
+pub fn create_enum_type_sql<'a>(name: &str, variants: impl Iterator<Item = &'a str>) -> String {
+    format!(
+        "CREATE TYPE {} AS ENUM ({})",
+        name,
+        variants.map(enum_label_sql).collect::<Vec<_>>().join(", ")
+    )
+}
+
+// labels are string literals in ddl
+pub fn enum_label_sql(label: &str) -> String {
+    format!("'{}'", label.replace('\'', "''"))
+}
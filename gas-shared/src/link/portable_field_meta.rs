@@ -1,7 +1,8 @@
 use crate::error::GasSharedError;
 use crate::link::portable_pg_type::PortablePgType;
 use crate::link::FixedStr;
-use crate::{FieldFlags, FieldMeta};
+use crate::{FieldFlag, FieldFlags, FieldMeta, ForeignKeyActions, IndexType, TypeModifiers};
+use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
@@ -10,7 +11,16 @@ pub struct PortableFieldMeta {
     pub table_name: FixedStr,
     pub name: FixedStr,
     pub pg_type: PortablePgType,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub type_modifiers: TypeModifiers,
     pub default_sql: Option<FixedStr>,
+    // defaulted so states written before these existed still load
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub generated_sql: Option<FixedStr<512>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub index_type: Option<IndexType>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub foreign_key_actions: ForeignKeyActions,
     pub flags: FieldFlags,
     pub index: usize,
 }
@@ -23,7 +33,11 @@ impl TryFrom<FieldMeta> for PortableFieldMeta {
             table_name: FixedStr::try_from(meta.table_name)?,
             name: FixedStr::try_from(meta.name)?,
             pg_type: PortablePgType::try_from(meta.pg_type)?,
+            type_modifiers: meta.type_modifiers,
             default_sql: meta.default_sql.map(FixedStr::try_from).transpose()?,
+            generated_sql: meta.generated_sql.map(FixedStr::try_from).transpose()?,
+            index_type: meta.index_type,
+            foreign_key_actions: meta.foreign_key_actions,
             flags: meta.flags,
             index: meta.index,
         })
@@ -36,6 +50,7 @@ impl PortableFieldMeta {
             table_name: FixedStr::from_panicking(meta.table_name),
             name: FixedStr::from_panicking(meta.name),
             pg_type: PortablePgType::from_unchecked(meta.pg_type),
+            type_modifiers: meta.type_modifiers,
             default_sql: {
                 if let Some(sql) = meta.default_sql {
                     Some(FixedStr::from_panicking(sql))
@@ -43,10 +58,25 @@ impl PortableFieldMeta {
                     None
                 }
             },
+            generated_sql: {
+                if let Some(sql) = meta.generated_sql {
+                    Some(FixedStr::from_panicking(sql))
+                } else {
+                    None
+                }
+            },
+            index_type: meta.index_type,
+            foreign_key_actions: meta.foreign_key_actions,
             flags: meta.flags,
             index: meta.index,
         }
     }
+
+    // same as FieldMeta::column_sql_type
+    pub fn column_sql_type(&self) -> Cow<'_, str> {
+        self.pg_type
+            .as_column_sql_type(self.flags.has_flag(FieldFlag::Serial), &self.type_modifiers)
+    }
 }
 
 impl Display for PortableFieldMeta {
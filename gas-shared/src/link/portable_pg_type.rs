@@ -1,8 +1,11 @@
 use crate::error::GasSharedError;
-use crate::internals::PgType;
+use crate::field::{FieldMeta, TypeModifiers};
+use crate::internals::{create_enum_type_sql, PgType};
 use crate::link::FixedStr;
 use std::borrow::Cow;
 
+// can't box anything here, the link section is copied out of the binary byte by byte
+#[allow(clippy::large_enum_variant)]
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PortablePgType {
@@ -12,6 +15,83 @@ pub enum PortablePgType {
         target_table_name: FixedStr,
         target_column_name: FixedStr,
     },
+    // column lists are comma separated, in the same order on both sides
+    CompositeForeignKey {
+        columns: FixedStr<128>,
+        target_table_name: FixedStr,
+        target_columns: FixedStr<128>,
+    },
+    // labels are comma separated, in declaration order (which is also the sort order in postgres)
+    Enum {
+        name: FixedStr,
+        variants: FixedStr<512>,
+    },
+}
+
+const fn join_names<const SIZE: usize>(fields: &[&FieldMeta]) -> FixedStr<SIZE> {
+    let mut buffer = [0u8; SIZE];
+    let mut len = 0;
+
+    let mut index = 0;
+    while index < fields.len() {
+        if index > 0 {
+            assert!(len + 2 <= SIZE);
+            buffer[len] = b',';
+            buffer[len + 1] = b' ';
+            len += 2;
+        }
+
+        let name = fields[index].name.as_bytes();
+        assert!(len + name.len() <= SIZE);
+
+        let mut byte = 0;
+        while byte < name.len() {
+            buffer[len] = name[byte];
+            len += 1;
+            byte += 1;
+        }
+
+        index += 1;
+    }
+
+    FixedStr(buffer)
+}
+
+const fn join_labels<const SIZE: usize>(labels: &[&str]) -> FixedStr<SIZE> {
+    let mut buffer = [0u8; SIZE];
+    let mut len = 0;
+
+    let mut index = 0;
+    while index < labels.len() {
+        if index > 0 {
+            assert!(len + 2 <= SIZE);
+            buffer[len] = b',';
+            buffer[len + 1] = b' ';
+            len += 2;
+        }
+
+        let label = labels[index].as_bytes();
+        assert!(len + label.len() <= SIZE);
+
+        let mut byte = 0;
+        while byte < label.len() {
+            buffer[len] = label[byte];
+            len += 1;
+            byte += 1;
+        }
+
+        index += 1;
+    }
+
+    FixedStr(buffer)
+}
+
+fn names(fields: &[&FieldMeta]) -> String {
+    fields
+        .iter()
+        .map(|field| field.name)
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 impl TryFrom<PgType> for PortablePgType {
@@ -28,6 +108,23 @@ impl TryFrom<PgType> for PortablePgType {
                 target_table_name: FixedStr::try_from(target_field.table_name)?,
                 target_column_name: FixedStr::try_from(target_field.name)?,
             },
+            PgType::COMPOSITE_FOREIGN_KEY {
+                columns,
+                target_columns,
+            } => Self::CompositeForeignKey {
+                columns: FixedStr::try_from(names(columns).as_str())?,
+                target_table_name: FixedStr::try_from(
+                    target_columns
+                        .first()
+                        .map(|field| field.table_name)
+                        .unwrap_or_default(),
+                )?,
+                target_columns: FixedStr::try_from(names(target_columns).as_str())?,
+            },
+            PgType::ENUM { name, variants } => Self::Enum {
+                name: FixedStr::try_from(name)?,
+                variants: FixedStr::try_from(variants.join(", ").as_str())?,
+            },
             _ => Self::Raw(pg_type),
         })
     }
@@ -45,10 +142,30 @@ impl PortablePgType {
                 target_table_name: FixedStr::from_panicking(target_field.table_name),
                 target_column_name: FixedStr::from_panicking(target_field.name),
             },
+            PgType::COMPOSITE_FOREIGN_KEY {
+                columns,
+                target_columns,
+            } => Self::CompositeForeignKey {
+                columns: join_names(columns),
+                target_table_name: FixedStr::from_panicking(target_columns[0].table_name),
+                target_columns: join_names(target_columns),
+            },
+            PgType::ENUM { name, variants } => Self::Enum {
+                name: FixedStr::from_panicking(name),
+                variants: join_labels(variants),
+            },
             _ => Self::Raw(pg_type),
         }
     }
 
+    // only plain types take modifiers, foreign keys follow the referenced column
+    pub fn as_column_sql_type(&self, is_serial: bool, modifiers: &TypeModifiers) -> Cow<'_, str> {
+        match self {
+            PortablePgType::Raw(pg_type) => pg_type.as_column_sql_type(is_serial, modifiers),
+            _ => self.as_sql_type(is_serial),
+        }
+    }
+
     pub fn as_sql_type(&self, is_serial: bool) -> Cow<'_, str> {
         match self {
             PortablePgType::Raw(pg_type) => pg_type.as_sql_type(is_serial),
@@ -63,15 +180,58 @@ impl PortablePgType {
                 target_column_name.as_ref()
             )
             .into(),
+            PortablePgType::CompositeForeignKey { .. } => "".into(),
+            PortablePgType::Enum { name, .. } => name.as_ref().into(),
         }
     }
+
+    pub fn enum_variants(&self) -> Option<impl Iterator<Item = &str>> {
+        let PortablePgType::Enum { variants, .. } = self else {
+            return None;
+        };
+
+        Some(variants.as_ref().split(", ").filter(|it| !it.is_empty()))
+    }
+
+    pub fn as_create_type_sql(&self) -> Option<String> {
+        let PortablePgType::Enum { name, .. } = self else {
+            return None;
+        };
+
+        Some(create_enum_type_sql(name.as_ref(), self.enum_variants()?))
+    }
+
+    pub fn as_constraint_sql(&self) -> Option<String> {
+        let PortablePgType::CompositeForeignKey {
+            columns,
+            target_table_name,
+            target_columns,
+        } = self
+        else {
+            return None;
+        };
+
+        Some(format!(
+            "FOREIGN KEY ({}) REFERENCES {}({})",
+            columns.as_ref(),
+            target_table_name.as_ref(),
+            target_columns.as_ref()
+        ))
+    }
 }
 
-impl From<PortablePgType> for PgType {
-    fn from(pg_type: PortablePgType) -> Self {
+// the other variants point to other fields (or own their labels), that's lost in the portable form
+impl TryFrom<PortablePgType> for PgType {
+    type Error = GasSharedError;
+
+    fn try_from(pg_type: PortablePgType) -> Result<Self, Self::Error> {
         match pg_type {
-            PortablePgType::Raw(pg_type) => pg_type,
-            PortablePgType::ForeignKey { .. } => todo!(),
+            PortablePgType::Raw(pg_type) => Ok(pg_type),
+            PortablePgType::ForeignKey { .. }
+            | PortablePgType::CompositeForeignKey { .. }
+            | PortablePgType::Enum { .. } => Err(GasSharedError::InternalError(Cow::from(
+                "only plain types can be converted back to a PgType",
+            ))),
         }
     }
 }
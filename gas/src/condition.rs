@@ -1,5 +1,5 @@
-use crate::internals::PgParam;
 use crate::internals::SqlQuery;
+use crate::internals::{PgArrayParam, PgParam};
 use std::ops::{BitAnd, BitOr};
 
 #[derive(Debug, Clone)]
@@ -13,6 +13,13 @@ pub enum Condition {
         lhs: Box<Condition>,
         rhs: Box<Condition>,
     },
+    // [NOT] EXISTS (SELECT 1 FROM (select WHERE condition) AS __related WHERE link), see relation filters
+    Exists {
+        negated: bool,
+        select: String,
+        link: String,
+        condition: Box<Condition>,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -48,6 +55,67 @@ impl EqExpression {
     }
 }
 
+// escape hatch for conditions the field methods can't express, e.g. `lower(name) = ?`
+//  placeholders are counted against params when the query runs
+pub fn raw_condition(sql: impl Into<String>, params: &[PgParam]) -> EqExpression {
+    EqExpression::new(Condition::Basic(sql.into()), params.to_vec())
+}
+
+// column=ANY(?) with all the values in one array, the query stays the same for any amount of them
+//  enums and custom types can't be sent as arrays, those fall back to column IN (?, ?, ...)
+pub(crate) fn one_of_condition(column: &str, values: Vec<PgParam>) -> EqExpression {
+    // an empty array has no type postgres could compare the column with
+    if values.is_empty() {
+        return EqExpression::new(Condition::Basic("FALSE".to_string()), vec![]);
+    }
+
+    match PgArrayParam::try_collect(values) {
+        Ok(array) => any_condition(column, array),
+        Err(values) => in_condition(column, values),
+    }
+}
+
+// postgres takes at most this many parameters per statement
+pub(crate) const MAX_PARAMS: usize = u16::MAX as usize;
+
+// one_of_condition for any amount of keys, one condition (and statement) per max_params values
+//  of the IN (...) fallback, an array is always just one
+pub(crate) fn one_of_conditions(
+    column: &str,
+    values: Vec<PgParam>,
+    max_params: usize,
+) -> Vec<EqExpression> {
+    if values.is_empty() {
+        return vec![one_of_condition(column, values)];
+    }
+
+    match PgArrayParam::try_collect(values) {
+        Ok(array) => vec![any_condition(column, array)],
+        Err(values) => values
+            .chunks(max_params)
+            .map(|chunk| in_condition(column, chunk.to_vec()))
+            .collect(),
+    }
+}
+
+fn any_condition(column: &str, array: PgArrayParam) -> EqExpression {
+    EqExpression::new(
+        Condition::Basic(format!("{}=ANY(?)", column)),
+        vec![PgParam::ARRAY(array)],
+    )
+}
+
+fn in_condition(column: &str, values: Vec<PgParam>) -> EqExpression {
+    EqExpression::new(
+        Condition::Basic(format!(
+            "{} IN ({})",
+            column,
+            vec!["?"; values.len()].join(", ")
+        )),
+        values,
+    )
+}
+
 impl BitAnd for EqExpression {
     type Output = EqExpression;
 
@@ -84,6 +152,24 @@ impl Condition {
                 sql.append_query(&rhs.as_sql());
                 sql.append_str(")");
             }
+            Condition::Exists {
+                negated,
+                select,
+                link,
+                condition,
+            } => {
+                if *negated {
+                    sql.append_str("NOT ");
+                }
+
+                sql.append_str("EXISTS (SELECT 1 FROM (");
+                sql.append_str(select);
+                sql.append_str(" WHERE ");
+                sql.append_query(&condition.as_sql());
+                sql.append_str(") AS __related WHERE ");
+                sql.append_str(link);
+                sql.append_str(")");
+            }
         };
 
         sql
@@ -1,7 +1,7 @@
 use crate::error::GasError;
 use crate::internals::SqlQuery;
 use crate::internals::{pg_param_all, PgParam};
-use crate::row::{FromRow, ResponseCtx, Row};
+use crate::row::{FromRow, RelationCtx, ResponseCtx, ResponseIncludes, Row};
 use crate::GasResult;
 use sqlx::postgres::{PgArguments, PgPoolOptions};
 use sqlx::Arguments;
@@ -71,36 +71,56 @@ impl PgTransaction {
     }
 }
 
+// the executor a response came from, kept around to load its relations with
+//  so they see the same data as the query itself (e.g. uncommitted writes of a transaction)
+#[derive(Clone)]
+pub(crate) enum RelationExecutor {
+    Connection(PgConnection),
+    Transaction(PgTransaction),
+}
+
 pub(crate) trait PgExecutor: Copy + Sized {
     async fn execute(self, sql: SqlQuery, params: &[PgParam]) -> GasResult<Vec<Row>>;
 
-    fn get_backing_connection(&self) -> PgConnection;
+    fn relation_executor(&self) -> RelationExecutor;
 
     async fn execute_parsed<T: FromRow>(
         self,
         sql: SqlQuery<'_>,
         params: &[PgParam],
     ) -> GasResult<Vec<T>> {
-        let connection = self.get_backing_connection();
+        self.execute_parsed_including(sql, params, ResponseIncludes::default())
+            .await
+    }
 
+    // includes are the joins to decode and inverse relations that should be loaded even if they're lazy
+    async fn execute_parsed_including<T: FromRow>(
+        self,
+        sql: SqlQuery<'_>,
+        params: &[PgParam],
+        includes: ResponseIncludes,
+    ) -> GasResult<Vec<T>> {
         let rows = self.execute(sql, params).await?;
 
-        tokio::task::spawn_blocking(move || {
-            let ctx = ResponseCtx {
-                all_rows: &rows,
-                connection,
-            };
+        let mut items = {
+            let ctx = ResponseCtx::new(&rows, &includes);
 
             rows.iter()
                 .map(|row| FromRow::from_row(&ctx, row))
-                .collect::<Result<Vec<T>, _>>()
-        })
-        .await
-        .unwrap()
+                .collect::<Result<Vec<T>, _>>()?
+        };
+
+        let ctx = RelationCtx {
+            executor: self.relation_executor(),
+            includes: includes.relations,
+        };
+        T::load_relations(items.iter_mut().collect(), &ctx).await?;
+
+        Ok(items)
     }
 
     fn prepare_query(sql: SqlQuery, params: &[PgParam]) -> GasResult<(String, PgArguments)> {
-        let query = sql.finish()?;
+        let query = sql.finish(params)?;
 
         tracing::trace!(sql = query, params = ?params, "executing query");
 
@@ -128,8 +148,8 @@ impl PgExecutor for &PgConnection {
         Ok(rows.into_iter().map(Row::from).collect())
     }
 
-    fn get_backing_connection(&self) -> PgConnection {
-        PgConnection::clone(self)
+    fn relation_executor(&self) -> RelationExecutor {
+        RelationExecutor::Connection(PgConnection::clone(self))
     }
 }
 
@@ -146,8 +166,21 @@ impl PgExecutor for &PgTransaction {
         Ok(rows.into_iter().map(Row::from).collect())
     }
 
-    fn get_backing_connection(&self) -> PgConnection {
-        self.connection.clone()
+    fn relation_executor(&self) -> RelationExecutor {
+        RelationExecutor::Transaction(PgTransaction::clone(self))
+    }
+}
+
+impl PgExecutor for &RelationExecutor {
+    async fn execute(self, sql: SqlQuery<'_>, params: &[PgParam]) -> GasResult<Vec<Row>> {
+        match self {
+            RelationExecutor::Connection(connection) => connection.execute(sql, params).await,
+            RelationExecutor::Transaction(transaction) => transaction.execute(sql, params).await,
+        }
+    }
+
+    fn relation_executor(&self) -> RelationExecutor {
+        RelationExecutor::clone(self)
     }
 }
 
@@ -162,3 +195,57 @@ pub trait PgRawExecutor: PgExecutor {
 }
 
 impl<T: PgExecutor> PgRawExecutor for T {}
+
+// nothing listens there, any query fails
+#[cfg(test)]
+pub(crate) fn unreachable() -> RelationExecutor {
+    let pool = PgPoolOptions::new()
+        .acquire_timeout(std::time::Duration::from_millis(200))
+        .connect_lazy("postgres://gas@127.0.0.1:1/gas")
+        .unwrap();
+
+    RelationExecutor::Connection(PgConnection {
+        pool: Arc::new(pool),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::*;
+    use crate::ModelOps;
+
+    #[tokio::test]
+    pub async fn test_lazy_includes() {
+        let includes = document::Model::query()
+            .include_many(document::comments)
+            .response_includes()
+            .relations;
+        assert_eq!(includes, vec![document::comments.full_name]);
+
+        let mut documents = [document::Model::default(), document::Model::default()];
+
+        // not included, stays unloaded without a query
+        let ctx = RelationCtx {
+            executor: unreachable(),
+            includes: vec![],
+        };
+        assert!(!ctx.is_included(&document::comments.meta));
+        FromRow::load_relations(documents.iter_mut().collect(), &ctx)
+            .await
+            .unwrap();
+        assert!(documents.iter().all(|it| !it.comments.is_loaded()));
+
+        // included, the query is attempted
+        let ctx = RelationCtx {
+            executor: unreachable(),
+            includes,
+        };
+        assert!(ctx.is_included(&document::comments.meta));
+        assert!(
+            FromRow::load_relations(documents.iter_mut().collect(), &ctx)
+                .await
+                .is_err()
+        );
+    }
+}
@@ -1,9 +1,14 @@
-use crate::condition::{Condition, EqExpression};
+use crate::condition::{one_of_condition, Condition, EqExpression};
 use crate::field::Field;
 use crate::internals::{AsPgType, PgParam};
-use crate::types::Decimal;
-use crate::ModelSidecar;
-use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use crate::relation::composite::{
+    CompositeField, CompositeKey, CompositeKeySpec, CompositeRelation,
+};
+use crate::relation::forward::FullRelation;
+use crate::types::{Decimal, PgCustomType};
+use crate::{ModelMeta, ModelSidecar};
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Utc};
+use std::ops::{Add, Sub};
 
 pub trait PgEq<T> {
     fn eq(&self, other: T) -> EqExpression;
@@ -27,6 +32,26 @@ pub trait PgEqNone {
     fn is_not_null(&self) -> EqExpression;
 }
 
+// forward relations compare by their foreign key, typed as Fk
+//  book::author.eq_key(5), book::author.eq_model(&author)
+pub trait PgRelationEq<Fk, Model> {
+    fn eq_key(&self, key: Fk) -> EqExpression;
+
+    fn eq_model(&self, model: &Model) -> EqExpression;
+
+    fn one_of_keys(&self, keys: &[Fk]) -> EqExpression;
+}
+
+// subnet containment on INET and CIDR columns
+#[cfg(feature = "ipnetwork")]
+pub trait PgEqNetwork {
+    // column <<= network, the address (or subnet) is inside the network
+    fn within<N: Into<ipnetwork::IpNetwork>>(&self, network: N) -> EqExpression;
+
+    // column >>= network, the column is a network around the address (or subnet)
+    fn contains<N: Into<ipnetwork::IpNetwork>>(&self, network: N) -> EqExpression;
+}
+
 pub trait PgEqTime {
     fn is_now(&self) -> EqExpression;
     fn is_before_now(&self) -> EqExpression;
@@ -35,17 +60,29 @@ pub trait PgEqTime {
     fn is_now_or_after(&self) -> EqExpression;
 }
 
+// compared to NOW() by the database, see RelativeTime
+pub trait PgEqRelativeTime {
+    // between NOW() - delta and NOW()
+    fn within_last(&self, delta: TimeDelta) -> EqExpression;
+    fn older_than(&self, delta: TimeDelta) -> EqExpression;
+    fn is_before(&self, time: RelativeTime) -> EqExpression;
+    fn is_after(&self, time: RelativeTime) -> EqExpression;
+}
+
 impl<T, M: ModelSidecar> PgEqNone for Field<Option<T>, M>
 where
     Option<T>: AsPgType,
 {
     fn is_null(&self) -> EqExpression {
-        EqExpression::new(Condition::Basic(format!("{} IS NULL", self.name)), vec![])
+        EqExpression::new(
+            Condition::Basic(format!("{} IS NULL", self.full_name)),
+            vec![],
+        )
     }
 
     fn is_not_null(&self) -> EqExpression {
         EqExpression::new(
-            Condition::Basic(format!("{} IS NOT NULL", self.name)),
+            Condition::Basic(format!("{} IS NOT NULL", self.full_name)),
             vec![],
         )
     }
@@ -61,6 +98,176 @@ fn make_in_expression(name: &str, params: usize) -> String {
     )
 }
 
+fn relation_key_of<Fk: AsPgType + 'static, Model: ModelMeta, const FIELD_INDEX: usize>(
+    model: &Model,
+) -> Fk {
+    let field = Model::FIELDS
+        .get(FIELD_INDEX)
+        .expect("field relation is not correctly defined");
+
+    model
+        .get_by_field(field)
+        .expect("foreign key should be accessible by field")
+}
+
+impl<Fk: AsPgType + 'static, Model: ModelMeta, const FIELD_INDEX: usize, M: ModelSidecar>
+    PgRelationEq<Fk, Model> for Field<FullRelation<Fk, Model, FIELD_INDEX>, M>
+where
+    PgParam: From<Fk>,
+    FullRelation<Fk, Model, FIELD_INDEX>: AsPgType,
+{
+    fn eq_key(&self, key: Fk) -> EqExpression {
+        EqExpression::new(
+            Condition::Basic(format!("{}=?", self.full_name)),
+            vec![PgParam::from(key)],
+        )
+    }
+
+    fn eq_model(&self, model: &Model) -> EqExpression {
+        self.eq_key(relation_key_of::<Fk, Model, FIELD_INDEX>(model))
+    }
+
+    fn one_of_keys(&self, keys: &[Fk]) -> EqExpression {
+        one_of_condition(
+            self.full_name,
+            keys.iter().cloned().map(PgParam::from).collect(),
+        )
+    }
+}
+
+// is_null comes from PgEqNone
+impl<Fk: AsPgType + 'static, Model: ModelMeta, const FIELD_INDEX: usize, M: ModelSidecar>
+    PgRelationEq<Fk, Model> for Field<Option<FullRelation<Fk, Model, FIELD_INDEX>>, M>
+where
+    PgParam: From<Fk>,
+    Option<FullRelation<Fk, Model, FIELD_INDEX>>: AsPgType,
+{
+    fn eq_key(&self, key: Fk) -> EqExpression {
+        EqExpression::new(
+            Condition::Basic(format!("{}=?", self.full_name)),
+            vec![PgParam::from(key)],
+        )
+    }
+
+    fn eq_model(&self, model: &Model) -> EqExpression {
+        self.eq_key(relation_key_of::<Fk, Model, FIELD_INDEX>(model))
+    }
+
+    fn one_of_keys(&self, keys: &[Fk]) -> EqExpression {
+        one_of_condition(
+            self.full_name,
+            keys.iter().cloned().map(PgParam::from).collect(),
+        )
+    }
+}
+
+// (tenant_id, order_no)=(?, ?), the field itself has no column
+fn composite_columns<Spec: CompositeKeySpec>() -> String {
+    Spec::COLUMNS
+        .iter()
+        .map(|column| column.full_name)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn composite_tuple<Spec: CompositeKeySpec>() -> String {
+    format!("({})", vec!["?"; Spec::COLUMNS.len()].join(", "))
+}
+
+fn composite_eq_key<Fk: CompositeKey, Spec: CompositeKeySpec>(key: Fk) -> EqExpression {
+    EqExpression::new(
+        Condition::Basic(format!(
+            "({})={}",
+            composite_columns::<Spec>(),
+            composite_tuple::<Spec>()
+        )),
+        key.into_params(),
+    )
+}
+
+fn composite_key_of<Fk: CompositeKey, Model: ModelMeta, Spec: CompositeKeySpec>(
+    model: &Model,
+) -> Fk {
+    Fk::from_model(model, Spec::TARGET_COLUMNS)
+        .expect("composite key should be accessible by fields")
+}
+
+fn composite_one_of<Fk: CompositeKey, Spec: CompositeKeySpec>(keys: &[Fk]) -> EqExpression {
+    if keys.is_empty() {
+        return EqExpression::new(Condition::Basic("FALSE".to_string()), vec![]);
+    }
+
+    EqExpression::new(
+        Condition::Basic(format!(
+            "({}) IN ({})",
+            composite_columns::<Spec>(),
+            vec![composite_tuple::<Spec>(); keys.len()].join(", ")
+        )),
+        keys.iter().cloned().flat_map(Fk::into_params).collect(),
+    )
+}
+
+impl<Fk: CompositeKey, Model: ModelMeta, Spec: CompositeKeySpec, M: ModelSidecar>
+    PgRelationEq<Fk, Model> for CompositeField<CompositeRelation<Fk, Model, Spec>, M>
+{
+    fn eq_key(&self, key: Fk) -> EqExpression {
+        composite_eq_key::<Fk, Spec>(key)
+    }
+
+    fn eq_model(&self, model: &Model) -> EqExpression {
+        self.eq_key(composite_key_of::<Fk, Model, Spec>(model))
+    }
+
+    fn one_of_keys(&self, keys: &[Fk]) -> EqExpression {
+        composite_one_of::<Fk, Spec>(keys)
+    }
+}
+
+impl<Fk: CompositeKey, Model: ModelMeta, Spec: CompositeKeySpec, M: ModelSidecar>
+    PgRelationEq<Fk, Model> for CompositeField<Option<CompositeRelation<Fk, Model, Spec>>, M>
+{
+    fn eq_key(&self, key: Fk) -> EqExpression {
+        composite_eq_key::<Fk, Spec>(key)
+    }
+
+    fn eq_model(&self, model: &Model) -> EqExpression {
+        self.eq_key(composite_key_of::<Fk, Model, Spec>(model))
+    }
+
+    fn one_of_keys(&self, keys: &[Fk]) -> EqExpression {
+        composite_one_of::<Fk, Spec>(keys)
+    }
+}
+
+// a single null column is enough for no relation (MATCH SIMPLE), same as when it's read
+impl<Fk: CompositeKey, Model: ModelMeta, Spec: CompositeKeySpec, M: ModelSidecar> PgEqNone
+    for CompositeField<Option<CompositeRelation<Fk, Model, Spec>>, M>
+{
+    fn is_null(&self) -> EqExpression {
+        let columns = Spec::COLUMNS
+            .iter()
+            .map(|column| format!("{} IS NULL", column.full_name))
+            .collect::<Vec<_>>();
+
+        EqExpression::new(
+            Condition::Basic(format!("({})", columns.join(" OR "))),
+            vec![],
+        )
+    }
+
+    fn is_not_null(&self) -> EqExpression {
+        let columns = Spec::COLUMNS
+            .iter()
+            .map(|column| format!("{} IS NOT NULL", column.full_name))
+            .collect::<Vec<_>>();
+
+        EqExpression::new(
+            Condition::Basic(format!("({})", columns.join(" AND "))),
+            vec![],
+        )
+    }
+}
+
 macro_rules! pg_eq_impl {
     ($field_type:ty as $cmp_type:ty, $pg_param:expr) => {
         impl<M: ModelSidecar> PgEq<$cmp_type> for Field<$field_type, M> {
@@ -282,6 +489,25 @@ pg_eq_impl!(Option<Decimal> as u64, PgParam::DECIMAL);
 pg_eq_impl!(Option<Decimal> as u128, PgParam::DECIMAL);
 pg_eq_impl!(Option<Decimal> as usize, PgParam::DECIMAL);
 
+// uuid
+#[cfg(feature = "uuid")]
+pg_eq_impl!(uuid::Uuid as uuid::Uuid, PgParam::UUID);
+#[cfg(feature = "uuid")]
+pg_eq_impl!(Option<uuid::Uuid> as uuid::Uuid, PgParam::UUID);
+
+// bytea
+pg_eq_impl!(Vec<u8> as &[u8], PgParam::BYTEA);
+pg_eq_impl!(Option<Vec<u8>> as &[u8], PgParam::BYTEA);
+
+// macaddr
+#[cfg(feature = "mac_address")]
+pg_eq_impl!(
+    mac_address::MacAddress as mac_address::MacAddress,
+    PgParam::MACADDR
+);
+#[cfg(feature = "mac_address")]
+pg_eq_impl!(Option<mac_address::MacAddress> as mac_address::MacAddress, PgParam::MACADDR);
+
 // timestamp
 pg_eq_impl!(NaiveDateTime as NaiveDateTime, PgParam::TIMESTAMP);
 pg_eq_impl!(Option<NaiveDateTime> as NaiveDateTime, PgParam::TIMESTAMP);
@@ -314,40 +540,108 @@ pg_eq_impl!(Option<NaiveDate> as NaiveDate, PgParam::DATE);
 pg_eq_impl!(NaiveTime as NaiveTime, PgParam::TIME);
 pg_eq_impl!(Option<NaiveTime> as NaiveTime, PgParam::TIME);
 
+// custom types compare the way their postgres type does, enums in declaration order
+macro_rules! pg_custom_eq_impl {
+    ($field_type:ty) => {
+        impl<T: PgCustomType, M: ModelSidecar> PgEq<T> for Field<$field_type, M> {
+            fn eq(&self, other: T) -> EqExpression {
+                EqExpression::new(
+                    Condition::Basic(format!("{}=?", self.full_name)),
+                    vec![PgParam::from(other)],
+                )
+            }
+
+            fn neq(&self, other: T) -> EqExpression {
+                EqExpression::new(
+                    Condition::Basic(format!("{}!=?", self.full_name)),
+                    vec![PgParam::from(other)],
+                )
+            }
+
+            fn lt(&self, other: T) -> EqExpression {
+                EqExpression::new(
+                    Condition::Basic(format!("{}<?", self.full_name)),
+                    vec![PgParam::from(other)],
+                )
+            }
+
+            fn lte(&self, other: T) -> EqExpression {
+                EqExpression::new(
+                    Condition::Basic(format!("{}<=?", self.full_name)),
+                    vec![PgParam::from(other)],
+                )
+            }
+
+            fn gt(&self, other: T) -> EqExpression {
+                EqExpression::new(
+                    Condition::Basic(format!("{}>?", self.full_name)),
+                    vec![PgParam::from(other)],
+                )
+            }
+
+            fn gte(&self, other: T) -> EqExpression {
+                EqExpression::new(
+                    Condition::Basic(format!("{}>=?", self.full_name)),
+                    vec![PgParam::from(other)],
+                )
+            }
+
+            fn one_of(&self, other: &[T]) -> EqExpression {
+                if other.is_empty() {
+                    return EqExpression::new(Condition::Basic("FALSE".to_string()), vec![]);
+                }
+
+                EqExpression::new(
+                    Condition::Basic(format!(
+                        "{} IN ({})",
+                        self.full_name,
+                        vec!["?"; other.len()].join(", ")
+                    )),
+                    other.iter().cloned().map(PgParam::from).collect(),
+                )
+            }
+        }
+    };
+}
+
+pg_custom_eq_impl!(T);
+pg_custom_eq_impl!(Option<T>);
+
+// $now is NOW() as the type of the field, timestamptz is compared as is so the time zone is kept
 macro_rules! pg_timed_now_impl {
-    ($field_type:ty, $time_cast:literal) => {
+    ($field_type:ty, $now:literal) => {
         impl<M: ModelSidecar> PgEqTime for Field<$field_type, M> {
             fn is_now(&self) -> EqExpression {
                 EqExpression::new(
-                    Condition::Basic(format!(concat!("{}=NOW()::", $time_cast), self.full_name)),
+                    Condition::Basic(format!(concat!("{}=", $now), self.full_name)),
                     vec![],
                 )
             }
 
             fn is_before_now(&self) -> EqExpression {
                 EqExpression::new(
-                    Condition::Basic(format!(concat!("{}<NOW()::", $time_cast), self.full_name)),
+                    Condition::Basic(format!(concat!("{}<", $now), self.full_name)),
                     vec![],
                 )
             }
 
             fn is_now_or_before(&self) -> EqExpression {
                 EqExpression::new(
-                    Condition::Basic(format!(concat!("{}<=NOW()::", $time_cast), self.full_name)),
+                    Condition::Basic(format!(concat!("{}<=", $now), self.full_name)),
                     vec![],
                 )
             }
 
             fn is_after_now(&self) -> EqExpression {
                 EqExpression::new(
-                    Condition::Basic(format!(concat!("{}>NOW()::", $time_cast), self.full_name)),
+                    Condition::Basic(format!(concat!("{}>", $now), self.full_name)),
                     vec![],
                 )
             }
 
             fn is_now_or_after(&self) -> EqExpression {
                 EqExpression::new(
-                    Condition::Basic(format!(concat!("{}>=NOW()::", $time_cast), self.full_name)),
+                    Condition::Basic(format!(concat!("{}>=", $now), self.full_name)),
                     vec![],
                 )
             }
@@ -356,21 +650,330 @@ macro_rules! pg_timed_now_impl {
 }
 
 // timestamp
-pg_timed_now_impl!(NaiveDateTime, "timestamp");
-pg_timed_now_impl!(Option<NaiveDateTime>, "timestamp");
+pg_timed_now_impl!(NaiveDateTime, "NOW()::timestamp");
+pg_timed_now_impl!(Option<NaiveDateTime>, "NOW()::timestamp");
 
 // timestamp with timezone
-pg_timed_now_impl!(DateTime<Utc>, "timestamp");
-pg_timed_now_impl!(DateTime<Local>, "timestamp");
-pg_timed_now_impl!(DateTime<FixedOffset>, "timestamp");
-pg_timed_now_impl!(Option<DateTime<Utc>>, "timestamp");
-pg_timed_now_impl!(Option<DateTime<Local>>, "timestamp");
-pg_timed_now_impl!(Option<DateTime<FixedOffset>>, "timestamp");
+pg_timed_now_impl!(DateTime<Utc>, "NOW()");
+pg_timed_now_impl!(DateTime<Local>, "NOW()");
+pg_timed_now_impl!(DateTime<FixedOffset>, "NOW()");
+pg_timed_now_impl!(Option<DateTime<Utc>>, "NOW()");
+pg_timed_now_impl!(Option<DateTime<Local>>, "NOW()");
+pg_timed_now_impl!(Option<DateTime<FixedOffset>>, "NOW()");
 
 // date
-pg_timed_now_impl!(NaiveDate, "date");
-pg_timed_now_impl!(Option<NaiveDate>, "date");
+pg_timed_now_impl!(NaiveDate, "NOW()::date");
+pg_timed_now_impl!(Option<NaiveDate>, "NOW()::date");
 
 // time
-pg_timed_now_impl!(NaiveTime, "time");
-pg_timed_now_impl!(Option<NaiveTime>, "time");
+pg_timed_now_impl!(NaiveTime, "NOW()::time");
+pg_timed_now_impl!(Option<NaiveTime>, "NOW()::time");
+
+// NOW() moved by an interval, evaluated by the database
+//  created_at.is_before(now() - TimeDelta::days(7))
+#[derive(Debug, Clone, Copy)]
+pub struct RelativeTime {
+    offset: TimeDelta,
+}
+
+pub fn now() -> RelativeTime {
+    RelativeTime {
+        offset: TimeDelta::zero(),
+    }
+}
+
+impl Add<TimeDelta> for RelativeTime {
+    type Output = RelativeTime;
+
+    fn add(self, delta: TimeDelta) -> Self::Output {
+        RelativeTime {
+            offset: self.offset + delta,
+        }
+    }
+}
+
+impl Sub<TimeDelta> for RelativeTime {
+    type Output = RelativeTime;
+
+    fn sub(self, delta: TimeDelta) -> Self::Output {
+        RelativeTime {
+            offset: self.offset - delta,
+        }
+    }
+}
+
+macro_rules! pg_relative_time_impl {
+    ($field_type:ty, $now:literal) => {
+        impl<M: ModelSidecar> PgEqRelativeTime for Field<$field_type, M> {
+            fn within_last(&self, delta: TimeDelta) -> EqExpression {
+                EqExpression::new(
+                    Condition::Basic(format!(
+                        concat!("{} BETWEEN ", $now, "-? AND ", $now),
+                        self.full_name
+                    )),
+                    vec![PgParam::INTERVAL(Some(delta))],
+                )
+            }
+
+            fn older_than(&self, delta: TimeDelta) -> EqExpression {
+                EqExpression::new(
+                    Condition::Basic(format!(concat!("{}<", $now, "-?"), self.full_name)),
+                    vec![PgParam::INTERVAL(Some(delta))],
+                )
+            }
+
+            fn is_before(&self, time: RelativeTime) -> EqExpression {
+                EqExpression::new(
+                    Condition::Basic(format!(concat!("{}<", $now, "+?"), self.full_name)),
+                    vec![PgParam::INTERVAL(Some(time.offset))],
+                )
+            }
+
+            fn is_after(&self, time: RelativeTime) -> EqExpression {
+                EqExpression::new(
+                    Condition::Basic(format!(concat!("{}>", $now, "+?"), self.full_name)),
+                    vec![PgParam::INTERVAL(Some(time.offset))],
+                )
+            }
+        }
+    };
+}
+
+// not for time, an interval would wrap around midnight
+pg_relative_time_impl!(NaiveDateTime, "NOW()::timestamp");
+pg_relative_time_impl!(Option<NaiveDateTime>, "NOW()::timestamp");
+pg_relative_time_impl!(DateTime<Utc>, "NOW()");
+pg_relative_time_impl!(DateTime<Local>, "NOW()");
+pg_relative_time_impl!(DateTime<FixedOffset>, "NOW()");
+pg_relative_time_impl!(Option<DateTime<Utc>>, "NOW()");
+pg_relative_time_impl!(Option<DateTime<Local>>, "NOW()");
+pg_relative_time_impl!(Option<DateTime<FixedOffset>>, "NOW()");
+pg_relative_time_impl!(NaiveDate, "NOW()::date");
+pg_relative_time_impl!(Option<NaiveDate>, "NOW()::date");
+
+#[cfg(feature = "ipnetwork")]
+macro_rules! pg_network_impl {
+    ($field_type:ty) => {
+        impl<M: ModelSidecar> PgEqNetwork for Field<$field_type, M> {
+            fn within<N: Into<ipnetwork::IpNetwork>>(&self, network: N) -> EqExpression {
+                EqExpression::new(
+                    Condition::Basic(format!("{}<<=?", self.full_name)),
+                    vec![PgParam::INET(Some(network.into()))],
+                )
+            }
+
+            fn contains<N: Into<ipnetwork::IpNetwork>>(&self, network: N) -> EqExpression {
+                EqExpression::new(
+                    Condition::Basic(format!("{}>>=?", self.full_name)),
+                    vec![PgParam::INET(Some(network.into()))],
+                )
+            }
+        }
+    };
+}
+
+#[cfg(feature = "ipnetwork")]
+pg_network_impl!(crate::types::Inet);
+#[cfg(feature = "ipnetwork")]
+pg_network_impl!(Option<crate::types::Inet>);
+#[cfg(feature = "ipnetwork")]
+pg_network_impl!(crate::types::Cidr);
+#[cfg(feature = "ipnetwork")]
+pg_network_impl!(Option<crate::types::Cidr>);
+
+#[cfg(test)]
+mod test {
+    use crate::eq::PgEq;
+    use crate::fixtures::*;
+    use crate::internals::{AsPgType, PgParam};
+    use crate::ModelOps;
+
+    #[test]
+    pub fn test_relation_eq() {
+        use crate::eq::PgRelationEq;
+
+        let person = person::Model {
+            id: 4,
+            ..Default::default()
+        };
+
+        let rendered = document::Model::query()
+            .filter(|| {
+                document::created_by.eq_key(3)
+                    | document::updated_by.eq_model(&person)
+                    | document::created_by.one_of_keys(&[5, 6])
+                    | document::created_by.one_of_keys(&[])
+            })
+            .to_sql()
+            .unwrap();
+
+        assert_eq!(
+            rendered.sql,
+            "SELECT documents.id AS documents_id, documents.title AS documents_title, \
+             documents.created_by AS documents_created_by, documents.updated_by AS documents_updated_by \
+             FROM documents WHERE (((documents.created_by=$1) OR (documents.updated_by=$2)) \
+             OR (documents.created_by=ANY($3))) OR (FALSE);"
+        );
+        assert_eq!(rendered.params.len(), 3);
+
+        let order = order::Model {
+            tenant_id: 1,
+            order_no: 2,
+            ..Default::default()
+        };
+
+        let rendered = order_line::Model::query()
+            .filter(|| {
+                order_line::order.eq_model(&order)
+                    | order_line::order.one_of_keys(&[(1, 2), (3, 4)])
+            })
+            .to_sql()
+            .unwrap();
+
+        assert!(rendered.sql.ends_with(
+            "WHERE ((order_lines.tenant_id, order_lines.order_no)=($1, $2)) \
+             OR ((order_lines.tenant_id, order_lines.order_no) IN (($3, $4), ($5, $6)));"
+        ));
+    }
+
+    #[test]
+    pub fn test_null_checks() {
+        use crate::eq::PgEqNone;
+
+        // orders has a tenant_id too, a bare column would be ambiguous once it's joined
+        let rendered = refund::Model::query()
+            .include(refund::order)
+            .filter(|| refund::tenant_id.is_null() | refund::order_no.is_not_null())
+            .to_sql()
+            .unwrap();
+
+        assert!(rendered
+            .sql
+            .ends_with("WHERE (refunds.tenant_id IS NULL) OR (refunds.order_no IS NOT NULL);"));
+        assert!(rendered.sql.contains(" JOIN orders "));
+    }
+
+    #[test]
+    pub fn test_optional_composite_relation() {
+        use crate::eq::{PgEqNone, PgRelationEq};
+
+        let rendered = refund::Model::query()
+            .filter(|| {
+                (refund::order.is_null() | refund::order.eq_key((1, 2)))
+                    & refund::order.is_not_null()
+                    & refund::order.one_of_keys(&[(3, 4)])
+            })
+            .to_sql()
+            .unwrap();
+
+        assert!(rendered.sql.ends_with(
+            "WHERE ((((refunds.tenant_id IS NULL OR refunds.order_no IS NULL)) \
+             OR ((refunds.tenant_id, refunds.order_no)=($1, $2))) \
+             AND ((refunds.tenant_id IS NOT NULL AND refunds.order_no IS NOT NULL))) \
+             AND ((refunds.tenant_id, refunds.order_no) IN (($3, $4)));"
+        ));
+        assert_eq!(rendered.params.len(), 4);
+    }
+
+    #[test]
+    pub fn test_bytea() {
+        use crate::eq::PgEqNone;
+        use crate::internals::PgType;
+
+        assert_eq!(<Vec<u8> as AsPgType>::PG_TYPE, PgType::BYTEA);
+        assert_eq!(PgType::BYTEA.as_sql_type(false), "BYTEA");
+
+        let hash = vec![0xde, 0xad, 0xbe, 0xef];
+        let rendered = upload::Model::query()
+            .filter(|| upload::sha256.eq(hash.as_slice()) & upload::signature.is_not_null())
+            .to_sql()
+            .unwrap();
+        assert!(rendered
+            .sql
+            .ends_with("WHERE (uploads.sha256=$1) AND (uploads.signature IS NOT NULL);"));
+        assert!(matches!(&rendered.params[..], [PgParam::BYTEA(Some(it))] if *it == hash));
+    }
+
+    #[test]
+    pub fn test_relative_time() {
+        use crate::eq::{now, PgEqRelativeTime, PgEqTime};
+        use crate::internals::PgType;
+        use crate::types::TimeDelta;
+
+        assert_eq!(<TimeDelta as AsPgType>::PG_TYPE, PgType::INTERVAL);
+
+        let rendered = job::Model::query()
+            .filter(|| {
+                job::created_at.within_last(TimeDelta::days(7))
+                    & job::started_at.older_than(TimeDelta::hours(1))
+                    & job::run_on.is_before(now() - TimeDelta::days(2))
+                    & job::created_at.is_before_now()
+                    & job::timeout.gt(TimeDelta::minutes(5))
+            })
+            .to_sql()
+            .unwrap();
+
+        // timestamptz is compared to NOW() as is
+        assert!(rendered.sql.ends_with(
+            "WHERE ((((jobs.created_at BETWEEN NOW()-$1 AND NOW()) \
+             AND (jobs.started_at<NOW()::timestamp-$2)) \
+             AND (jobs.run_on<NOW()::date+$3)) \
+             AND (jobs.created_at<NOW())) \
+             AND (jobs.timeout>$4);"
+        ));
+        assert!(matches!(
+            &rendered.params[..],
+            [
+                PgParam::INTERVAL(Some(week)),
+                PgParam::INTERVAL(Some(_)),
+                PgParam::INTERVAL(Some(two_days_ago)),
+                PgParam::INTERVAL(Some(_)),
+            ] if *week == TimeDelta::days(7) && *two_days_ago == TimeDelta::days(-2)
+        ));
+    }
+
+    #[cfg(feature = "ipnetwork")]
+    #[test]
+    pub fn test_network_types() {
+        use crate::eq::PgEqNetwork;
+        use crate::internals::PgType;
+        use crate::types::{Cidr, Inet, IpAddr, IpNetwork};
+
+        assert_eq!(<Inet as AsPgType>::PG_TYPE, PgType::INET);
+        assert_eq!(<Option<Inet> as AsPgType>::PG_TYPE, PgType::INET);
+        assert_eq!(<Cidr as AsPgType>::PG_TYPE, PgType::CIDR);
+        assert_eq!(Inet::default().to_string(), "0.0.0.0/32");
+
+        let subnet: IpNetwork = "10.0.0.0/8".parse().unwrap();
+        let client = IpAddr::from([10, 1, 2, 3]);
+        let rendered = visit::Model::query()
+            .filter(|| visit::client.within(subnet) & visit::network.contains(client))
+            .to_sql()
+            .unwrap();
+        assert!(rendered
+            .sql
+            .ends_with("WHERE (visits.client<<=$1) AND (visits.network>>=$2);"));
+        assert!(matches!(
+            &rendered.params[..],
+            [PgParam::INET(Some(first)), PgParam::INET(Some(second))]
+                if *first == subnet && second.ip() == client && second.prefix() == 32
+        ));
+
+        // host bits are kept, an INET takes them
+        let host: Inet = "10.1.2.3/8".parse().unwrap();
+        let rendered = visit::Model::query()
+            .filter(|| visit::network.eq(host) | visit::client.eq(Inet::from(client)))
+            .to_sql()
+            .unwrap();
+        assert!(matches!(
+            &rendered.params[..],
+            [PgParam::INET(Some(it)), PgParam::INET(Some(_))] if it.ip() == client && it.prefix() == 8
+        ));
+
+        let rendered = visit::Model::query()
+            .filter(|| visit::allowed.contains(client))
+            .to_sql()
+            .unwrap();
+        assert!(rendered.sql.ends_with("WHERE visits.allowed>>=$1;"));
+    }
+}
@@ -14,6 +14,9 @@ pub enum GasError {
     #[error("invalid query format")]
     QueryFormatError,
 
+    #[error("query has {placeholders} placeholders, but got {params} parameters")]
+    ParameterCountMismatch { placeholders: usize, params: usize },
+
     #[error("unexpected response: {0}")]
     UnexpectedResponse(Cow<'static, str>),
 
@@ -0,0 +1,76 @@
+use crate::connection::PgExecutor;
+use crate::internals::{SqlQuery, SqlStatement};
+use crate::GasResult;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExplainFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ExplainOptions {
+    // careful, this actually runs the query
+    pub analyze: bool,
+    pub buffers: bool,
+    pub format: ExplainFormat,
+}
+
+#[derive(Debug, Clone)]
+pub enum ExplainPlan {
+    Text(String),
+    Json(serde_json::Value),
+}
+
+impl ExplainOptions {
+    fn as_sql(&self) -> String {
+        let mut options = vec![];
+
+        if self.analyze {
+            options.push("ANALYZE");
+        }
+
+        if self.buffers {
+            options.push("BUFFERS");
+        }
+
+        options.push(match self.format {
+            ExplainFormat::Text => "FORMAT TEXT",
+            ExplainFormat::Json => "FORMAT JSON",
+        });
+
+        format!("EXPLAIN ({}) ", options.join(", "))
+    }
+}
+
+pub(crate) async fn explain<E: PgExecutor>(
+    ctx: E,
+    (statement, params): SqlStatement<'_>,
+    options: &ExplainOptions,
+) -> GasResult<ExplainPlan> {
+    let mut sql = SqlQuery::from(options.as_sql());
+    sql.append_query(&statement);
+
+    let rows = ctx.execute(sql, &params).await?;
+
+    match options.format {
+        ExplainFormat::Text => Ok(ExplainPlan::Text(
+            rows.iter()
+                .map(|row| row.try_get::<String>("QUERY PLAN"))
+                .collect::<GasResult<Vec<_>>>()?
+                .join("\n"),
+        )),
+        ExplainFormat::Json => {
+            let mut plans = rows
+                .iter()
+                .map(|row| row.try_get::<serde_json::Value>("QUERY PLAN"))
+                .collect::<GasResult<Vec<_>>>()?;
+
+            // postgres always returns a single row for json
+            Ok(ExplainPlan::Json(
+                plans.pop().unwrap_or(serde_json::Value::Null),
+            ))
+        }
+    }
+}
@@ -16,12 +16,16 @@ pub struct Field<T: AsPgType, M: ModelSidecar> {
 
 pub enum VirtualFieldType {
     InverseRelation,
+    ManyToMany,
+    RelationCount,
 }
 
-pub struct VirtualField<M: ModelSidecar> {
+// T is the type of the relation field, used by any/none
+pub struct VirtualField<M: ModelSidecar, T> {
     pub field_type: VirtualFieldType,
     pub meta: FieldMeta,
     _model_marker: PhantomData<M>,
+    _marker: PhantomData<T>,
 }
 
 impl<T: AsPgType, M: ModelSidecar> Field<T, M> {
@@ -35,14 +39,16 @@ impl<T: AsPgType, M: ModelSidecar> Field<T, M> {
 
     pub fn asc(&self) -> SortDefinition {
         SortDefinition::from(SortOp {
-            field_full_name: self.full_name,
+            field_full_name: self.full_name.into(),
+            params: vec![],
             direction: SortDirection::Ascending,
         })
     }
 
     pub fn desc(&self) -> SortDefinition {
         SortDefinition::from(SortOp {
-            field_full_name: self.full_name,
+            field_full_name: self.full_name.into(),
+            params: vec![],
             direction: SortDirection::Descending,
         })
     }
@@ -56,17 +62,18 @@ impl<T: AsPgType, M: ModelSidecar> Deref for Field<T, M> {
     }
 }
 
-impl<M: ModelSidecar> VirtualField<M> {
+impl<M: ModelSidecar, T> VirtualField<M, T> {
     pub const fn new(field_type: VirtualFieldType, meta: FieldMeta) -> Self {
         Self {
             field_type,
             meta,
             _model_marker: PhantomData,
+            _marker: PhantomData,
         }
     }
 }
 
-impl<M: ModelSidecar> Deref for VirtualField<M> {
+impl<M: ModelSidecar, T> Deref for VirtualField<M, T> {
     type Target = FieldMeta;
 
     fn deref(&self) -> &Self::Target {
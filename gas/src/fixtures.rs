@@ -0,0 +1,225 @@
+// models shared by the tests all over the crate, never compiled outside of them
+use crate::types::TsVector;
+use crate::Relation;
+
+// see migrations/mod.rs
+mod gas {
+    pub use crate::*;
+}
+
+#[gas_macros::model(table_name = "people", exclude_link_meta = true)]
+pub(crate) struct Person {
+    #[primary_key]
+    #[serial]
+    pub(crate) id: i64,
+    pub(crate) name: String,
+    pub(crate) age: i32,
+    #[relation(through = friendship::Model, from = friendship::person, to = friendship::friend, lazy)]
+    pub(crate) friends: Vec<person::Model>,
+}
+
+// joins people to people
+#[gas_macros::model(table_name = "friendships", exclude_link_meta = true)]
+pub(crate) struct Friendship {
+    #[primary_key]
+    #[relation(field = person::id)]
+    pub(crate) person: Relation<i64, person::Model>,
+    #[primary_key]
+    #[relation(field = person::id)]
+    pub(crate) friend: Relation<i64, person::Model>,
+}
+
+#[gas_macros::model(table_name = "posts", exclude_link_meta = true)]
+pub(crate) struct Post {
+    #[primary_key]
+    #[serial]
+    pub(crate) id: i64,
+    pub(crate) title: String,
+    pub(crate) body: String,
+    #[tsvector(language = "english", from(title, body))]
+    #[index(using = "gin")]
+    pub(crate) search: TsVector,
+    #[relation(through = post_tag::Model, from = post_tag::post, to = post_tag::tag, lazy)]
+    pub(crate) tags: Vec<tag::Model>,
+}
+
+#[gas_macros::model(table_name = "tags", exclude_link_meta = true)]
+pub(crate) struct Tag {
+    #[primary_key]
+    #[serial]
+    pub(crate) id: i64,
+    pub(crate) name: String,
+    #[relation(count_of = post_tag::tag)]
+    pub(crate) post_count: i64,
+}
+
+#[gas_macros::model(table_name = "post_tags", exclude_link_meta = true)]
+pub(crate) struct PostTag {
+    #[primary_key]
+    #[relation(field = post::id, on_delete = "cascade")]
+    pub(crate) post: Relation<i64, post::Model>,
+    #[primary_key]
+    #[relation(field = tag::id)]
+    pub(crate) tag: Relation<i64, tag::Model>,
+}
+
+#[gas_macros::model(table_name = "documents", exclude_link_meta = true)]
+pub(crate) struct Document {
+    #[primary_key]
+    #[serial]
+    pub(crate) id: i64,
+    pub(crate) title: String,
+    #[relation(field = person::id)]
+    pub(crate) created_by: Relation<i64, person::Model>,
+    #[relation(field = person::id)]
+    pub(crate) updated_by: Relation<i64, person::Model>,
+    #[relation(inverse = comment::document, lazy, sort = comment::id.desc(), limit = 5)]
+    pub(crate) comments: Vec<comment::Model>,
+}
+
+#[gas_macros::model(table_name = "comments", exclude_link_meta = true)]
+pub(crate) struct Comment {
+    #[primary_key]
+    #[serial]
+    pub(crate) id: i64,
+    #[relation(field = document::id, on_delete = "cascade", on_update = "restrict")]
+    pub(crate) document: Relation<i64, document::Model>,
+}
+
+#[gas_macros::model(table_name = "orders", exclude_link_meta = true)]
+pub(crate) struct Order {
+    #[primary_key]
+    pub(crate) tenant_id: i64,
+    #[primary_key]
+    pub(crate) order_no: i64,
+    pub(crate) note: String,
+    pub(crate) status: OrderStatus,
+}
+
+#[derive(gas_macros::Type, Clone, Copy, Debug, Default, PartialEq)]
+#[gas(transparent)]
+pub(crate) struct AccountId(pub(crate) i64);
+
+#[derive(gas_macros::Type, Clone, Debug, Default, PartialEq)]
+#[gas(transparent)]
+pub(crate) struct Email {
+    pub(crate) address: String,
+}
+
+#[gas_macros::model(table_name = "accounts", exclude_link_meta = true)]
+pub(crate) struct Account {
+    #[primary_key]
+    pub(crate) id: AccountId,
+    pub(crate) email: Email,
+    pub(crate) backup_email: Option<Email>,
+}
+
+#[gas_macros::model(table_name = "sessions", exclude_link_meta = true)]
+pub(crate) struct Session {
+    #[primary_key]
+    #[serial]
+    pub(crate) id: i64,
+    #[relation(field = account::id)]
+    pub(crate) account: Relation<AccountId, account::Model>,
+}
+
+#[cfg(feature = "uuid")]
+#[gas_macros::model(table_name = "api_keys", exclude_link_meta = true)]
+pub(crate) struct ApiKey {
+    #[primary_key]
+    #[default(sql = "gen_random_uuid()")]
+    pub(crate) id: crate::types::Uuid,
+    pub(crate) label: String,
+    #[default(fn = "default".to_string(), sql = "'default'")]
+    pub(crate) scope: String,
+}
+
+#[gas_macros::model(table_name = "uploads", exclude_link_meta = true)]
+pub(crate) struct Upload {
+    #[primary_key]
+    #[serial]
+    pub(crate) id: i64,
+    pub(crate) sha256: Vec<u8>,
+    pub(crate) signature: Option<Vec<u8>>,
+}
+
+#[gas_macros::model(table_name = "products", exclude_link_meta = true)]
+pub(crate) struct Product {
+    #[primary_key]
+    #[serial]
+    pub(crate) id: i64,
+    pub(crate) sizes: crate::types::Json<std::collections::BTreeMap<String, i64>>,
+    pub(crate) extra: Option<serde_json::Value>,
+}
+
+#[gas_macros::model(table_name = "jobs", exclude_link_meta = true)]
+pub(crate) struct Job {
+    #[primary_key]
+    #[serial]
+    pub(crate) id: i64,
+    pub(crate) created_at: crate::types::DateTime<crate::types::Utc>,
+    pub(crate) started_at: Option<crate::types::NaiveDateTime>,
+    pub(crate) run_on: crate::types::NaiveDate,
+    pub(crate) timeout: crate::types::TimeDelta,
+}
+
+#[gas_macros::model(table_name = "ledger_entries", exclude_link_meta = true)]
+pub(crate) struct LedgerEntry {
+    #[primary_key]
+    #[serial]
+    pub(crate) id: i64,
+    #[column(max_length = 32)]
+    pub(crate) code: String,
+    #[column(name = "total", precision = 18, scale = 4)]
+    pub(crate) amount: crate::types::Decimal,
+    #[column(precision = 3)]
+    pub(crate) booked_at: Option<crate::types::DateTime<crate::types::Utc>>,
+}
+
+#[cfg(feature = "ipnetwork")]
+#[gas_macros::model(table_name = "visits", exclude_link_meta = true)]
+pub(crate) struct Visit {
+    #[primary_key]
+    #[serial]
+    pub(crate) id: i64,
+    pub(crate) client: crate::types::Inet,
+    pub(crate) network: Option<crate::types::Inet>,
+    pub(crate) allowed: crate::types::Cidr,
+}
+
+#[derive(gas_macros::PgEnum, Clone, Copy, Debug, Default, PartialEq)]
+#[pg_enum(name = "order_status")]
+pub(crate) enum OrderStatus {
+    #[default]
+    Pending,
+    #[pg_enum(rename = "sent")]
+    Shipped,
+    Delivered,
+}
+
+#[gas_macros::model(table_name = "order_lines", exclude_link_meta = true)]
+pub(crate) struct OrderLine {
+    #[primary_key]
+    #[serial]
+    pub(crate) id: i64,
+    pub(crate) tenant_id: i64,
+    pub(crate) order_no: i64,
+    #[relation(
+        fields = (tenant_id, order_no),
+        references = (order::tenant_id, order::order_no),
+        on_delete = "cascade"
+    )]
+    pub(crate) order: Relation<(i64, i64), order::Model>,
+}
+
+// not every refund is for an order, the composite relation is optional
+#[gas_macros::model(table_name = "refunds", exclude_link_meta = true)]
+pub(crate) struct Refund {
+    #[primary_key]
+    #[serial]
+    pub(crate) id: i64,
+    pub(crate) tenant_id: Option<i64>,
+    pub(crate) order_no: Option<i64>,
+    #[relation(fields = (tenant_id, order_no), references = (order::tenant_id, order::order_no))]
+    pub(crate) order: Option<Relation<(i64, i64), order::Model>>,
+}
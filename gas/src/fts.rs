@@ -0,0 +1,310 @@
+use crate::condition::{Condition, EqExpression};
+use crate::connection::PgExecutor;
+use crate::error::GasError;
+use crate::internals::{AsPgType, PgParam, RenderedQuery};
+use crate::model::ModelMeta;
+use crate::ops::select::{Projection, SelectBuilder};
+use crate::row::{FromRow, FromRowNamed, RelationCtx, RelationFuture, ResponseCtx, Row};
+use crate::sort::{SortDefinition, SortDirection, SortOp};
+use crate::types::TsVector;
+use crate::{Field, GasResult, ModelSidecar};
+use std::ops::{Deref, DerefMut};
+
+// a text search query, language is passed as a parameter and cast to regconfig
+//  so it's validated by postgres and never ends up in the sql itself
+#[derive(Debug, Clone)]
+pub struct TsQuery {
+    sql: &'static str,
+    language: String,
+    query: String,
+}
+
+impl TsQuery {
+    fn params(&self) -> Vec<PgParam> {
+        vec![
+            PgParam::TEXT(Some(self.language.clone())),
+            PgParam::TEXT(Some(self.query.clone())),
+        ]
+    }
+}
+
+// the one you usually want for user input, never fails on bad syntax
+pub fn websearch_to_tsquery(language: &str, query: &str) -> TsQuery {
+    TsQuery {
+        sql: "websearch_to_tsquery(?::regconfig, ?)",
+        language: language.to_string(),
+        query: query.to_string(),
+    }
+}
+
+pub fn plainto_tsquery(language: &str, query: &str) -> TsQuery {
+    TsQuery {
+        sql: "plainto_tsquery(?::regconfig, ?)",
+        language: language.to_string(),
+        query: query.to_string(),
+    }
+}
+
+pub fn phraseto_tsquery(language: &str, query: &str) -> TsQuery {
+    TsQuery {
+        sql: "phraseto_tsquery(?::regconfig, ?)",
+        language: language.to_string(),
+        query: query.to_string(),
+    }
+}
+
+// raw tsquery syntax (e.g. `fat & (rat | cat)`), errors on invalid input
+pub fn to_tsquery(language: &str, query: &str) -> TsQuery {
+    TsQuery {
+        sql: "to_tsquery(?::regconfig, ?)",
+        language: language.to_string(),
+        query: query.to_string(),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TsRank {
+    sql: String,
+    params: Vec<PgParam>,
+}
+
+impl TsRank {
+    fn sort(self, direction: SortDirection) -> SortDefinition {
+        SortDefinition::from(SortOp {
+            field_full_name: self.sql.into(),
+            params: self.params,
+            direction,
+        })
+    }
+
+    pub fn asc(self) -> SortDefinition {
+        self.sort(SortDirection::Ascending)
+    }
+
+    pub fn desc(self) -> SortDefinition {
+        self.sort(SortDirection::Descending)
+    }
+}
+
+pub trait PgTextSearch {
+    fn matches(&self, query: &TsQuery) -> EqExpression;
+
+    fn rank(&self, query: &TsQuery) -> TsRank;
+
+    // cover density ranking, takes proximity of the matching lexemes into account
+    fn rank_cd(&self, query: &TsQuery) -> TsRank;
+}
+
+macro_rules! pg_text_search_impl {
+    ($field_type:ty) => {
+        impl<M: ModelSidecar> PgTextSearch for Field<$field_type, M> {
+            fn matches(&self, query: &TsQuery) -> EqExpression {
+                EqExpression::new(
+                    Condition::Basic(format!("{} @@ {}", self.full_name, query.sql)),
+                    query.params(),
+                )
+            }
+
+            fn rank(&self, query: &TsQuery) -> TsRank {
+                TsRank {
+                    sql: format!("ts_rank({}, {})", self.full_name, query.sql),
+                    params: query.params(),
+                }
+            }
+
+            fn rank_cd(&self, query: &TsQuery) -> TsRank {
+                TsRank {
+                    sql: format!("ts_rank_cd({}, {})", self.full_name, query.sql),
+                    params: query.params(),
+                }
+            }
+        }
+    };
+}
+
+pg_text_search_impl!(TsVector);
+pg_text_search_impl!(Option<TsVector>);
+
+// fields ts_headline can be run on
+pub(crate) trait Headlineable {}
+
+impl Headlineable for String {}
+impl Headlineable for Option<String> {}
+
+const HEADLINE_ALIAS: &str = "headline";
+
+const HEADLINE_COMPOUND_ERR: GasError =
+    GasError::InvalidInput("headlines are not supported on compound (UNION, ...) selects");
+
+#[derive(Clone)]
+pub struct HeadlineSelect<M: ModelMeta> {
+    select: SelectBuilder<M>,
+    field_full_name: &'static str,
+    query: TsQuery,
+    options: Option<String>,
+}
+
+impl<M: ModelMeta> HeadlineSelect<M> {
+    pub(crate) fn new<Ty: AsPgType + Headlineable, FM: ModelSidecar>(
+        select: SelectBuilder<M>,
+        field: Field<Ty, FM>,
+        query: &TsQuery,
+    ) -> Self {
+        Self {
+            select,
+            field_full_name: field.full_name,
+            query: query.clone(),
+            options: None,
+        }
+    }
+
+    // passed as is to ts_headline, e.g. "MaxWords=20, MinWords=5, StartSel=<b>, StopSel=</b>"
+    pub fn options(mut self, options: impl Into<String>) -> Self {
+        self.options = Some(options.into());
+        self
+    }
+
+    fn into_select(self) -> GasResult<SelectBuilder<M>> {
+        self.select
+            .ensure_not_compound()
+            .or(Err(HEADLINE_COMPOUND_ERR))?;
+
+        let mut params = vec![PgParam::TEXT(Some(self.query.language.clone()))];
+        params.append(&mut self.query.params());
+
+        let options_sql = match self.options {
+            Some(options) => {
+                params.push(PgParam::TEXT(Some(options)));
+                ", ?"
+            }
+            None => "",
+        };
+
+        let mut select = self.select;
+        select.projections.push(Projection {
+            sql: format!(
+                "COALESCE(ts_headline(?::regconfig, {}, {}{}), '')",
+                self.field_full_name, self.query.sql, options_sql
+            ),
+            alias: HEADLINE_ALIAS.to_string(),
+            params,
+        });
+
+        Ok(select)
+    }
+
+    pub async fn find_all<E: PgExecutor>(self, ctx: E) -> GasResult<Vec<Headlined<M>>> {
+        let select = self.into_select()?;
+        let includes = select.response_includes();
+        let (sql, params) = select.build(true);
+
+        ctx.execute_parsed_including::<Headlined<M>>(sql, &params, includes)
+            .await
+    }
+
+    pub async fn find_one<E: PgExecutor>(self, ctx: E) -> GasResult<Option<Headlined<M>>> {
+        let select = self.into_select()?;
+        let includes = select.response_includes();
+        let (mut sql, params) = select.build(false);
+
+        sql.append_str(" LIMIT 1");
+
+        let mut items = ctx
+            .execute_parsed_including::<Headlined<M>>(sql, &params, includes)
+            .await?;
+
+        if items.len() > 1 {
+            return Err(GasError::UnexpectedResponse(
+                format!("find_one: got {}, expected <= 1", items.len()).into(),
+            ));
+        }
+
+        Ok(items.pop())
+    }
+
+    pub fn to_sql(&self) -> GasResult<RenderedQuery> {
+        RenderedQuery::render(self.clone().into_select()?.build(true))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Headlined<M> {
+    pub model: M,
+    pub headline: String,
+}
+
+impl<M: FromRow> FromRow for Headlined<M> {
+    fn from_row(ctx: &ResponseCtx, row: &Row) -> GasResult<Self> {
+        Ok(Self {
+            model: M::from_row(ctx, row)?,
+            headline: String::from_row_named(ctx, row, HEADLINE_ALIAS)?,
+        })
+    }
+
+    fn load_relations<'a>(items: Vec<&'a mut Self>, ctx: &'a RelationCtx) -> RelationFuture<'a> {
+        M::load_relations(items.into_iter().map(|it| &mut it.model).collect(), ctx)
+    }
+}
+
+impl<M> Deref for Headlined<M> {
+    type Target = M;
+
+    fn deref(&self) -> &Self::Target {
+        &self.model
+    }
+}
+
+impl<M> DerefMut for Headlined<M> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.model
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::fixtures::*;
+    use crate::fts::{websearch_to_tsquery, PgTextSearch};
+    use crate::internals::PgParam;
+    use crate::{ModelMeta, ModelOps};
+
+    #[test]
+    pub fn test_full_text_search() {
+        assert_eq!(
+            post::search.generated_sql,
+            Some(
+                "to_tsvector('english'::regconfig, coalesce(title::text, '') || ' ' || coalesce(body::text, ''))"
+            )
+        );
+
+        // generated by the database, never written
+        let post = post::Model {
+            title: "title".to_string(),
+            body: "body".to_string(),
+            ..Default::default()
+        };
+        let (_, params) = post.gen_insert_values_sql();
+        assert!(matches!(
+            &params[..],
+            [PgParam::TEXT(Some(title)), PgParam::TEXT(Some(body))]
+                if title == "title" && body == "body"
+        ));
+
+        let query = websearch_to_tsquery("english", "rust orm");
+        let rendered = post::Model::query()
+            .filter(|| post::search.matches(&query))
+            .sort(post::search.rank(&query).desc())
+            .headline(post::body, &query)
+            .to_sql()
+            .unwrap();
+
+        assert_eq!(
+            rendered.sql,
+            "SELECT posts.id AS posts_id, posts.title AS posts_title, posts.body AS posts_body, \
+             posts.search AS posts_search, \
+             COALESCE(ts_headline($1::regconfig, posts.body, websearch_to_tsquery($2::regconfig, $3)), '') AS headline \
+             FROM posts WHERE posts.search @@ websearch_to_tsquery($4::regconfig, $5) \
+             ORDER BY ts_rank(posts.search, websearch_to_tsquery($6::regconfig, $7)) DESC;"
+        );
+        assert_eq!(rendered.params.len(), 7);
+    }
+}
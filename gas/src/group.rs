@@ -1,5 +1,5 @@
 use crate::connection::PgExecutor;
-use crate::internals::{AsPgType, Numeric, SqlQuery, SqlStatement};
+use crate::internals::{AsPgType, Numeric, RenderedQuery, SqlQuery, SqlStatement};
 use crate::ops::select::SelectBuilder;
 use crate::row::{FromRow, FromRowNamed, ResponseCtx, Row};
 use crate::sort::{SortDefinition, SortDirection, SortOp};
@@ -21,14 +21,16 @@ impl GroupSorting {
 
     pub fn asc(&self) -> SortDefinition {
         SortDefinition::from(SortOp {
-            field_full_name: self.sql_select_name(),
+            field_full_name: self.sql_select_name().into(),
+            params: vec![],
             direction: SortDirection::Ascending,
         })
     }
 
     pub fn desc(&self) -> SortDefinition {
         SortDefinition::from(SortOp {
-            field_full_name: self.sql_select_name(),
+            field_full_name: self.sql_select_name().into(),
+            params: vec![],
             direction: SortDirection::Descending,
         })
     }
@@ -67,6 +69,8 @@ impl<M: ModelMeta, G: AsPgType + 'static> Group<M, G> {
         ctx: E,
         field: Field<N, M::Id>,
     ) -> GasResult<Vec<Summed<G, N::SumType>>> {
+        self.select.ensure_not_compound()?;
+
         let aggregate_call = format!("SUM({})", field.full_name);
         let (sql, params) = self.build_aggregate_query(&aggregate_call);
 
@@ -78,13 +82,29 @@ impl<M: ModelMeta, G: AsPgType + 'static> Group<M, G> {
         ctx: E,
         field: Field<T, M::Id>,
     ) -> GasResult<Vec<Counted<G>>> {
+        self.select.ensure_not_compound()?;
+
         let aggregate_call = format!("COUNT({})", field.full_name);
         let (sql, params) = self.build_aggregate_query(&aggregate_call);
 
         ctx.execute_parsed(sql, &params).await
     }
 
-    pub fn build_aggregate_query(self, aggregate_call: &str) -> SqlStatement<'_> {
+    pub fn to_sum_sql<N: Numeric>(&self, field: Field<N, M::Id>) -> GasResult<RenderedQuery> {
+        self.select.ensure_not_compound()?;
+        let aggregate_call = format!("SUM({})", field.full_name);
+
+        RenderedQuery::render(self.build_aggregate_query(&aggregate_call))
+    }
+
+    pub fn to_count_sql<T: AsPgType>(&self, field: Field<T, M::Id>) -> GasResult<RenderedQuery> {
+        self.select.ensure_not_compound()?;
+        let aggregate_call = format!("COUNT({})", field.full_name);
+
+        RenderedQuery::render(self.build_aggregate_query(&aggregate_call))
+    }
+
+    pub fn build_aggregate_query(&self, aggregate_call: &str) -> SqlStatement<'_> {
         // sql
         let mut sql = SqlQuery::from(format!(
             "SELECT {} as key, {} as aggregate FROM {}",
@@ -115,8 +135,11 @@ impl<M: ModelMeta, G: AsPgType + 'static> Group<M, G> {
         let params = self
             .select
             .filter
-            .map(|it| it.params.into_boxed_slice())
-            .unwrap_or_else(|| Box::new([]));
+            .iter()
+            .flat_map(|it| it.params.iter())
+            .chain(self.sort.iter().flat_map(|it| it.params()))
+            .cloned()
+            .collect();
 
         (sql, params)
     }
@@ -5,7 +5,11 @@ pub mod pg_type;
 pub mod serial_compatible;
 pub mod sql_query;
 
-use crate::FieldMeta;
+use crate::condition::{Condition, EqExpression};
+use crate::relation::aggregate::relation_count_columns;
+use crate::relation::include::ForwardRelationType;
+use crate::types::TsVector;
+use crate::{Field, FieldFlag, FieldMeta, ModelMeta, ModelSidecar};
 pub use def_model::*;
 pub use gas_shared::internals::pg_type::*;
 pub(crate) use numeric::*;
@@ -18,11 +22,23 @@ use std::any::TypeId;
 pub fn generate_update_set_fields(fields: &[&FieldMeta]) -> String {
     fields
         .iter()
+        .filter(|field| field.generated_sql.is_none())
         .map(|field| format!("{}=?", field.name))
         .reduce(|acc, curr| format!("{}, {}", acc, curr))
         .unwrap_or_else(String::new)
 }
 
+// works for every key type, including relations (which compare by their foreign key)
+pub fn key_condition<T: AsPgType, M: ModelSidecar>(field: &Field<T, M>, key: T) -> EqExpression
+where
+    PgParam: From<T>,
+{
+    EqExpression::new(
+        Condition::Basic(format!("{}=?", field.full_name)),
+        vec![PgParam::from(key)],
+    )
+}
+
 pub fn type_id_of_value<T: 'static>(_: &T) -> TypeId {
     TypeId::of::<T>()
 }
@@ -34,3 +50,52 @@ impl<T> SameType<T> for T {}
 pub const fn assert_type<A>(_: &impl SameType<A>) {}
 
 pub const fn assert_types_param<A, B: SameType<A>>() {}
+
+// composite relations have to point at the whole primary key of the other model
+pub const fn is_full_primary_key(columns: &[&FieldMeta], fields: &[FieldMeta]) -> bool {
+    let mut primary_keys = 0;
+    let mut index = 0;
+    while index < fields.len() {
+        if fields[index].flags.has_flag(FieldFlag::PrimaryKey) {
+            primary_keys += 1;
+        }
+        index += 1;
+    }
+
+    let mut index = 0;
+    while index < columns.len() {
+        if !columns[index].flags.has_flag(FieldFlag::PrimaryKey) {
+            return false;
+        }
+        index += 1;
+    }
+
+    primary_keys == columns.len()
+}
+
+// the relation has to point at the given model (its sidecar),
+//  the model a count_of is on or the two ends of a many to many join table
+pub const fn assert_relation_target<Ty, S, Target>(_: &Field<Ty, S>)
+where
+    Ty: AsPgType + ForwardRelationType,
+    S: ModelSidecar,
+    Target: SameType<<Ty::Model as ModelMeta>::Id>,
+{
+}
+
+// RETURNING doesn't go through a select, so models with relation counts append them to it
+pub fn relation_counts_returning(fields: &[FieldMeta], table_name: &str) -> String {
+    relation_count_columns(fields, table_name, "")
+        .into_iter()
+        .map(|column| format!(", {}", column))
+        .collect()
+}
+
+// #[tsvector] columns
+pub trait TsVectorCompatible {}
+
+impl TsVectorCompatible for TsVector {}
+
+impl TsVectorCompatible for Option<TsVector> {}
+
+pub const fn assert_tsvector<T: TsVectorCompatible>() {}
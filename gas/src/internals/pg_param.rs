@@ -1,6 +1,13 @@
-use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use crate::error::GasError;
+use crate::types::{PgEnumParam, TsVector};
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Utc};
 use rust_decimal::Decimal;
-use std::fmt::{Display, Formatter};
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo};
+use sqlx::{Encode, Postgres, Type};
+use std::fmt::{Debug, Display, Formatter};
+use std::sync::Arc;
 
 #[derive(Debug, Clone)]
 pub enum PgParam {
@@ -22,7 +29,19 @@ pub enum PgParam {
     TIMESTAMP_TZ_FIXED_OFFSET(Option<DateTime<FixedOffset>>),
     DATE(Option<NaiveDate>),
     TIME(Option<NaiveTime>),
+    INTERVAL(Option<TimeDelta>),
     JSONB(Option<sqlx::types::Json<serde_json::Value>>),
+    TSVECTOR(Option<TsVector>),
+    #[cfg(feature = "uuid")]
+    UUID(Option<uuid::Uuid>),
+    BYTEA(Option<Vec<u8>>),
+    #[cfg(feature = "ipnetwork")]
+    INET(Option<ipnetwork::IpNetwork>),
+    #[cfg(feature = "mac_address")]
+    MACADDR(Option<mac_address::MacAddress>),
+    ENUM(PgEnumParam),
+    CUSTOM(CustomParam),
+    ARRAY(PgArrayParam),
     RAW(Option<&'static str>),
     IGNORED,
 }
@@ -45,7 +64,19 @@ macro_rules! pg_param_all {
             PgParam::TIMESTAMP_TZ_FIXED_OFFSET(value) => $ex("TIMESTAMP_TZ_FIXED_OFFSET", value),
             PgParam::DATE(value) => $ex("DATE", value),
             PgParam::TIME(value) => $ex("TIME", value),
+            PgParam::INTERVAL(value) => $ex("INTERVAL", value),
             PgParam::JSONB(value) => $ex("JSONB", value),
+            PgParam::TSVECTOR(value) => $ex("TSVECTOR", value),
+            #[cfg(feature = "uuid")]
+            PgParam::UUID(value) => $ex("UUID", value),
+            PgParam::BYTEA(value) => $ex("BYTEA", value),
+            #[cfg(feature = "ipnetwork")]
+            PgParam::INET(value) => $ex("INET", value),
+            #[cfg(feature = "mac_address")]
+            PgParam::MACADDR(value) => $ex("MACADDR", value),
+            PgParam::ENUM(value) => $ex("ENUM", value),
+            PgParam::CUSTOM(value) => $ex("CUSTOM", value),
+            PgParam::ARRAY(value) => $ex("ARRAY", value),
             PgParam::RAW(value) => $ex("RAW", value),
             PgParam::IGNORED => $ex("IGNORED", Option::<i8>::None),
         }
@@ -61,3 +92,155 @@ impl Display for PgParam {
         })
     }
 }
+
+impl PgParam {
+    // anything sqlx can encode, see PgCustomType
+    pub fn custom<T>(value: Option<T>) -> Self
+    where
+        T: for<'a> Encode<'a, Postgres> + Type<Postgres> + Debug + Send + Sync + 'static,
+    {
+        PgParam::CUSTOM(CustomParam(Arc::new(value)))
+    }
+}
+
+trait ErasedParam: Debug + Send + Sync {
+    fn encode(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError>;
+
+    fn produces(&self) -> PgTypeInfo;
+}
+
+impl<T> ErasedParam for Option<T>
+where
+    T: for<'a> Encode<'a, Postgres> + Type<Postgres> + Debug + Send + Sync + 'static,
+{
+    fn encode(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        self.encode_by_ref(buf)
+    }
+
+    fn produces(&self) -> PgTypeInfo {
+        Encode::<Postgres>::produces(self).unwrap_or_else(T::type_info)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CustomParam(Arc<dyn ErasedParam>);
+
+impl Type<Postgres> for CustomParam {
+    // the actual type is reported by `produces`
+    fn type_info() -> PgTypeInfo {
+        <&str as Type<Postgres>>::type_info()
+    }
+}
+
+impl Encode<'_, Postgres> for CustomParam {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        self.0.encode(buf)
+    }
+
+    fn produces(&self) -> Option<PgTypeInfo> {
+        Some(self.0.produces())
+    }
+}
+
+// a postgres array built out of scalar params, e.g. for `column = ANY(?)`
+macro_rules! pg_array_param_impl {
+    ($($(#[$meta:meta])* $variant:ident($inner:ty)),* $(,)?) => {
+        #[derive(Debug, Clone)]
+        #[allow(nonstandard_style)]
+        pub enum PgArrayParam {
+            $($(#[$meta])* $variant(Vec<Option<$inner>>),)*
+        }
+
+        impl TryFrom<Vec<PgParam>> for PgArrayParam {
+            type Error = GasError;
+
+            // all params must be of the same variant, an empty array is TEXT[]
+            fn try_from(params: Vec<PgParam>) -> Result<Self, Self::Error> {
+                let Some(first) = params.first() else {
+                    return Ok(PgArrayParam::TEXT(vec![]));
+                };
+
+                match first {
+                    $($(#[$meta])* PgParam::$variant(_) => params
+                        .into_iter()
+                        .map(|param| match param {
+                            PgParam::$variant(value) => Ok(value),
+                            other => Err(GasError::TypeError(other)),
+                        })
+                        .collect::<Result<Vec<_>, _>>()
+                        .map(PgArrayParam::$variant),)*
+                    other => Err(GasError::TypeError(other.clone())),
+                }
+            }
+        }
+
+        impl PgArrayParam {
+            // the params are handed back if they can't be an array (enums, custom types, ...)
+            pub(crate) fn try_collect(params: Vec<PgParam>) -> Result<Self, Vec<PgParam>> {
+                match params.first() {
+                    None => Ok(PgArrayParam::TEXT(vec![])),
+                    $($(#[$meta])* Some(PgParam::$variant(_))
+                        if params.iter().all(|it| matches!(it, PgParam::$variant(_))) =>
+                    {
+                        Ok(PgArrayParam::$variant(
+                            params
+                                .into_iter()
+                                .filter_map(|param| match param {
+                                    PgParam::$variant(value) => Some(value),
+                                    _ => None,
+                                })
+                                .collect(),
+                        ))
+                    })*
+                    _ => Err(params),
+                }
+            }
+        }
+
+        impl Type<Postgres> for PgArrayParam {
+            // the actual type is reported by `produces`
+            fn type_info() -> PgTypeInfo {
+                <Vec<Option<String>> as Type<Postgres>>::type_info()
+            }
+        }
+
+        impl Encode<'_, Postgres> for PgArrayParam {
+            fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+                match self {
+                    $($(#[$meta])* PgArrayParam::$variant(values) => values.encode_by_ref(buf),)*
+                }
+            }
+
+            fn produces(&self) -> Option<PgTypeInfo> {
+                match self {
+                    $($(#[$meta])* PgArrayParam::$variant(_) => Some(<Vec<Option<$inner>> as Type<Postgres>>::type_info()),)*
+                }
+            }
+        }
+    };
+}
+
+pg_array_param_impl!(
+    TEXT(String),
+    BOOLEAN(bool),
+    SMALLINT(i16),
+    INTEGER(i32),
+    BIGINT(i64),
+    REAL(f32),
+    DOUBLE(f64),
+    DECIMAL(Decimal),
+    TIMESTAMP(NaiveDateTime),
+    TIMESTAMP_TZ_UTC(DateTime<Utc>),
+    TIMESTAMP_TZ_LOCAL(DateTime<Local>),
+    TIMESTAMP_TZ_FIXED_OFFSET(DateTime<FixedOffset>),
+    DATE(NaiveDate),
+    TIME(NaiveTime),
+    INTERVAL(TimeDelta),
+    #[cfg(feature = "uuid")]
+    UUID(uuid::Uuid),
+    BYTEA(Vec<u8>),
+    #[cfg(feature = "ipnetwork")]
+    INET(ipnetwork::IpNetwork),
+    #[cfg(feature = "mac_address")]
+    MACADDR(mac_address::MacAddress),
+);
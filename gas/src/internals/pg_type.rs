@@ -1,6 +1,7 @@
 use crate::internals::PgParam;
-use crate::row::FromRowNamed;
-use crate::types::Decimal;
+use crate::row::{decode_naive, FromRowNamed, ResponseCtx, Row};
+use crate::types::{Decimal, PgCustomType, TsVector};
+use crate::GasResult;
 use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use gas_shared::internals::pg_type::PgType;
 use sqlx::{Decode, Postgres, Type};
@@ -32,6 +33,18 @@ macro_rules! pg_type_impl {
         impl NaiveDecodable for $field_type {}
         impl NaiveDecodable for Option<$field_type> {}
 
+        impl FromRowNamed for $field_type {
+            fn from_row_named(ctx: &ResponseCtx, row: &Row, name: &str) -> GasResult<Self> {
+                decode_naive(ctx, row, name)
+            }
+        }
+
+        impl FromRowNamed for Option<$field_type> {
+            fn from_row_named(ctx: &ResponseCtx, row: &Row, name: &str) -> GasResult<Self> {
+                decode_naive(ctx, row, name)
+            }
+        }
+
         // default to 0, blanked implemented to 1 for all Option<T: AsPgType>
         impl IsOptional for $field_type {
             const FACTOR: u8 = 0;
@@ -73,3 +86,38 @@ pg_type_impl!(serde_json::Value as PgType::JSONB, |value: Option<
 >| {
     PgParam::JSONB(value.map(sqlx::types::Json))
 });
+
+pg_type_impl!(TsVector as PgType::TSVECTOR, PgParam::TSVECTOR);
+
+#[cfg(feature = "uuid")]
+pg_type_impl!(uuid::Uuid as PgType::UUID, PgParam::UUID);
+
+pg_type_impl!(Vec<u8> as PgType::BYTEA, PgParam::BYTEA);
+
+#[cfg(feature = "mac_address")]
+pg_type_impl!(mac_address::MacAddress as PgType::MACADDR, PgParam::MACADDR);
+
+// see PgCustomType, nullable or not
+impl<T: PgCustomType> AsPgType for T {
+    const PG_TYPE: PgType = <T as PgCustomType>::PG_TYPE;
+}
+
+impl<T: PgCustomType> AsPgType for Option<T> {
+    const PG_TYPE: PgType = <T as PgCustomType>::PG_TYPE;
+}
+
+impl<T: PgCustomType> IsOptional for T {
+    const FACTOR: u8 = 0;
+}
+
+impl<T: PgCustomType> From<T> for PgParam {
+    fn from(value: T) -> Self {
+        T::to_param(Some(value))
+    }
+}
+
+impl<T: PgCustomType> From<Option<T>> for PgParam {
+    fn from(value: Option<T>) -> Self {
+        T::to_param(value)
+    }
+}
@@ -16,6 +16,22 @@ pub struct SqlQuery<'a> {
 
 pub type SqlStatement<'a> = (SqlQuery<'a>, Box<[PgParam]>);
 
+// what actually gets sent to the database, mostly useful for debugging and logging
+#[derive(Debug, Clone)]
+pub struct RenderedQuery {
+    pub sql: String,
+    pub params: Box<[PgParam]>,
+}
+
+impl RenderedQuery {
+    pub(crate) fn render((sql, params): SqlStatement) -> GasResult<Self> {
+        Ok(Self {
+            sql: sql.finish(&params)?,
+            params,
+        })
+    }
+}
+
 impl<'a> SqlQuery<'a> {
     pub fn new() -> Self {
         SqlQuery {
@@ -31,7 +47,25 @@ impl<'a> SqlQuery<'a> {
         self.query.to_mut().push_str(other);
     }
 
-    pub(crate) fn finish(self) -> GasResult<String> {
+    pub(crate) fn as_str(&self) -> &str {
+        &self.query
+    }
+
+    // also makes sure every placeholder has a parameter (and vice versa)
+    pub(crate) fn finish(self, params: &[PgParam]) -> GasResult<String> {
+        let (out, placeholders) = self.finish_counted()?;
+
+        if placeholders != params.len() {
+            return Err(GasError::ParameterCountMismatch {
+                placeholders,
+                params: params.len(),
+            });
+        }
+
+        Ok(out)
+    }
+
+    fn finish_counted(self) -> GasResult<(String, usize)> {
         let mut updated = 0;
         let out = self.query.split("?").fold(String::new(), |acc, curr| {
             // special case for the first entry, which will always be the empty string
@@ -49,10 +83,10 @@ impl<'a> SqlQuery<'a> {
         }
 
         if !out.ends_with(';') {
-            return Ok(out + ";");
+            return Ok((out + ";", updated));
         }
 
-        Ok(out)
+        Ok((out, updated))
     }
 }
 
@@ -75,19 +109,34 @@ impl<'a> From<&'a str> for SqlQuery<'a> {
 #[cfg(test)]
 mod test {
     use crate::error::GasError;
-    use crate::internals::SqlQuery;
+    use crate::internals::{PgParam, SqlQuery};
 
     #[test]
     pub fn test_parameterize() {
-        let query = SqlQuery::from("WHERE id=? AND name IN (?, ?, ?)").finish();
+        let params = [1, 2, 3, 4].map(PgParam::from);
+        let query = SqlQuery::from("WHERE id=? AND name IN (?, ?, ?)").finish(&params);
 
-        assert!(matches!(query, Ok(out) if out == "WHERE id=$1 AND name IN ($2, $3, $4)"))
+        assert!(matches!(query, Ok(out) if out == "WHERE id=$1 AND name IN ($2, $3, $4);"))
     }
 
     #[test]
     pub fn test_fail() {
-        let query = SqlQuery::from("WHERE id IN (?, $2, ?)").finish();
+        let query =
+            SqlQuery::from("WHERE id IN (?, $2, ?)").finish(&[PgParam::from(1), PgParam::from(2)]);
 
         assert!(matches!(query, Err(GasError::QueryFormatError)));
     }
+
+    #[test]
+    pub fn test_param_count() {
+        let query = SqlQuery::from("WHERE id=? AND name=?").finish(&[PgParam::from(1)]);
+
+        assert!(matches!(
+            query,
+            Err(GasError::ParameterCountMismatch {
+                placeholders: 2,
+                params: 1
+            })
+        ));
+    }
 }
@@ -0,0 +1,245 @@
+use crate::condition::{Condition, EqExpression};
+use crate::eq::{PgEq, PgEqNone};
+use crate::internals::{AsPgType, PgArrayParam, PgParam};
+use crate::sort::{SortDefinition, SortDirection, SortOp};
+use crate::types::{json_param, Json};
+use crate::{Field, ModelSidecar};
+use serde::Serialize;
+use std::marker::PhantomData;
+
+// columns holding json, typed or not
+pub(crate) trait JsonColumn {}
+
+impl JsonColumn for serde_json::Value {}
+impl JsonColumn for Option<serde_json::Value> {}
+impl<T> JsonColumn for Json<T> {}
+impl<T> JsonColumn for Option<Json<T>> {}
+
+pub trait PgJson {
+    // a value inside the document, keys and array indexes from the outside in
+    //  product::attributes.path(["size", "width"]).cast::<f64>().gt(10.0)
+    fn path<I, S>(&self, path: I) -> JsonPath
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString;
+
+    // column @> value, the document contains the given (partial) document
+    fn contains<V: Serialize>(&self, value: &V) -> EqExpression;
+}
+
+impl<C: JsonColumn + AsPgType, M: ModelSidecar> PgJson for Field<C, M> {
+    fn path<I, S>(&self, path: I) -> JsonPath
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        JsonPath {
+            column: self.full_name,
+            path: path.into_iter().map(|it| it.to_string()).collect(),
+        }
+    }
+
+    fn contains<V: Serialize>(&self, value: &V) -> EqExpression {
+        EqExpression::new(
+            Condition::Basic(format!("{} @> ?", self.full_name)),
+            vec![json_param(value)],
+        )
+    }
+}
+
+// the path is sent as a text[] parameter, never ends up in the sql itself
+#[derive(Debug, Clone)]
+pub struct JsonPath {
+    column: &'static str,
+    path: Vec<String>,
+}
+
+impl JsonPath {
+    pub fn at(mut self, key: impl ToString) -> Self {
+        self.path.push(key.to_string());
+        self
+    }
+
+    pub fn text(self) -> JsonValue<String> {
+        self.cast()
+    }
+
+    // the value as text, cast to the column type of V (NULL when the path doesn't exist)
+    pub fn cast<V: AsPgType>(self) -> JsonValue<V> {
+        JsonValue {
+            sql: format!("({} #>> ?)::{}", self.column, V::PG_TYPE.as_sql_type(false)),
+            path: PgParam::ARRAY(PgArrayParam::TEXT(
+                self.path.into_iter().map(Some).collect(),
+            )),
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct JsonValue<V> {
+    sql: String,
+    path: PgParam,
+    _marker: PhantomData<V>,
+}
+
+impl<V> JsonValue<V> {
+    fn compare(&self, operator: &str, value: PgParam) -> EqExpression {
+        EqExpression::new(
+            Condition::Basic(format!("{}{}?", self.sql, operator)),
+            vec![self.path.clone(), value],
+        )
+    }
+
+    fn sort(self, direction: SortDirection) -> SortDefinition {
+        SortDefinition::from(SortOp {
+            field_full_name: self.sql.into(),
+            params: vec![self.path],
+            direction,
+        })
+    }
+
+    pub fn asc(self) -> SortDefinition {
+        self.sort(SortDirection::Ascending)
+    }
+
+    pub fn desc(self) -> SortDefinition {
+        self.sort(SortDirection::Descending)
+    }
+}
+
+impl<V: AsPgType, C: Into<V> + Clone> PgEq<C> for JsonValue<V>
+where
+    PgParam: From<V>,
+{
+    fn eq(&self, other: C) -> EqExpression {
+        self.compare("=", PgParam::from(other.into()))
+    }
+
+    fn neq(&self, other: C) -> EqExpression {
+        self.compare("!=", PgParam::from(other.into()))
+    }
+
+    fn lt(&self, other: C) -> EqExpression {
+        self.compare("<", PgParam::from(other.into()))
+    }
+
+    fn lte(&self, other: C) -> EqExpression {
+        self.compare("<=", PgParam::from(other.into()))
+    }
+
+    fn gt(&self, other: C) -> EqExpression {
+        self.compare(">", PgParam::from(other.into()))
+    }
+
+    fn gte(&self, other: C) -> EqExpression {
+        self.compare(">=", PgParam::from(other.into()))
+    }
+
+    fn one_of(&self, other: &[C]) -> EqExpression {
+        if other.is_empty() {
+            return EqExpression::new(Condition::Basic("FALSE".to_string()), vec![]);
+        }
+
+        let mut params = vec![self.path.clone()];
+        params.extend(other.iter().cloned().map(|it| PgParam::from(it.into())));
+
+        EqExpression::new(
+            Condition::Basic(format!(
+                "{} IN ({})",
+                self.sql,
+                vec!["?"; other.len()].join(", ")
+            )),
+            params,
+        )
+    }
+}
+
+// a missing path is NULL as well
+impl<V> PgEqNone for JsonValue<V> {
+    fn is_null(&self) -> EqExpression {
+        EqExpression::new(
+            Condition::Basic(format!("{} IS NULL", self.sql)),
+            vec![self.path.clone()],
+        )
+    }
+
+    fn is_not_null(&self) -> EqExpression {
+        EqExpression::new(
+            Condition::Basic(format!("{} IS NOT NULL", self.sql)),
+            vec![self.path.clone()],
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::eq::PgEq;
+    use crate::fixtures::*;
+    use crate::internals::{AsPgType, PgParam};
+    use crate::ModelOps;
+
+    #[test]
+    pub fn test_json() {
+        use crate::eq::PgEqNone;
+        use crate::internals::{PgArrayParam, PgType};
+        use crate::json::PgJson;
+        use crate::types::Json;
+        use std::collections::BTreeMap;
+
+        assert_eq!(<Json<Vec<i64>> as AsPgType>::PG_TYPE, PgType::JSONB);
+
+        let sizes = Json(BTreeMap::from([("width".to_string(), 10)]));
+        assert!(matches!(
+            PgParam::from(sizes.clone()),
+            PgParam::JSONB(Some(value)) if value.0 == serde_json::json!({ "width": 10 })
+        ));
+        // maps with non-string keys aren't json, the query fails once it runs
+        assert!(matches!(
+            PgParam::from(Json(BTreeMap::from([((1, 2), 3)]))),
+            PgParam::CUSTOM(_)
+        ));
+
+        let rendered = product::Model::query()
+            .filter(|| {
+                product::sizes.path(["width"]).cast::<i64>().gt(5)
+                    & product::extra
+                        .path(["tags"])
+                        .at(0)
+                        .text()
+                        .one_of(&["new", "sale"])
+                    & product::extra
+                        .path(["discontinued"])
+                        .cast::<bool>()
+                        .is_null()
+                    & product::sizes.contains(&sizes)
+            })
+            .sort(product::extra.path(["rank"]).cast::<f64>().desc())
+            .to_sql()
+            .unwrap();
+
+        assert!(rendered.sql.ends_with(
+            "WHERE ((((products.sizes #>> $1)::BIGINT>$2) \
+             AND ((products.extra #>> $3)::TEXT IN ($4, $5))) \
+             AND ((products.extra #>> $6)::BOOLEAN IS NULL)) \
+             AND (products.sizes @> $7) \
+             ORDER BY (products.extra #>> $8)::DOUBLE PRECISION DESC;"
+        ));
+        assert!(matches!(
+            &rendered.params[..],
+            [
+                PgParam::ARRAY(PgArrayParam::TEXT(width)),
+                PgParam::BIGINT(Some(5)),
+                PgParam::ARRAY(PgArrayParam::TEXT(tags)),
+                PgParam::TEXT(Some(first)),
+                PgParam::TEXT(Some(_)),
+                PgParam::ARRAY(PgArrayParam::TEXT(_)),
+                PgParam::JSONB(Some(_)),
+                PgParam::ARRAY(PgArrayParam::TEXT(rank)),
+            ] if width == &[Some("width".to_string())]
+                && tags == &[Some("tags".to_string()), Some("0".to_string())]
+                && first == "new"
+                && rank == &[Some("rank".to_string())]
+        ));
+    }
+}
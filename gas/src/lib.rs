@@ -5,24 +5,39 @@ pub mod condition;
 pub mod connection;
 pub mod eq;
 pub mod error;
+pub mod explain;
 pub mod extra;
 pub mod field;
+pub mod fts;
 pub mod group;
 pub mod helpers;
 pub mod internals;
+pub mod json;
 pub mod migrations;
 pub mod model;
 mod ops;
+pub mod raw;
 pub mod relation;
 pub mod row;
 pub mod sort;
 pub mod types;
 
+#[cfg(test)]
+mod fixtures;
+
 pub use field::*;
 pub use gas_macros::*;
 pub use gas_shared::*;
 pub use model::*;
+pub use raw::raw;
+pub use relation::aggregate::*;
+pub use relation::batch::*;
+pub use relation::composite::*;
+pub use relation::exists::*;
 pub use relation::forward::*;
+pub use relation::graph::*;
+pub use relation::include::*;
 pub use relation::inverse::*;
+pub use relation::many_to_many::*;
 
 pub type GasResult<T> = Result<T, error::GasError>;
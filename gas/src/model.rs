@@ -1,13 +1,14 @@
 use crate::condition::EqExpression;
 use crate::connection::PgExecutor;
 use crate::field::FieldMeta;
-use crate::internals::{AsPgType, SqlQuery, SqlStatement};
+use crate::internals::{AsPgType, PgParam, RenderedQuery, SqlQuery, SqlStatement};
 use crate::ops::create_table::CreateTableOp;
 use crate::ops::delete::DeleteOp;
 use crate::ops::insert::InsertOp;
 use crate::ops::select::SelectBuilder;
 use crate::ops::update::UpdateOp;
-use crate::row::FromRow;
+use crate::relation::graph::{self, insert_model};
+use crate::row::{FromRow, RelationCtx, RelationFuture};
 use crate::GasResult;
 
 pub trait ModelSidecar {}
@@ -38,6 +39,17 @@ pub trait ModelMeta: Sized + Default + Clone + FromRow {
     // will be implemented by a macro with some unsafe magic
     //  used with relations
     fn get_by_field<T: AsPgType + 'static>(&self, field: &FieldMeta) -> Option<T>;
+
+    // same as get_by_field, but without knowing the type, relations resolve to their key
+    fn get_param_by_field(&self, field: &FieldMeta) -> Option<PgParam>;
+
+    // virtual fields included, None if the type doesn't match
+    fn get_mut_by_field<T: 'static>(&mut self, field: &FieldMeta) -> Option<&mut T>;
+
+    // insert_with_relations, models with relations get their own from the macro
+    fn insert_graph<'a>(&'a mut self, ctx: &'a RelationCtx) -> RelationFuture<'a> {
+        Box::pin(insert_model(self, ctx))
+    }
 }
 
 pub trait ModelOps: ModelMeta {
@@ -56,6 +68,14 @@ pub trait ModelOps: ModelMeta {
         InsertOp::<Self>::new(std::slice::from_mut(self)).run(ctx)
     }
 
+    // also inserts the models loaded into its relations, see insert_graph
+    fn insert_with_relations<E: PgExecutor>(
+        &mut self,
+        ctx: E,
+    ) -> impl Future<Output = GasResult<()>> {
+        graph::insert_with_relations(self, ctx)
+    }
+
     fn inserted<E: PgExecutor>(&self, ctx: E) -> impl Future<Output = GasResult<Self>> {
         async move {
             let mut cloned = self.clone();
@@ -94,6 +114,14 @@ pub trait ModelOps: ModelMeta {
         }
     }
 
+    fn to_update_sql(&self) -> GasResult<RenderedQuery> {
+        RenderedQuery::render(self.gen_update_sql())
+    }
+
+    fn to_delete_sql(&self) -> GasResult<RenderedQuery> {
+        RenderedQuery::render(self.gen_delete_sql())
+    }
+
     fn delete<E: PgExecutor>(self, ctx: E) -> impl Future<Output = GasResult<()>> {
         DeleteOp::<Self>::new(self).run(ctx)
     }
@@ -115,3 +143,37 @@ pub trait ModelOps: ModelMeta {
 }
 
 impl<T: ModelMeta> ModelOps for T {}
+
+#[cfg(test)]
+mod test {
+    use crate::fixtures::*;
+    use crate::internals::AsPgType;
+    use crate::{CompositeField, Field, ModelMeta};
+
+    #[test]
+    pub fn test_get_mut_by_field() {
+        fn relation_of<M: ModelMeta, Ty: AsPgType + 'static>(
+            model: &mut M,
+            field: Field<Ty, M::Id>,
+        ) -> Option<&mut Ty> {
+            model.get_mut_by_field(&field)
+        }
+
+        let mut comment = comment::Model::default();
+        assert!(relation_of(&mut comment, comment::document).is_some());
+        assert!(comment.get_mut_by_field::<String>(&comment::id).is_none());
+
+        *comment.get_mut_by_field::<i64>(&comment::id).unwrap() = 5;
+        assert_eq!(comment.id, 5);
+
+        fn composite_of<M: ModelMeta, Ty: 'static>(
+            model: &mut M,
+            field: CompositeField<Ty, M::Id>,
+        ) -> Option<&mut Ty> {
+            model.get_mut_by_field(&field)
+        }
+
+        let mut line = order_line::Model::default();
+        assert!(composite_of(&mut line, order_line::order).is_some());
+    }
+}
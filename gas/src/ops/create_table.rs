@@ -20,6 +20,28 @@ impl<T: ModelMeta> CreateTableOp<T> {
 
     // could be at compile-time, but I don't care, it's create_table, who cares
     pub(crate) async fn run<E: PgExecutor>(self, ctx: E) -> GasResult<()> {
+        // enum types can be shared between tables, so one that already exists is fine either way
+        let mut enum_types: Vec<String> = Vec::new();
+        for field in T::FIELDS.iter() {
+            let Some(type_sql) = field.pg_type.as_create_type_sql() else {
+                continue;
+            };
+
+            if enum_types.contains(&type_sql) {
+                continue;
+            }
+
+            ctx.execute(
+                SqlQuery::from(format!(
+                    "DO $$ BEGIN {}; EXCEPTION WHEN duplicate_object THEN NULL; END $$",
+                    type_sql
+                )),
+                &[],
+            )
+            .await?;
+            enum_types.push(type_sql);
+        }
+
         let mut sql = SqlQuery::from("CREATE TABLE ");
 
         if self.ignore_existing {
@@ -36,12 +58,11 @@ impl<T: ModelMeta> CreateTableOp<T> {
                 primary_keys.push(field.name.to_string())
             }
 
-            let sql_type = field
-                .pg_type
-                .as_sql_type(field.flags.has_flag(FieldFlag::Serial));
+            let sql_type = field.column_sql_type();
             sql.append_str(field.name);
             sql.append_str(" ");
             sql.append_str(&sql_type);
+            sql.append_str(&field.foreign_key_actions.as_sql());
 
             if !field.flags.has_flag(FieldFlag::Nullable) {
                 sql.append_str(" NOT NULL");
@@ -51,6 +72,12 @@ impl<T: ModelMeta> CreateTableOp<T> {
                 sql.append_str(" UNIQUE");
             }
 
+            if let Some(generated_sql) = field.generated_sql {
+                sql.append_str(" GENERATED ALWAYS AS (");
+                sql.append_str(generated_sql);
+                sql.append_str(") STORED");
+            }
+
             if let Some(default_sql) = field.default_sql {
                 sql.append_str(" DEFAULT (");
                 sql.append_str(default_sql);
@@ -75,8 +102,113 @@ impl<T: ModelMeta> CreateTableOp<T> {
             sql.append_str(")");
         }
 
+        // composite foreign keys are table constraints, named like postgres would name a single one
+        for field in T::VIRTUAL_FIELDS.iter() {
+            let Some(constraint_sql) = field.pg_type.as_constraint_sql() else {
+                continue;
+            };
+
+            sql.append_str(&format!(
+                ", CONSTRAINT {}_{}_fkey {}{}",
+                T::TABLE_NAME,
+                field.name,
+                constraint_sql,
+                field.foreign_key_actions.as_sql()
+            ));
+        }
+
         sql.append_str(")");
 
-        ctx.execute(sql, &[]).await.map(|_| ())
+        ctx.execute(sql, &[]).await?;
+
+        // one statement per query, so indexes go separately
+        for sql in self.index_statements() {
+            ctx.execute(sql, &[]).await?;
+        }
+
+        Ok(())
+    }
+
+    fn index_statements(&self) -> Vec<SqlQuery<'static>> {
+        T::FIELDS
+            .iter()
+            .filter_map(|field| {
+                let index_type = field.index_type?;
+
+                let mut sql = SqlQuery::from("CREATE INDEX ");
+
+                if self.ignore_existing {
+                    sql.append_str("IF NOT EXISTS ");
+                }
+
+                sql.append_str(&format!(
+                    "{}_{}_idx ON {} USING {} ({})",
+                    T::TABLE_NAME,
+                    field.name,
+                    T::TABLE_NAME,
+                    index_type.as_sql(),
+                    field.name
+                ));
+
+                Some(sql)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::fixtures::*;
+    use crate::ops::create_table::CreateTableOp;
+
+    #[test]
+    pub fn test_indexes() {
+        let statements = CreateTableOp::<post::Model>::new(true).index_statements();
+        assert_eq!(statements.len(), 1);
+        assert_eq!(
+            statements[0].as_str(),
+            "CREATE INDEX IF NOT EXISTS posts_search_idx ON posts USING GIN (search)"
+        );
+
+        assert!(CreateTableOp::<comment::Model>::new(true)
+            .index_statements()
+            .is_empty());
+    }
+
+    #[test]
+    pub fn test_type_modifiers() {
+        use crate::internals::PgType;
+        use crate::TypeModifiers;
+
+        assert_eq!(ledger_entry::id.type_modifiers, TypeModifiers::NONE);
+        assert_eq!(ledger_entry::code.column_sql_type(), "VARCHAR(32)");
+        assert_eq!(ledger_entry::amount.name, "total");
+        assert_eq!(ledger_entry::amount.column_sql_type(), "NUMERIC(18, 4)");
+        assert_eq!(
+            ledger_entry::booked_at.column_sql_type(),
+            "TIMESTAMP(3) WITH TIME ZONE"
+        );
+
+        let length = TypeModifiers {
+            max_length: Some(64),
+            ..TypeModifiers::NONE
+        };
+        assert!(length.supports(&PgType::TEXT));
+        assert!(!length.supports(&PgType::DECIMAL));
+
+        let precision = TypeModifiers {
+            precision: Some(10),
+            ..TypeModifiers::NONE
+        };
+        assert_eq!(
+            PgType::DECIMAL.as_column_sql_type(false, &precision),
+            "NUMERIC(10)"
+        );
+        // fractional seconds stop at 6
+        assert!(!precision.supports(&PgType::TIMESTAMP));
+        assert_eq!(
+            PgType::TEXT.as_column_sql_type(false, &precision),
+            PgType::TEXT.as_sql_type(false)
+        );
     }
 }
@@ -63,3 +63,44 @@ impl<'a, T: ModelMeta> InsertOp<'a, T> {
         Ok(())
     }
 }
+
+#[cfg(all(test, feature = "uuid"))]
+mod test {
+    use crate::eq::PgEq;
+    use crate::fixtures::*;
+    use crate::internals::{AsPgType, PgParam, PgType};
+    use crate::types::Uuid;
+    use crate::{ModelMeta, ModelOps};
+
+    #[test]
+    pub fn test_uuid_database_default() {
+        assert_eq!(<Uuid as AsPgType>::PG_TYPE, PgType::UUID);
+        assert_eq!(api_key::id.default_sql, Some("gen_random_uuid()"));
+
+        // the database fills the key and it's read back, a rust side default is still sent
+        let key = api_key::Model {
+            label: "ci".to_string(),
+            ..Default::default()
+        };
+        let (_, params) = key.gen_insert_values_sql();
+        assert!(matches!(
+            &params[..],
+            [PgParam::TEXT(Some(label)), PgParam::TEXT(Some(scope))]
+                if label == "ci" && scope == "default"
+        ));
+
+        let (_, returning) = api_key::Model::gen_insert_parts_sql();
+        assert!(returning
+            .finish(&[])
+            .unwrap()
+            .contains("api_keys.id AS api_keys_id"));
+
+        let key = Uuid::from_u128(7);
+        let rendered = api_key::Model::query()
+            .filter(|| api_key::id.eq(key))
+            .to_sql()
+            .unwrap();
+        assert!(rendered.sql.ends_with("WHERE api_keys.id=$1;"));
+        assert!(matches!(&rendered.params[..], [PgParam::UUID(Some(it))] if *it == key));
+    }
+}
@@ -1,13 +1,19 @@
 #![allow(private_bounds)]
 
-use crate::condition::{Condition, EqExpression};
+use crate::condition::{raw_condition, EqExpression};
 use crate::connection::PgExecutor;
 use crate::error::GasError;
+use crate::explain::{self, ExplainOptions, ExplainPlan};
+use crate::fts::{HeadlineSelect, Headlineable, TsQuery};
 use crate::group::Group;
-use crate::internals::{AsPgType, NaiveDecodable, Numeric, PgParam, SqlQuery, SqlStatement};
+use crate::internals::{AsPgType, Numeric, PgParam, RenderedQuery, SqlQuery, SqlStatement};
 use crate::model::ModelMeta;
+use crate::relation::aggregate::{relation_count_columns, AggregatedSelect};
+use crate::relation::exists::RelationSubquery;
+use crate::relation::include::{IncludeHop, IncludeTarget};
+use crate::row::{JoinScope, ResponseIncludes};
 use crate::sort::SortDefinition;
-use crate::{Field, FieldMeta, FullRelation, GasResult, ModelSidecar};
+use crate::{Field, FieldMeta, GasResult, ModelSidecar, VirtualField};
 use std::marker::PhantomData;
 use std::num::NonZeroUsize;
 
@@ -16,17 +22,104 @@ pub struct SelectBuilder<T: ModelMeta> {
     pub(crate) filter: Option<EqExpression>,
     sort: Option<SortDefinition>,
     limit: Option<NonZeroUsize>,
-    includes: Vec<(String, &'static [FieldMeta])>,
+    // the limit applies to every group of rows with the same value of this field
+    partition: Option<&'static FieldMeta>,
+    includes: Vec<Join>,
+    // inverse relations to load after the query, full names of the virtual fields
+    pub(crate) relation_includes: Vec<&'static str>,
+    pub(crate) projections: Vec<Projection>,
+    compound: Option<Box<CompoundSelect<T>>>,
     _marker: PhantomData<T>,
 }
 
+// a LEFT JOIN of an included forward relation
+#[derive(Debug, Clone)]
+struct Join {
+    hop: IncludeHop,
+    parent: Option<usize>,
+    // the table name itself the first time a table is joined, table__n after that
+    table_alias: String,
+    column_prefix: Option<String>,
+    sql: String,
+}
+
+impl Join {
+    fn scope(&self) -> JoinScope {
+        JoinScope {
+            parent: self.parent,
+            fk_alias: self.hop.fk_alias,
+            column_prefix: self.column_prefix.clone(),
+        }
+    }
+
+    fn select_fields(&self) -> impl Iterator<Item = String> + '_ {
+        let column_prefix = self.column_prefix.as_deref().unwrap_or("");
+
+        self.hop
+            .target_fields
+            .iter()
+            .map(move |f| {
+                format!(
+                    "{}.{} AS {}{}",
+                    self.table_alias, f.name, column_prefix, f.alias_name
+                )
+            })
+            .chain(relation_count_columns(
+                self.hop.target_virtual_fields,
+                &self.table_alias,
+                column_prefix,
+            ))
+    }
+}
+
+// an extra column selected next to the model fields, e.g. ts_headline(...) AS headline
+#[derive(Debug, Clone)]
+pub(crate) struct Projection {
+    pub(crate) sql: String,
+    pub(crate) alias: String,
+    pub(crate) params: Vec<PgParam>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum SetOperator {
+    Union,
+    UnionAll,
+    Intersect,
+    Except,
+}
+
+impl SetOperator {
+    fn as_sql(&self) -> &'static str {
+        match self {
+            SetOperator::Union => "UNION",
+            SetOperator::UnionAll => "UNION ALL",
+            SetOperator::Intersect => "INTERSECT",
+            SetOperator::Except => "EXCEPT",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CompoundSelect<T: ModelMeta> {
+    lhs: SelectBuilder<T>,
+    operator: SetOperator,
+    rhs: SelectBuilder<T>,
+}
+
+const COMPOUND_AGGREGATE_ERR: GasError =
+    GasError::InvalidInput("aggregates are not supported on compound (UNION, ...) selects");
+
 impl<M: ModelMeta> SelectBuilder<M> {
     pub fn new() -> Self {
         Self {
             filter: None,
             sort: None,
             limit: None,
+            partition: None,
             includes: Vec::new(),
+            relation_includes: Vec::new(),
+            projections: Vec::new(),
+            compound: None,
             _marker: PhantomData,
         }
     }
@@ -39,34 +132,126 @@ impl<M: ModelMeta> SelectBuilder<M> {
     // bad, very bad
     pub(crate) unsafe fn raw_filter(mut self, where_statement: String, params: &[PgParam]) -> Self {
         // very good, very nice, much ORM
-        self.filter = Some(EqExpression::new(
-            Condition::Basic(where_statement),
-            params.to_vec(),
-        ));
+        self.filter = Some(raw_condition(where_statement, params));
         self
     }
 
-    pub fn include<RFk, RModel, const R_FIELD_INDEX: usize, Ty, Sidecar: ModelSidecar>(
-        mut self,
-        field: Field<Ty, Sidecar>,
-    ) -> Self
-    where
-        RFk: AsPgType + NaiveDecodable + 'static,
-        RModel: ModelMeta,
-        Ty: Into<Option<FullRelation<RFk, RModel, R_FIELD_INDEX>>> + AsPgType,
-    {
-        self.includes.push((
-            format!(
-                "LEFT JOIN {} ON {}={}",
-                RModel::TABLE_NAME,
-                field.full_name,
-                RModel::FIELDS
-                    .get(R_FIELD_INDEX)
-                    .expect("field relation is not correctly defined")
-                    .full_name,
-            ),
-            RModel::FIELDS,
-        ));
+    // keeps the filter that's already there
+    pub(crate) unsafe fn raw_and_filter(self, where_statement: String, params: &[PgParam]) -> Self {
+        self.and_filter(raw_condition(where_statement, params))
+    }
+
+    pub(crate) fn and_filter(mut self, condition: EqExpression) -> Self {
+        self.filter = Some(match self.filter {
+            Some(own) => own.and(condition),
+            None => condition,
+        });
+        self
+    }
+
+    // a forward relation, or a chain of them (review::book.then(book::author)),
+    //  a relation of an already included model can also be included directly
+    pub fn include<T: IncludeTarget>(mut self, target: T) -> Self {
+        let mut parent = None;
+
+        for (index, hop) in target.into_hops().into_iter().enumerate() {
+            if index == 0 {
+                parent = self.find_include_parent(&hop);
+            }
+
+            parent = Some(self.push_join(parent, hop));
+        }
+
+        self
+    }
+
+    fn find_include_parent(&self, hop: &IncludeHop) -> Option<usize> {
+        if hop.fk_table == M::TABLE_NAME {
+            return None;
+        }
+
+        self.includes
+            .iter()
+            .position(|join| join.hop.target_table == hop.fk_table)
+    }
+
+    fn push_join(&mut self, parent: Option<usize>, hop: IncludeHop) -> usize {
+        if let Some(existing) = self
+            .includes
+            .iter()
+            .position(|join| join.parent == parent && join.hop.fk_alias == hop.fk_alias)
+        {
+            return existing;
+        }
+
+        let taken = |name: &str| {
+            name == M::TABLE_NAME || self.includes.iter().any(|join| join.table_alias == name)
+        };
+
+        let (table_alias, column_prefix) = if taken(hop.target_table) {
+            let alias = (1..)
+                .map(|n| format!("{}__{}", hop.target_table, n))
+                .find(|it| !taken(it))
+                .expect("ran out of aliases");
+            let prefix = format!("{}__", alias);
+
+            (alias, Some(prefix))
+        } else {
+            (hop.target_table.to_string(), None)
+        };
+
+        let parent_alias = match parent {
+            Some(parent) => self.includes[parent].table_alias.as_str(),
+            None => hop.fk_table,
+        };
+
+        let on = hop
+            .columns
+            .iter()
+            .map(|(fk_column, target_column)| {
+                format!(
+                    "{}.{}={}.{}",
+                    parent_alias, fk_column, table_alias, target_column
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" AND ");
+
+        let sql = format!(
+            "LEFT JOIN {}{} ON {}",
+            hop.target_table,
+            match column_prefix {
+                Some(_) => format!(" AS {}", table_alias),
+                None => String::new(),
+            },
+            on,
+        );
+
+        self.includes.push(Join {
+            hop,
+            parent,
+            table_alias,
+            column_prefix,
+            sql,
+        });
+
+        self.includes.len() - 1
+    }
+
+    pub(crate) fn response_includes(&self) -> ResponseIncludes {
+        ResponseIncludes {
+            joins: self.includes.iter().map(Join::scope).collect(),
+            relations: self.relation_includes.clone(),
+            aggregates: vec![],
+        }
+    }
+
+    // loads an inverse relation (lazy or not) for every returned model, one query for all of them
+    //  only relation fields get through, a count_of field has nothing to load
+    pub fn include_many<T: RelationSubquery>(mut self, field: VirtualField<M::Id, T>) -> Self {
+        if !self.relation_includes.contains(&field.full_name) {
+            self.relation_includes.push(field.full_name);
+        }
         self
     }
 
@@ -80,24 +265,119 @@ impl<M: ModelMeta> SelectBuilder<M> {
         self
     }
 
+    // batched relation loads, "the latest 3 reviews of every book" is still one query
+    pub(crate) fn limit_per(mut self, field: &'static FieldMeta) -> Self {
+        self.partition = Some(field);
+        self
+    }
+
+    // set operations wrap both sides as they are, so anything called after them
+    //  (sort, limit, filter, include) applies to the combined result, see CompoundSelect::build
+    pub fn union(self, other: SelectBuilder<M>) -> Self {
+        self.combine(SetOperator::Union, other)
+    }
+
+    pub fn union_all(self, other: SelectBuilder<M>) -> Self {
+        self.combine(SetOperator::UnionAll, other)
+    }
+
+    pub fn intersect(self, other: SelectBuilder<M>) -> Self {
+        self.combine(SetOperator::Intersect, other)
+    }
+
+    pub fn except(self, other: SelectBuilder<M>) -> Self {
+        self.combine(SetOperator::Except, other)
+    }
+
+    pub fn combine(self, operator: SetOperator, other: SelectBuilder<M>) -> Self {
+        let relation_includes = self
+            .relation_includes
+            .iter()
+            .chain(&other.relation_includes)
+            .fold(Vec::new(), |mut acc, &it| {
+                if !acc.contains(&it) {
+                    acc.push(it);
+                }
+                acc
+            });
+
+        Self {
+            compound: Some(Box::new(CompoundSelect {
+                lhs: self,
+                operator,
+                rhs: other,
+            })),
+            relation_includes,
+            ..Self::new()
+        }
+    }
+
+    pub(crate) fn ensure_not_compound(&self) -> GasResult<()> {
+        match self.compound {
+            Some(_) => Err(COMPOUND_AGGREGATE_ERR),
+            None => Ok(()),
+        }
+    }
+
+    // selects a highlighted excerpt of field next to every model
+    pub fn headline<Ty: AsPgType + Headlineable, FM: ModelSidecar>(
+        self,
+        field: Field<Ty, FM>,
+        query: &TsQuery,
+    ) -> HeadlineSelect<M> {
+        HeadlineSelect::new(self, field, query)
+    }
+
+    // selects the number of related rows next to every model, see AggregatedSelect
+    pub fn with_count<T: RelationSubquery>(
+        self,
+        relation: VirtualField<M::Id, T>,
+    ) -> AggregatedSelect<M> {
+        AggregatedSelect::new(self).with_count(relation)
+    }
+
+    pub fn with_sum<T: RelationSubquery, N: Numeric>(
+        self,
+        relation: VirtualField<M::Id, T>,
+        field: Field<N, <T::Target as ModelMeta>::Id>,
+    ) -> AggregatedSelect<M>
+    where
+        N::SumType: Sync,
+    {
+        AggregatedSelect::new(self).with_sum(relation, field)
+    }
+
+    pub fn with_exists<T: RelationSubquery>(
+        self,
+        relation: VirtualField<M::Id, T>,
+    ) -> AggregatedSelect<M> {
+        AggregatedSelect::new(self).with_exists(relation)
+    }
+
     pub fn group<Ty: AsPgType>(self, field: Field<Ty, M::Id>) -> Group<M, Ty> {
         Group::new(field, self)
     }
 
     pub async fn find_all<E: PgExecutor>(self, ctx: E) -> GasResult<Vec<M>> {
+        let includes = self.response_includes();
         let (sql, params) = self.build(true);
 
-        let items = ctx.execute_parsed::<M>(sql, &params).await?;
+        let items = ctx
+            .execute_parsed_including::<M>(sql, &params, includes)
+            .await?;
 
         Ok(items)
     }
 
     pub async fn find_one<E: PgExecutor>(self, ctx: E) -> GasResult<Option<M>> {
+        let includes = self.response_includes();
         let (mut sql, params) = self.build(false);
 
         sql.append_str(" LIMIT 1");
 
-        let mut items = ctx.execute_parsed::<M>(sql, &params).await?;
+        let mut items = ctx
+            .execute_parsed_including::<M>(sql, &params, includes)
+            .await?;
 
         if items.len() > 1 {
             return Err(GasError::UnexpectedResponse(
@@ -108,11 +388,26 @@ impl<M: ModelMeta> SelectBuilder<M> {
         Ok(items.pop())
     }
 
+    // the exact sql and parameters find_all would send
+    pub fn to_sql(&self) -> GasResult<RenderedQuery> {
+        RenderedQuery::render(self.clone().build(true))
+    }
+
+    pub async fn explain<E: PgExecutor>(
+        self,
+        ctx: E,
+        options: ExplainOptions,
+    ) -> GasResult<ExplainPlan> {
+        explain::explain(ctx, self.build(true), &options).await
+    }
+
     pub async fn sum<E: PgExecutor, FM: ModelSidecar, N: Numeric>(
         self,
         ctx: E,
         field: Field<N, FM>,
     ) -> GasResult<N::SumType> {
+        self.ensure_not_compound()?;
+
         let aggregate_call = format!("SUM({})", field.full_name);
         let (sql, params) = self.build_aggregate_query(&aggregate_call);
 
@@ -131,6 +426,8 @@ impl<M: ModelMeta> SelectBuilder<M> {
         ctx: E,
         field: Field<T, FM>,
     ) -> GasResult<i64> {
+        self.ensure_not_compound()?;
+
         let aggregate_call = format!("COUNT({})", field.full_name);
         let (sql, params) = self.build_aggregate_query(&aggregate_call);
 
@@ -147,50 +444,125 @@ impl<M: ModelMeta> SelectBuilder<M> {
     // include_limit is important here because of find_one
     //  if limit is built into the query and then later on enforced by find_one,
     //  the query would fail; not very nice way to enforce an invariant but eh
-    fn build<'a>(self, include_limit: bool) -> SqlStatement<'a> {
-        let tmp = self.includes.iter().flat_map(|it| it.1);
+    pub(crate) fn build<'a>(self, include_limit: bool) -> SqlStatement<'a> {
+        if include_limit
+            && self.compound.is_none()
+            && let (Some(partition), Some(limit)) = (self.partition, self.limit)
+        {
+            return self.build_limited_per(partition, limit);
+        }
+
+        let mut params = Vec::new();
+
+        let mut sql = Self::build_select(
+            self.filter,
+            self.compound,
+            &self.includes,
+            &self.projections,
+            &mut params,
+        );
 
+        if let Some(sort_sql) = self.sort.as_ref().and_then(|it| it.as_sql()) {
+            sql.append_str(" ORDER BY ");
+            sql.append_query(&sort_sql);
+
+            params.extend(self.sort.iter().flat_map(|it| it.params()).cloned());
+        }
+
+        if include_limit && let Some(limit) = self.limit {
+            sql.append_str(&format!(" LIMIT {}", limit.get()));
+        }
+
+        (sql, params.into_boxed_slice())
+    }
+
+    // the rows are numbered within their partition, in the order of the sort
+    fn build_limited_per<'a>(self, partition: &FieldMeta, limit: NonZeroUsize) -> SqlStatement<'a> {
+        let order_by = self
+            .sort
+            .as_ref()
+            .and_then(|it| it.as_sql())
+            .map(|sql| format!(" ORDER BY {}", sql.as_str()))
+            .unwrap_or_default();
+
+        let mut projections = self.projections;
+        projections.push(Projection {
+            sql: format!(
+                "ROW_NUMBER() OVER (PARTITION BY {}{})",
+                partition.full_name, order_by
+            ),
+            alias: "__rank".to_string(),
+            params: self
+                .sort
+                .iter()
+                .flat_map(|it| it.params())
+                .cloned()
+                .collect(),
+        });
+
+        let mut params = Vec::new();
+        let inner =
+            Self::build_select(self.filter, None, &self.includes, &projections, &mut params);
+
+        let mut sql = SqlQuery::from("SELECT * FROM (");
+        sql.append_query(&inner);
+        sql.append_str(&format!(
+            ") AS __ranked WHERE __rank <= {} ORDER BY __rank",
+            limit.get()
+        ));
+
+        (sql, params.into_boxed_slice())
+    }
+
+    fn build_select<'a>(
+        filter: Option<EqExpression>,
+        compound: Option<Box<CompoundSelect<M>>>,
+        includes: &[Join],
+        projections: &[Projection],
+        params: &mut Vec<PgParam>,
+    ) -> SqlQuery<'a> {
         // sql
         let fields = M::FIELDS
             .iter()
-            .chain(tmp)
             .map(|f| format!("{} AS {}", f.full_name, f.alias_name))
+            .chain(relation_count_columns(M::VIRTUAL_FIELDS, M::TABLE_NAME, ""))
+            .chain(includes.iter().flat_map(Join::select_fields))
+            .chain(
+                projections
+                    .iter()
+                    .map(|p| format!("{} AS {}", p.sql, p.alias)),
+            )
             .reduce(|acc, cur| format!("{}, {}", acc, cur))
             .expect("no fields");
 
-        let mut sql = SqlQuery::from(format!("SELECT {} FROM {}", fields, M::TABLE_NAME));
+        // projections come before WHERE, so do their params
+        params.extend(projections.iter().flat_map(|p| p.params.iter()).cloned());
 
-        for include in self.includes {
+        let mut sql = SqlQuery::from(format!("SELECT {} FROM ", fields));
+
+        match compound {
+            Some(compound) => sql.append_query(&compound.build(params)),
+            None => sql.append_str(M::TABLE_NAME),
+        }
+
+        for include in includes {
             sql.append_str(" ");
-            sql.append_str(include.0.as_str());
+            sql.append_str(include.sql.as_str());
         }
 
-        if let Some(ref filter) = self.filter {
+        if let Some(filter) = filter {
             sql.append_str(" WHERE ");
             sql.append_query(&filter.condition.as_sql());
-        }
-
-        if let Some(ref sort) = self.sort
-            && let Some(sort_sql) = sort.as_sql()
-        {
-            sql.append_str(" ORDER BY ");
-            sql.append_query(&sort_sql);
-        }
 
-        if include_limit && let Some(limit) = self.limit {
-            sql.append_str(&format!(" LIMIT {}", limit.get()));
+            params.extend(filter.params);
         }
 
-        // params
-        let params = self
-            .filter
-            .map(|it| it.params.into_boxed_slice())
-            .unwrap_or_else(|| Box::new([]));
-
-        (sql, params)
+        sql
     }
 
     pub fn build_aggregate_query(self, aggregate_call: &str) -> SqlStatement<'_> {
+        debug_assert!(self.compound.is_none());
+
         // sql
         let mut sql = SqlQuery::from(format!(
             "SELECT {} as aggregate FROM {}",
@@ -200,7 +572,7 @@ impl<M: ModelMeta> SelectBuilder<M> {
 
         for include in self.includes {
             sql.append_str(" ");
-            sql.append_str(include.0.as_str());
+            sql.append_str(include.sql.as_str());
         }
 
         if let Some(ref filter) = self.filter {
@@ -217,3 +589,164 @@ impl<M: ModelMeta> SelectBuilder<M> {
         (sql, params)
     }
 }
+
+impl<M: ModelMeta> CompoundSelect<M> {
+    // both sides are selected as they are and the combined rows take the place of the table,
+    //  under its name and with its column names, so the outer select reads them like the table itself
+    //  and the filter, sort, limit and includes of the combined select apply after the set operation
+    fn build<'a>(self, params: &mut Vec<PgParam>) -> SqlQuery<'a> {
+        let mut sql = SqlQuery::from("(");
+
+        for (index, side) in [self.lhs, self.rhs].into_iter().enumerate() {
+            if index > 0 {
+                sql.append_str(&format!(" {} ", self.operator.as_sql()));
+            }
+
+            let (side_sql, side_params) = side.build(true);
+            sql.append_str("(");
+            sql.append_query(&side_sql);
+            sql.append_str(")");
+
+            params.extend(side_params);
+        }
+
+        // the model columns come first in every side, anything after them keeps its alias
+        let columns = M::FIELDS
+            .iter()
+            .map(|f| f.name)
+            .collect::<Vec<_>>()
+            .join(", ");
+        sql.append_str(&format!(") AS {}({})", M::TABLE_NAME, columns));
+
+        sql
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::eq::PgEq;
+    use crate::fixtures::*;
+    use crate::internals::PgParam;
+    use crate::{ModelMeta, ModelOps};
+
+    #[test]
+    pub fn test_to_sql() {
+        let rendered = person::Model::query()
+            .filter(|| person::name.eq("ferris") & person::age.gt(5))
+            .sort(person::age.desc())
+            .limit(10)
+            .to_sql()
+            .unwrap();
+
+        assert_eq!(
+            rendered.sql,
+            "SELECT people.id AS people_id, people.name AS people_name, people.age AS people_age \
+             FROM people WHERE (people.name=$1) AND (people.age>$2) \
+             ORDER BY people.age DESC LIMIT 10;"
+        );
+        assert_eq!(rendered.params.len(), 2);
+    }
+
+    #[test]
+    pub fn test_union_params() {
+        let young = person::Model::query()
+            .filter(|| person::age.lt(18))
+            .limit(5);
+        let named = person::Model::query().filter(|| person::name.eq("ferris"));
+
+        let rendered = young
+            .union(named)
+            .filter(|| person::id.gt(100))
+            .sort(person::age.desc())
+            .limit(10)
+            .to_sql()
+            .unwrap();
+
+        // the outer filter, sort and limit apply to the combined rows, after the limit of a side
+        assert_eq!(
+            rendered.sql,
+            "SELECT people.id AS people_id, people.name AS people_name, people.age AS people_age \
+             FROM ((SELECT people.id AS people_id, people.name AS people_name, people.age AS people_age \
+             FROM people WHERE people.age<$1 LIMIT 5) \
+             UNION (SELECT people.id AS people_id, people.name AS people_name, people.age AS people_age \
+             FROM people WHERE people.name=$2)) AS people(id, name, age) \
+             WHERE people.id>$3 ORDER BY people.age DESC LIMIT 10;"
+        );
+        assert!(matches!(
+            &rendered.params[..],
+            [
+                PgParam::INTEGER(Some(18)),
+                PgParam::TEXT(Some(_)),
+                PgParam::BIGINT(Some(100))
+            ]
+        ));
+
+        // includes are joined to the combined rows as well
+        let rendered = document::Model::query()
+            .intersect(document::Model::query().filter(|| document::title.eq("draft")))
+            .include(document::created_by)
+            .to_sql()
+            .unwrap();
+
+        assert!(rendered.sql.ends_with(
+            "AS documents(id, title, created_by, updated_by) \
+             LEFT JOIN people ON documents.created_by=people.id;"
+        ));
+    }
+
+    #[test]
+    pub fn test_include_aliases() {
+        let rendered = comment::Model::query()
+            .include(comment::document.then(document::created_by))
+            .include(comment::document.then(document::updated_by))
+            .include(comment::document)
+            .to_sql()
+            .unwrap();
+
+        assert_eq!(
+            rendered.sql,
+            "SELECT comments.id AS comments_id, comments.document AS comments_document, \
+             documents.id AS documents_id, documents.title AS documents_title, \
+             documents.created_by AS documents_created_by, documents.updated_by AS documents_updated_by, \
+             people.id AS people_id, people.name AS people_name, people.age AS people_age, \
+             people__1.id AS people__1__people_id, people__1.name AS people__1__people_name, \
+             people__1.age AS people__1__people_age \
+             FROM comments LEFT JOIN documents ON comments.document=documents.id \
+             LEFT JOIN people ON documents.created_by=people.id \
+             LEFT JOIN people AS people__1 ON documents.updated_by=people__1.id;"
+        );
+
+        let scopes = comment::Model::query()
+            .include(comment::document)
+            .include(document::updated_by)
+            .response_includes()
+            .joins;
+
+        assert_eq!(scopes.len(), 2);
+        assert_eq!(scopes[1].parent, Some(0));
+        assert_eq!(scopes[1].fk_alias, "documents_updated_by");
+    }
+
+    #[test]
+    pub fn test_limit_per() {
+        use crate::InverseRelationSpec;
+
+        let rendered = comment::Model::query()
+            .filter(|| comment::id.gt(1))
+            .sort(comment::id.desc())
+            .limit(3)
+            .limit_per(&comment::Model::FIELDS[1])
+            .to_sql()
+            .unwrap();
+
+        assert_eq!(
+            rendered.sql,
+            "SELECT * FROM (SELECT comments.id AS comments_id, comments.document AS comments_document, \
+             ROW_NUMBER() OVER (PARTITION BY comments.document ORDER BY comments.id DESC) AS __rank \
+             FROM comments WHERE comments.id>$1) AS __ranked WHERE __rank <= 3 ORDER BY __rank;"
+        );
+
+        assert_eq!(document::__comments_inverse_query::LIMIT, Some(5));
+        assert!(document::__comments_inverse_query::sort().is_some());
+    }
+}
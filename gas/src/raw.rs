@@ -0,0 +1,55 @@
+use crate::connection::PgExecutor;
+use crate::error::GasError;
+use crate::internals::{PgParam, SqlQuery};
+use crate::row::FromRow;
+use crate::GasResult;
+use std::marker::PhantomData;
+
+// for the queries the builders can't express
+//  placeholders are `?`, same as everywhere else, they get renumbered before executing
+//  and every placeholder needs a bound parameter, otherwise the query is rejected
+#[derive(Debug, Clone)]
+pub struct RawQuery<T: FromRow> {
+    sql: String,
+    params: Vec<PgParam>,
+    _marker: PhantomData<T>,
+}
+
+pub fn raw<T: FromRow>(sql: impl Into<String>) -> RawQuery<T> {
+    RawQuery {
+        sql: sql.into(),
+        params: Vec::new(),
+        _marker: PhantomData,
+    }
+}
+
+impl<T: FromRow> RawQuery<T> {
+    pub fn bind(mut self, value: impl Into<PgParam>) -> Self {
+        self.params.push(value.into());
+        self
+    }
+
+    pub async fn fetch_all<E: PgExecutor>(self, ctx: E) -> GasResult<Vec<T>> {
+        ctx.execute_parsed::<T>(SqlQuery::from(self.sql), &self.params)
+            .await
+    }
+
+    pub async fn fetch_one<E: PgExecutor>(self, ctx: E) -> GasResult<Option<T>> {
+        let mut items = self.fetch_all(ctx).await?;
+
+        if items.len() > 1 {
+            return Err(GasError::UnexpectedResponse(
+                format!("fetch_one: got {}, expected <= 1", items.len()).into(),
+            ));
+        }
+
+        Ok(items.pop())
+    }
+
+    // for statements that don't return anything useful
+    pub async fn execute<E: PgExecutor>(self, ctx: E) -> GasResult<()> {
+        ctx.execute(SqlQuery::from(self.sql), &self.params).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,391 @@
+use crate::connection::PgExecutor;
+use crate::error::GasError;
+use crate::internals::{AsPgType, Numeric, PgType, RenderedQuery};
+use crate::model::ModelMeta;
+use crate::ops::select::{Projection, SelectBuilder};
+use crate::relation::exists::{self, RelationSubquery};
+use crate::row::{FromRow, FromRowNamed, RelationCtx, RelationFuture, ResponseCtx, Row};
+use crate::{Field, FieldMeta, GasResult, VirtualField};
+use std::any::Any;
+use std::fmt::{Debug, Formatter};
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+// #[relation(count_of = book::author)] book_count: i64
+//  the subquery names the counted table, so a model can count rows of its own table too
+pub(crate) fn relation_count_columns(
+    fields: &[FieldMeta],
+    table_alias: &str,
+    column_prefix: &str,
+) -> Vec<String> {
+    fields
+        .iter()
+        .filter_map(|field| match field.pg_type {
+            PgType::RELATION_COUNT { foreign_key } => Some((field, foreign_key)),
+            _ => None,
+        })
+        .map(|(field, foreign_key)| {
+            let link = counted_columns(foreign_key)
+                .into_iter()
+                .map(|(own, target)| format!("__counted.{}={}.{}", own, table_alias, target))
+                .collect::<Vec<_>>()
+                .join(" AND ");
+
+            format!(
+                "(SELECT COUNT(*) FROM {} AS __counted WHERE {}) AS {}{}",
+                foreign_key.table_name, link, column_prefix, field.alias_name
+            )
+        })
+        .collect()
+}
+
+// (column of the counted table, column of the model) pairs
+fn counted_columns(foreign_key: &FieldMeta) -> Vec<(&'static str, &'static str)> {
+    match foreign_key.pg_type {
+        PgType::FOREIGN_KEY { target_field, .. } => vec![(foreign_key.name, target_field.name)],
+        PgType::COMPOSITE_FOREIGN_KEY {
+            columns,
+            target_columns,
+        } => columns
+            .iter()
+            .zip(target_columns)
+            .map(|(own, target)| (own.name, target.name))
+            .collect(),
+        _ => panic!("count_of must point to a forward relation"),
+    }
+}
+
+// the relation rows as a derived table, see exists.rs, value is selected as __value
+fn related_rows<T: RelationSubquery>(value: Option<&str>) -> (String, String) {
+    exists::related_rows(&T::subquery_from(), &T::subquery_link(), value)
+}
+
+type AggregateValue = Arc<dyn Any + Send + Sync>;
+
+// a column selected by with_count/with_sum/with_exists, decoded into whatever type it was asked for
+#[derive(Debug, Clone)]
+pub(crate) struct AggregateColumn {
+    pub(crate) alias: String,
+    sql: String,
+    decode: fn(&ResponseCtx, &Row, &str) -> GasResult<AggregateValue>,
+}
+
+fn decode_value<T: FromRowNamed + Sync>(
+    ctx: &ResponseCtx,
+    row: &Row,
+    alias: &str,
+) -> GasResult<AggregateValue> {
+    Ok(Arc::new(T::from_row_named(ctx, row, alias)?))
+}
+
+fn count_alias(relation: &FieldMeta) -> String {
+    format!("count_{}", relation.alias_name)
+}
+
+fn exists_alias(relation: &FieldMeta) -> String {
+    format!("exists_{}", relation.alias_name)
+}
+
+fn sum_alias(relation: &FieldMeta, field: &FieldMeta) -> String {
+    format!("sum_{}_{}", relation.alias_name, field.name)
+}
+
+const AGGREGATE_COMPOUND_ERR: GasError = GasError::InvalidInput(
+    "relation aggregates are not supported on compound (UNION, ...) selects",
+);
+
+#[derive(Debug, Clone)]
+pub struct AggregatedSelect<M: ModelMeta> {
+    select: SelectBuilder<M>,
+    aggregates: Vec<AggregateColumn>,
+}
+
+impl<M: ModelMeta> AggregatedSelect<M> {
+    pub(crate) fn new(select: SelectBuilder<M>) -> Self {
+        Self {
+            select,
+            aggregates: vec![],
+        }
+    }
+
+    fn push(
+        mut self,
+        alias: String,
+        sql: String,
+        decode: fn(&ResponseCtx, &Row, &str) -> GasResult<AggregateValue>,
+    ) -> Self {
+        if !self.aggregates.iter().any(|it| it.alias == alias) {
+            self.aggregates.push(AggregateColumn { alias, sql, decode });
+        }
+        self
+    }
+
+    // number of related rows, author::books -> how many books every author has
+    pub fn with_count<T: RelationSubquery>(self, relation: VirtualField<M::Id, T>) -> Self {
+        let (rows, link) = related_rows::<T>(None);
+        let sql = format!(
+            "(SELECT COUNT(*) FROM ({}) AS __related WHERE {})",
+            rows, link
+        );
+
+        self.push(count_alias(&relation), sql, decode_value::<i64>)
+    }
+
+    // sum of a field of the related rows, 0 when there are none
+    pub fn with_sum<T: RelationSubquery, N: Numeric>(
+        self,
+        relation: VirtualField<M::Id, T>,
+        field: Field<N, <T::Target as ModelMeta>::Id>,
+    ) -> Self
+    where
+        N::SumType: Sync,
+    {
+        // cast, so the column is always of the sum type (SUM(real) is a real)
+        let (rows, link) = related_rows::<T>(Some(field.full_name));
+        let sql = format!(
+            "(SELECT COALESCE(SUM(__related.__value), 0)::{} FROM ({}) AS __related WHERE {})",
+            <N::SumType as AsPgType>::PG_TYPE.as_sql_type(false),
+            rows,
+            link
+        );
+
+        self.push(
+            sum_alias(&relation, &field),
+            sql,
+            decode_value::<N::SumType>,
+        )
+    }
+
+    pub fn with_exists<T: RelationSubquery>(self, relation: VirtualField<M::Id, T>) -> Self {
+        let (rows, link) = related_rows::<T>(None);
+        let sql = format!(
+            "EXISTS (SELECT 1 FROM ({}) AS __related WHERE {})",
+            rows, link
+        );
+
+        self.push(exists_alias(&relation), sql, decode_value::<bool>)
+    }
+
+    fn into_select(self) -> GasResult<(SelectBuilder<M>, Vec<AggregateColumn>)> {
+        self.select
+            .ensure_not_compound()
+            .or(Err(AGGREGATE_COMPOUND_ERR))?;
+
+        let mut select = self.select;
+        select
+            .projections
+            .extend(self.aggregates.iter().map(|aggregate| Projection {
+                sql: aggregate.sql.clone(),
+                alias: aggregate.alias.clone(),
+                params: vec![],
+            }));
+
+        Ok((select, self.aggregates))
+    }
+
+    pub async fn find_all<E: PgExecutor>(self, ctx: E) -> GasResult<Vec<Aggregated<M>>> {
+        let (select, aggregates) = self.into_select()?;
+        let mut includes = select.response_includes();
+        includes.aggregates = aggregates;
+        let (sql, params) = select.build(true);
+
+        ctx.execute_parsed_including::<Aggregated<M>>(sql, &params, includes)
+            .await
+    }
+
+    pub async fn find_one<E: PgExecutor>(self, ctx: E) -> GasResult<Option<Aggregated<M>>> {
+        let (select, aggregates) = self.into_select()?;
+        let mut includes = select.response_includes();
+        includes.aggregates = aggregates;
+        let (mut sql, params) = select.build(false);
+
+        sql.append_str(" LIMIT 1");
+
+        let mut items = ctx
+            .execute_parsed_including::<Aggregated<M>>(sql, &params, includes)
+            .await?;
+
+        if items.len() > 1 {
+            return Err(GasError::UnexpectedResponse(
+                format!("find_one: got {}, expected <= 1", items.len()).into(),
+            ));
+        }
+
+        Ok(items.pop())
+    }
+
+    pub fn to_sql(&self) -> GasResult<RenderedQuery> {
+        RenderedQuery::render(self.clone().into_select()?.0.build(true))
+    }
+}
+
+#[derive(Clone)]
+pub struct Aggregated<M> {
+    pub model: M,
+    values: Vec<(String, AggregateValue)>,
+}
+
+impl<M: ModelMeta> Aggregated<M> {
+    // None if it wasn't selected, same as get() of a relation that wasn't loaded
+    fn value<T: Clone + 'static>(&self, alias: &str) -> Option<T> {
+        self.values
+            .iter()
+            .find(|(it, _)| it == alias)
+            .and_then(|(_, value)| value.downcast_ref::<T>())
+            .cloned()
+    }
+
+    // see with_count
+    pub fn count<T>(&self, relation: VirtualField<M::Id, T>) -> Option<i64> {
+        self.value(&count_alias(&relation))
+    }
+
+    pub fn sum<T: RelationSubquery, N: Numeric>(
+        &self,
+        relation: VirtualField<M::Id, T>,
+        field: Field<N, <T::Target as ModelMeta>::Id>,
+    ) -> Option<N::SumType> {
+        self.value(&sum_alias(&relation, &field))
+    }
+
+    pub fn exists<T>(&self, relation: VirtualField<M::Id, T>) -> Option<bool> {
+        self.value(&exists_alias(&relation))
+    }
+}
+
+impl<M: Debug> Debug for Aggregated<M> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Aggregated")
+            .field("model", &self.model)
+            .field(
+                "values",
+                &self
+                    .values
+                    .iter()
+                    .map(|(alias, _)| alias)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl<M: FromRow> FromRow for Aggregated<M> {
+    fn from_row(ctx: &ResponseCtx, row: &Row) -> GasResult<Self> {
+        Ok(Self {
+            model: M::from_row(ctx, row)?,
+            values: ctx
+                .aggregates
+                .iter()
+                .map(|aggregate| {
+                    Ok((
+                        aggregate.alias.clone(),
+                        (aggregate.decode)(ctx, row, &aggregate.alias)?,
+                    ))
+                })
+                .collect::<GasResult<_>>()?,
+        })
+    }
+
+    fn load_relations<'a>(items: Vec<&'a mut Self>, ctx: &'a RelationCtx) -> RelationFuture<'a> {
+        M::load_relations(items.into_iter().map(|it| &mut it.model).collect(), ctx)
+    }
+}
+
+impl<M> Deref for Aggregated<M> {
+    type Target = M;
+
+    fn deref(&self) -> &Self::Target {
+        &self.model
+    }
+}
+
+impl<M> DerefMut for Aggregated<M> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.model
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::fixtures::*;
+    use crate::relation::aggregate::Aggregated;
+    use crate::{ModelMeta, ModelOps};
+
+    #[test]
+    pub fn test_relation_aggregates() {
+        let rendered = document::Model::query()
+            .with_count(document::comments)
+            .with_sum(document::comments, comment::id)
+            .with_exists(document::comments)
+            .to_sql()
+            .unwrap();
+
+        assert_eq!(
+            rendered.sql,
+            "SELECT documents.id AS documents_id, documents.title AS documents_title, \
+             documents.created_by AS documents_created_by, documents.updated_by AS documents_updated_by, \
+             (SELECT COUNT(*) FROM (SELECT comments.document AS __link0 FROM comments) AS __related \
+             WHERE __related.__link0=documents.id) AS count_virtual_comments, \
+             (SELECT COALESCE(SUM(__related.__value), 0)::DECIMAL \
+             FROM (SELECT comments.document AS __link0, comments.id AS __value FROM comments) AS __related \
+             WHERE __related.__link0=documents.id) AS sum_virtual_comments_id, \
+             EXISTS (SELECT 1 FROM (SELECT comments.document AS __link0 FROM comments) AS __related \
+             WHERE __related.__link0=documents.id) AS exists_virtual_comments \
+             FROM documents;"
+        );
+
+        // people counting their own table
+        let rendered = person::Model::query()
+            .with_count(person::friends)
+            .to_sql()
+            .unwrap();
+        assert!(rendered.sql.contains(
+            "(SELECT COUNT(*) FROM (SELECT friendships.person AS __link0 \
+             FROM friendships JOIN people ON friendships.friend=people.id) AS __related \
+             WHERE __related.__link0=people.id) AS count_virtual_friends"
+        ));
+
+        // nothing selected, nothing to panic about
+        let aggregated = Aggregated {
+            model: document::Model::default(),
+            values: vec![],
+        };
+        assert_eq!(aggregated.count(document::comments), None);
+        assert_eq!(aggregated.sum(document::comments, comment::id), None);
+        assert_eq!(aggregated.exists(document::comments), None);
+
+        let count_sql =
+            "(SELECT COUNT(*) FROM post_tags AS __counted WHERE __counted.tag=tags.id) \
+                         AS tags_post_count";
+
+        let rendered = tag::Model::query().to_sql().unwrap();
+        assert_eq!(
+            rendered.sql,
+            format!(
+                "SELECT tags.id AS tags_id, tags.name AS tags_name, {} FROM tags;",
+                count_sql
+            )
+        );
+
+        // never written, but returned
+        let tag = tag::Model::default();
+        let (update, params) = tag.gen_update_sql();
+        assert_eq!(
+            update.finish(&params).unwrap(),
+            format!(
+                "UPDATE tags SET name=$1 WHERE id=$2 RETURNING tags.id AS tags_id, tags.name AS tags_name, {};",
+                count_sql
+            )
+        );
+
+        let rendered = post_tag::Model::query()
+            .include(post_tag::tag)
+            .to_sql()
+            .unwrap();
+        assert!(rendered.sql.contains(count_sql));
+        assert!(matches!(
+            tag::post_count.field_type,
+            crate::VirtualFieldType::RelationCount
+        ));
+    }
+}
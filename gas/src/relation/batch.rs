@@ -0,0 +1,259 @@
+use crate::condition::MAX_PARAMS;
+use crate::connection::PgExecutor;
+use crate::error::GasError;
+use crate::internals::{AsPgType, PgArrayParam, PgParam};
+use crate::relation::composite::{CompositeKey, CompositeKeySpec, CompositeRelation};
+use crate::relation::forward::FullRelation;
+use crate::relation::inverse::{batch_key, find_all_batched, make_batch_query, InverseRelationOps};
+use crate::row::RelationCtx;
+use crate::{Field, FieldMeta, GasResult, ModelMeta, ModelOps, VirtualField};
+use std::collections::{HashMap, HashSet};
+
+// loads a forward relation of every model at once, for models that were fetched without include
+//  gas::load_relation(&db, &mut books, book::author)
+pub async fn load_relation<E: PgExecutor, M: ModelMeta, Ty: AsPgType + ForwardRelationBatchOps>(
+    ctx: E,
+    models: &mut [M],
+    field: Field<Ty, M::Id>,
+) -> GasResult<()> {
+    let relations = relations_of(models, &field)?;
+    Ty::load_batch(relations, ctx).await
+}
+
+// same for inverse and many-to-many relations, already loaded ones are reloaded
+//  gas::load_inverse_relation(&db, &mut authors, author::books)
+pub async fn load_inverse_relation<E: PgExecutor, M: ModelMeta, T, Ret: 'static>(
+    ctx: E,
+    models: &mut [M],
+    field: VirtualField<M::Id, T>,
+) -> GasResult<()>
+where
+    T: InverseRelationOps<Ret> + 'static,
+{
+    let relations = relations_of(models, &field)?;
+    let ctx = RelationCtx {
+        executor: ctx.relation_executor(),
+        includes: vec![],
+    };
+
+    T::load_batch(relations, &ctx).await
+}
+
+fn relations_of<'a, M: ModelMeta, T: 'static>(
+    models: &'a mut [M],
+    field: &FieldMeta,
+) -> GasResult<Vec<&'a mut T>> {
+    models
+        .iter_mut()
+        .map(|model| {
+            model
+                .get_mut_by_field::<T>(field)
+                .ok_or(GasError::InvalidRelation)
+        })
+        .collect()
+}
+
+// implemented by the field types of forward relations
+pub trait ForwardRelationBatchOps: Sized + 'static {
+    // one query for all the relations that aren't loaded yet, keys without a match are left as they are
+    fn load_batch<E: PgExecutor>(
+        relations: Vec<&mut Self>,
+        ctx: E,
+    ) -> impl Future<Output = GasResult<()>>;
+}
+
+impl<Fk: AsPgType + 'static, Model: ModelMeta, const FIELD_INDEX: usize> ForwardRelationBatchOps
+    for FullRelation<Fk, Model, FIELD_INDEX>
+where
+    PgParam: From<Fk>,
+{
+    async fn load_batch<E: PgExecutor>(relations: Vec<&mut Self>, ctx: E) -> GasResult<()> {
+        let mut pending = relations
+            .into_iter()
+            .filter_map(|relation| match &*relation {
+                FullRelation::ForeignKey(key) => Some((PgParam::from(key.clone()), relation)),
+                FullRelation::Loaded(_) => None,
+            })
+            .collect::<Vec<_>>();
+
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        let field = Model::FIELDS
+            .get(FIELD_INDEX)
+            .ok_or(GasError::InvalidRelation)?;
+
+        let keys = pending.iter().map(|(key, _)| key.clone()).collect();
+        let models = find_all_batched(make_batch_query::<Model>(field, keys), ctx)
+            .await?
+            .into_iter()
+            .map(|model| {
+                let key = model
+                    .get_param_by_field(field)
+                    .ok_or(GasError::InvalidRelation)?;
+                Ok((batch_key(&key), model))
+            })
+            .collect::<GasResult<HashMap<_, _>>>()?;
+
+        for (key, relation) in pending.iter_mut() {
+            if let Some(model) = models.get(&batch_key(key)) {
+                **relation = FullRelation::Loaded(model.clone());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<Fk: CompositeKey, Model: ModelMeta, Spec: CompositeKeySpec> ForwardRelationBatchOps
+    for CompositeRelation<Fk, Model, Spec>
+{
+    async fn load_batch<E: PgExecutor>(relations: Vec<&mut Self>, ctx: E) -> GasResult<()> {
+        let mut pending = relations
+            .into_iter()
+            .filter(|relation| relation.model.is_none())
+            .map(|relation| (relation.key().clone().into_params(), relation))
+            .collect::<Vec<_>>();
+
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        // every key only once
+        let mut seen = HashSet::new();
+        let keys = pending
+            .iter()
+            .map(|(key, _)| key)
+            .filter(|key| seen.insert(composite_batch_key(key)))
+            .cloned()
+            .collect();
+
+        let mut models = HashMap::new();
+        for (condition, params) in composite_key_conditions(Spec::TARGET_COLUMNS, keys) {
+            let mut select = Model::query();
+            unsafe {
+                select = select.raw_filter(condition, &params);
+            }
+
+            for model in select.find_all(ctx).await? {
+                let key = Spec::TARGET_COLUMNS
+                    .iter()
+                    .map(|field| model.get_param_by_field(field))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or(GasError::InvalidRelation)?;
+                models.insert(composite_batch_key(&key), model);
+            }
+        }
+
+        for (key, relation) in pending.iter_mut() {
+            if let Some(model) = models.get(&composite_batch_key(key)) {
+                relation.model = Some(model.clone());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// (a, b) IN (SELECT * FROM UNNEST(?, ?)), an array per column, so any number of keys is one query
+//  postgres has no arrays of rows to use with ANY, keys without an array type (enums, ...) fall back
+//  to (a, b) IN ((?, ?), (?, ?)), split over as many queries as the parameters need
+fn composite_key_conditions(
+    columns: &[&FieldMeta],
+    keys: Vec<Vec<PgParam>>,
+) -> Vec<(String, Vec<PgParam>)> {
+    let names = columns
+        .iter()
+        .map(|field| field.full_name)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let placeholders = vec!["?"; columns.len()].join(", ");
+
+    let arrays = (0..columns.len())
+        .map(|index| {
+            PgArrayParam::try_collect(keys.iter().map(|key| key[index].clone()).collect())
+                .ok()
+                .map(PgParam::ARRAY)
+        })
+        .collect::<Option<Vec<_>>>();
+
+    if let Some(arrays) = arrays {
+        let condition = format!("({}) IN (SELECT * FROM UNNEST({}))", names, placeholders);
+        return vec![(condition, arrays)];
+    }
+
+    let tuple = format!("({})", placeholders);
+    keys.chunks(MAX_PARAMS / columns.len())
+        .map(|chunk| {
+            let condition = format!(
+                "({}) IN ({})",
+                names,
+                vec![tuple.as_str(); chunk.len()].join(", ")
+            );
+            (condition, chunk.iter().flatten().cloned().collect())
+        })
+        .collect()
+}
+
+fn composite_batch_key(key: &[PgParam]) -> String {
+    key.iter().map(batch_key).collect::<Vec<_>>().join(",")
+}
+
+// null foreign keys are skipped
+impl<T: ForwardRelationBatchOps> ForwardRelationBatchOps for Option<T> {
+    async fn load_batch<E: PgExecutor>(relations: Vec<&mut Self>, ctx: E) -> GasResult<()> {
+        T::load_batch(
+            relations.into_iter().filter_map(Option::as_mut).collect(),
+            ctx,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::condition::MAX_PARAMS;
+    use crate::fixtures::*;
+    use crate::internals::PgParam;
+    use crate::relation::batch::composite_key_conditions;
+    use crate::ModelMeta;
+
+    #[test]
+    pub fn test_composite_key_conditions() {
+        let columns = [&order::Model::FIELDS[0], &order::Model::FIELDS[1]];
+
+        // any number of keys, just an array per column
+        let keys = (0..MAX_PARAMS as i64)
+            .map(|no| vec![PgParam::from(1i64), PgParam::from(no)])
+            .collect();
+        let conditions = composite_key_conditions(&columns, keys);
+        assert_eq!(conditions.len(), 1);
+        assert_eq!(
+            conditions[0].0,
+            "(orders.tenant_id, orders.order_no) IN (SELECT * FROM UNNEST(?, ?))"
+        );
+        assert_eq!(conditions[0].1.len(), 2);
+
+        // no array of enums, rows of params that never go over the limit
+        let columns = [&order::Model::FIELDS[0], &order::Model::FIELDS[3]];
+        let keys = (0..MAX_PARAMS as i64)
+            .map(|tenant| vec![PgParam::from(tenant), PgParam::from(OrderStatus::Shipped)])
+            .collect();
+        let conditions = composite_key_conditions(&columns, keys);
+        assert_eq!(conditions.len(), 3);
+        assert!(conditions[0]
+            .0
+            .starts_with("(orders.tenant_id, orders.status) IN ((?, ?), (?, ?), "));
+        assert!(conditions
+            .iter()
+            .all(|(_, params)| params.len() <= MAX_PARAMS));
+        assert_eq!(
+            conditions
+                .iter()
+                .map(|(_, params)| params.len())
+                .sum::<usize>(),
+            2 * MAX_PARAMS
+        );
+    }
+}
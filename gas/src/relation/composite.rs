@@ -0,0 +1,371 @@
+use crate::connection::PgExecutor;
+use crate::internals::{AsPgType, IsOptional, PgParam, PgType};
+use crate::relation::forward::{Relation, RelationOps};
+use crate::row::{FromRowNamed, ResponseCtx, Row};
+use crate::{Field, FieldMeta, GasResult, ModelMeta, ModelOps, ModelSidecar};
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+// generated by the macro for every composite relation, holds the columns on both sides
+pub trait CompositeKeySpec: Debug + Clone + Default + Send + Sync + 'static {
+    const COLUMNS: &'static [&'static FieldMeta];
+    const TARGET_COLUMNS: &'static [&'static FieldMeta];
+}
+
+// tuples of keys, (tenant_id, order_no)
+pub trait CompositeKey: Clone + Default + Send + Sync + 'static {
+    // the fields the key is made of, used to check the types of `fields` and `references`
+    type Fields<S: ModelSidecar>;
+    type OptionalFields<S: ModelSidecar>;
+
+    fn from_row_columns(ctx: &ResponseCtx, row: &Row, columns: &[&FieldMeta]) -> GasResult<Self>;
+
+    // postgres doesn't check the constraint when any of the columns is null (MATCH SIMPLE),
+    //  so neither do we
+    fn from_row_columns_optional(
+        ctx: &ResponseCtx,
+        row: &Row,
+        columns: &[&FieldMeta],
+    ) -> GasResult<Option<Self>>;
+
+    fn into_params(self) -> Vec<PgParam>;
+
+    // the key as stored in the columns of a model
+    fn from_model<M: ModelMeta>(model: &M, columns: &[&FieldMeta]) -> Option<Self>;
+
+    // and back, optional columns are set to Some
+    fn write_columns<M: ModelMeta>(&self, model: &mut M, columns: &[&FieldMeta]);
+}
+
+macro_rules! composite_key_impl {
+    ($($ty:ident $var:ident $index:tt),+) => {
+        impl<$($ty),+> CompositeKey for ($($ty,)+)
+        where
+            $($ty: AsPgType + Sync, Option<$ty>: AsPgType, PgParam: From<$ty>,)+
+        {
+            type Fields<S: ModelSidecar> = ($(Field<$ty, S>,)+);
+            type OptionalFields<S: ModelSidecar> = ($(Field<Option<$ty>, S>,)+);
+
+            fn from_row_columns(ctx: &ResponseCtx, row: &Row, columns: &[&FieldMeta]) -> GasResult<Self> {
+                Ok(($($ty::from_row_named(ctx, row, columns[$index].alias_name)?,)+))
+            }
+
+            fn from_row_columns_optional(
+                ctx: &ResponseCtx,
+                row: &Row,
+                columns: &[&FieldMeta],
+            ) -> GasResult<Option<Self>> {
+                $(let $var = Option::<$ty>::from_row_named(ctx, row, columns[$index].alias_name)?;)+
+
+                match ($($var,)+) {
+                    ($(Some($var),)+) => Ok(Some(($($var,)+))),
+                    _ => Ok(None),
+                }
+            }
+
+            fn into_params(self) -> Vec<PgParam> {
+                vec![$(PgParam::from(self.$index)),+]
+            }
+
+            fn from_model<M: ModelMeta>(model: &M, columns: &[&FieldMeta]) -> Option<Self> {
+                Some(($(model.get_by_field::<$ty>(columns[$index])?,)+))
+            }
+
+            fn write_columns<M: ModelMeta>(&self, model: &mut M, columns: &[&FieldMeta]) {
+                $(
+                    if let Some(column) = model.get_mut_by_field::<$ty>(columns[$index]) {
+                        *column = self.$index.clone();
+                    } else if let Some(column) = model.get_mut_by_field::<Option<$ty>>(columns[$index]) {
+                        *column = Some(self.$index.clone());
+                    }
+                )+
+            }
+        }
+    };
+}
+
+composite_key_impl!(A a 0, B b 1);
+composite_key_impl!(A a 0, B b 1, C c 2);
+composite_key_impl!(A a 0, B b 1, C c 2, D d 3);
+
+pub trait CompositeRelationTypeOps {
+    type Model: ModelMeta;
+    type ToComposite<Spec: CompositeKeySpec>;
+    type LocalFields<S: ModelSidecar>;
+    type TargetFields;
+}
+
+impl<Fk: CompositeKey, Model: ModelMeta> CompositeRelationTypeOps for Relation<Fk, Model> {
+    type Model = Model;
+    type ToComposite<Spec: CompositeKeySpec> = CompositeRelation<Fk, Model, Spec>;
+    type LocalFields<S: ModelSidecar> = Fk::Fields<S>;
+    type TargetFields = Fk::Fields<Model::Id>;
+}
+
+impl<Fk: CompositeKey, Model: ModelMeta> CompositeRelationTypeOps for Option<Relation<Fk, Model>> {
+    type Model = Model;
+    type ToComposite<Spec: CompositeKeySpec> = Option<CompositeRelation<Fk, Model, Spec>>;
+    type LocalFields<S: ModelSidecar> = Fk::OptionalFields<S>;
+    type TargetFields = Fk::Fields<Model::Id>;
+}
+
+// the constant of a composite relation field, there's no column behind it (see CompositeKeySpec),
+//  so it can only be included, filtered with has and compared by key
+pub struct CompositeField<T, M: ModelSidecar> {
+    pub meta: FieldMeta,
+    _marker: PhantomData<T>,
+    _model_marker: PhantomData<M>,
+}
+
+impl<T, M: ModelSidecar> CompositeField<T, M> {
+    pub const fn new(meta: FieldMeta) -> Self {
+        Self {
+            meta,
+            _marker: PhantomData,
+            _model_marker: PhantomData,
+        }
+    }
+}
+
+impl<T, M: ModelSidecar> Deref for CompositeField<T, M> {
+    type Target = FieldMeta;
+
+    fn deref(&self) -> &Self::Target {
+        &self.meta
+    }
+}
+
+// a relation over several columns of the model, the columns themselves are regular fields
+//  and this is only a view over them, so it's never written on insert/update
+#[derive(Debug, Clone, Default)]
+pub struct CompositeRelation<Fk: CompositeKey, Model: ModelMeta, Spec: CompositeKeySpec> {
+    pub(crate) key: Fk,
+    pub(crate) model: Option<Model>,
+    _spec: PhantomData<Spec>,
+}
+
+impl<Fk: CompositeKey, Model: ModelMeta, Spec: CompositeKeySpec>
+    CompositeRelation<Fk, Model, Spec>
+{
+    pub fn from_key(key: Fk) -> Self {
+        Self {
+            key,
+            model: None,
+            _spec: PhantomData,
+        }
+    }
+
+    // the key is taken from the model, insert_with_relations inserts it first if it's new
+    pub fn from_model(model: Model) -> Self {
+        Self {
+            key: Fk::from_model(&model, Spec::TARGET_COLUMNS).unwrap_or_default(),
+            model: Some(model),
+            _spec: PhantomData,
+        }
+    }
+
+    // the key as it was when the row was read
+    pub fn key(&self) -> &Fk {
+        &self.key
+    }
+
+    async fn load_by_key<E: PgExecutor>(ctx: E, key: Fk) -> GasResult<Option<Model>> {
+        let condition = Spec::TARGET_COLUMNS
+            .iter()
+            .map(|field| format!("{}=?", field.full_name))
+            .collect::<Vec<_>>()
+            .join(" AND ");
+
+        let mut select = Model::query();
+        unsafe {
+            select = select.raw_filter(condition, &key.into_params());
+        }
+
+        select.find_one(ctx).await
+    }
+
+    fn from_row_key(ctx: &ResponseCtx, row: &Row, name: &str, key: Fk) -> Self {
+        Self {
+            key,
+            model: ctx
+                .joined(name)
+                .and_then(|joined| Model::from_row(&joined, row).ok()),
+            _spec: PhantomData,
+        }
+    }
+}
+
+impl<Fk: CompositeKey, Model: ModelMeta, Spec: CompositeKeySpec> RelationOps<Model>
+    for CompositeRelation<Fk, Model, Spec>
+{
+    async fn load_mut<'a, E: PgExecutor>(&'a mut self, ctx: E) -> GasResult<Option<&'a mut Model>>
+    where
+        Model: 'a,
+    {
+        if self.model.is_none() {
+            self.model = Self::load_by_key(ctx, self.key.clone()).await?;
+        }
+
+        Ok(self.model.as_mut())
+    }
+
+    async fn load<'a, E: PgExecutor>(&'a mut self, ctx: E) -> GasResult<Option<&'a Model>>
+    where
+        Model: 'a,
+    {
+        match self.load_mut(ctx).await? {
+            Some(val) => Ok(Some(val)),
+            None => Ok(None),
+        }
+    }
+
+    fn model_mut(&mut self) -> Option<&mut Model> {
+        self.model.as_mut()
+    }
+
+    fn model(&self) -> Option<&Model> {
+        self.model.as_ref()
+    }
+}
+
+impl<Fk: CompositeKey, Model: ModelMeta, Spec: CompositeKeySpec> RelationOps<Model>
+    for Option<CompositeRelation<Fk, Model, Spec>>
+{
+    async fn load_mut<'a, E: PgExecutor>(&'a mut self, ctx: E) -> GasResult<Option<&'a mut Model>>
+    where
+        Model: 'a,
+    {
+        match self {
+            Some(relation) => relation.load_mut(ctx).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn load<'a, E: PgExecutor>(&'a mut self, ctx: E) -> GasResult<Option<&'a Model>>
+    where
+        Model: 'a,
+    {
+        match self {
+            Some(relation) => relation.load(ctx).await,
+            None => Ok(None),
+        }
+    }
+
+    fn model_mut(&mut self) -> Option<&mut Model> {
+        self.as_mut().and_then(|relation| relation.model_mut())
+    }
+
+    fn model(&self) -> Option<&Model> {
+        self.as_ref().and_then(|relation| relation.model())
+    }
+}
+
+// things required for the CompositeRelation type compatible with gas::model macro
+
+impl<Fk: CompositeKey, Model: ModelMeta, Spec: CompositeKeySpec> AsPgType
+    for CompositeRelation<Fk, Model, Spec>
+{
+    const PG_TYPE: PgType = PgType::COMPOSITE_FOREIGN_KEY {
+        columns: Spec::COLUMNS,
+        target_columns: Spec::TARGET_COLUMNS,
+    };
+}
+
+impl<Fk: CompositeKey, Model: ModelMeta, Spec: CompositeKeySpec> AsPgType
+    for Option<CompositeRelation<Fk, Model, Spec>>
+{
+    const PG_TYPE: PgType = <CompositeRelation<Fk, Model, Spec> as AsPgType>::PG_TYPE;
+}
+
+impl<Fk: CompositeKey, Model: ModelMeta, Spec: CompositeKeySpec> IsOptional
+    for CompositeRelation<Fk, Model, Spec>
+{
+    const FACTOR: u8 = 0;
+}
+
+impl<Fk: CompositeKey, Model: ModelMeta, Spec: CompositeKeySpec> FromRowNamed
+    for CompositeRelation<Fk, Model, Spec>
+{
+    fn from_row_named(ctx: &ResponseCtx, row: &Row, name: &str) -> GasResult<Self> {
+        let key = Fk::from_row_columns(ctx, row, Spec::COLUMNS)?;
+        Ok(Self::from_row_key(ctx, row, name, key))
+    }
+}
+
+impl<Fk: CompositeKey, Model: ModelMeta, Spec: CompositeKeySpec> FromRowNamed
+    for Option<CompositeRelation<Fk, Model, Spec>>
+{
+    fn from_row_named(ctx: &ResponseCtx, row: &Row, name: &str) -> GasResult<Self> {
+        Ok(Fk::from_row_columns_optional(ctx, row, Spec::COLUMNS)?
+            .map(|key| CompositeRelation::from_row_key(ctx, row, name, key)))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Fk: CompositeKey, Model: ModelMeta, Spec: CompositeKeySpec> serde::Serialize
+    for CompositeRelation<Fk, Model, Spec>
+where
+    Fk: serde::Serialize,
+    Model: serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self.model {
+            Some(ref model) => model.serialize(serializer),
+            None => self.key.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::eq::{PgEq, PgRelationEq};
+    use crate::fixtures::*;
+    use crate::{ModelOps, PgRelationHas};
+
+    #[test]
+    pub fn test_composite_relation() {
+        assert_eq!(
+            order_line::order.pg_type.as_constraint_sql().unwrap(),
+            "FOREIGN KEY (tenant_id, order_no) REFERENCES orders(tenant_id, order_no)"
+        );
+        assert_eq!(
+            order_line::order.foreign_key_actions.as_sql(),
+            " ON DELETE CASCADE"
+        );
+
+        let rendered = order_line::Model::query()
+            .include(order_line::order)
+            .to_sql()
+            .unwrap();
+
+        assert_eq!(
+            rendered.sql,
+            "SELECT order_lines.id AS order_lines_id, order_lines.tenant_id AS order_lines_tenant_id, \
+             order_lines.order_no AS order_lines_order_no, \
+             orders.tenant_id AS orders_tenant_id, orders.order_no AS orders_order_no, orders.note AS orders_note, \
+             orders.status AS orders_status \
+             FROM order_lines LEFT JOIN orders \
+             ON order_lines.tenant_id=orders.tenant_id AND order_lines.order_no=orders.order_no;"
+        );
+
+        // only key comparisons and has, there's no order_lines.order column
+        let rendered = order_line::Model::query()
+            .filter(|| {
+                order_line::order.eq_key((1, 2)) & order_line::order.has(|| order::note.eq("rush"))
+            })
+            .to_sql()
+            .unwrap();
+
+        assert!(!rendered.sql.contains("order_lines.order "));
+        assert!(rendered.sql.ends_with(
+            "WHERE ((order_lines.tenant_id, order_lines.order_no)=($1, $2)) \
+             AND (EXISTS (SELECT 1 FROM (SELECT orders.tenant_id AS __link0, orders.order_no AS __link1 \
+             FROM orders WHERE orders.note=$3) AS __related \
+             WHERE __related.__link0=order_lines.tenant_id AND __related.__link1=order_lines.order_no));"
+        ));
+    }
+}
@@ -0,0 +1,280 @@
+use crate::condition::{Condition, EqExpression};
+use crate::internals::{AsPgType, PgParam, PgType};
+use crate::relation::include::{ForwardRelationType, RelationField};
+use crate::relation::inverse::{InverseRelation, InverseRelationSpec};
+use crate::relation::many_to_many::ManyToMany;
+use crate::{FieldMeta, ModelMeta, ModelSidecar, VirtualField};
+
+// the rows behind a virtual relation, as seen from inside an EXISTS subquery
+pub trait RelationSubquery {
+    type Target: ModelMeta;
+
+    // e.g. "books", or "post_tags JOIN tags ON post_tags.tag=tags.id"
+    fn subquery_from() -> String;
+
+    // (column of the subquery rows, column of the outer row) pairs that tie the two together
+    fn subquery_link() -> Vec<(String, String)>;
+}
+
+// the related rows go through a derived table, so the link can only ever mean the outer row
+//  a plain FROM of the same table (people -> friends) would shadow it
+pub(crate) fn related_rows(
+    from: &str,
+    link: &[(String, String)],
+    value: Option<&str>,
+) -> (String, String) {
+    let columns = link
+        .iter()
+        .enumerate()
+        .map(|(i, (column, _))| format!("{} AS __link{}", column, i))
+        .chain(value.map(|value| format!("{} AS __value", value)))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let link = link
+        .iter()
+        .enumerate()
+        .map(|(i, (_, outer))| format!("__related.__link{}={}", i, outer))
+        .collect::<Vec<_>>()
+        .join(" AND ");
+
+    (format!("SELECT {} FROM {}", columns, from), link)
+}
+
+fn exists(
+    negated: bool,
+    from: &str,
+    link: &[(String, String)],
+    inner: EqExpression,
+) -> EqExpression {
+    let (select, link) = related_rows(from, link, None);
+
+    EqExpression::new(
+        Condition::Exists {
+            negated,
+            select,
+            link,
+            condition: Box::new(inner.condition),
+        },
+        inner.params,
+    )
+}
+
+pub trait PgRelationHas {
+    // book::author.has(|| author::name.eq("X"))
+    fn has<F: FnOnce() -> EqExpression>(&self, cond_fn: F) -> EqExpression;
+}
+
+impl<R: RelationField> PgRelationHas for R {
+    fn has<F: FnOnce() -> EqExpression>(&self, cond_fn: F) -> EqExpression {
+        let meta = self.meta();
+        let target_table = <<R::Relation as ForwardRelationType>::Model as ModelMeta>::TABLE_NAME;
+
+        let link = R::Relation::join_columns(meta)
+            .into_iter()
+            .map(|(own, target)| {
+                (
+                    format!("{}.{}", target_table, target),
+                    format!("{}.{}", meta.table_name, own),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        exists(false, target_table, &link, cond_fn())
+    }
+}
+
+pub trait PgRelationAny {
+    // author::books.any(|| book::published_year.gt(2000))
+    fn any<F: FnOnce() -> EqExpression>(&self, cond_fn: F) -> EqExpression;
+
+    fn none<F: FnOnce() -> EqExpression>(&self, cond_fn: F) -> EqExpression;
+}
+
+impl<S: ModelSidecar, T: RelationSubquery> PgRelationAny for VirtualField<S, T> {
+    fn any<F: FnOnce() -> EqExpression>(&self, cond_fn: F) -> EqExpression {
+        exists(false, &T::subquery_from(), &T::subquery_link(), cond_fn())
+    }
+
+    fn none<F: FnOnce() -> EqExpression>(&self, cond_fn: F) -> EqExpression {
+        exists(true, &T::subquery_from(), &T::subquery_link(), cond_fn())
+    }
+}
+
+fn inverse_link<Parent: ModelMeta, Child: ModelMeta>(
+    forward_index: usize,
+    own_index: usize,
+) -> Vec<(String, String)> {
+    let child = Child::FIELDS.get(forward_index).expect("invalid relation");
+    let parent = Parent::FIELDS.get(own_index).expect("invalid relation");
+
+    vec![(child.full_name.to_string(), parent.full_name.to_string())]
+}
+
+impl<
+        SelfModel: ModelMeta,
+        Fk: AsPgType,
+        M: ModelMeta,
+        const FORWARD_FIELD_INDEX: usize,
+        const OWN_FIELD_INDEX: usize,
+        Spec: InverseRelationSpec,
+    > RelationSubquery
+    for InverseRelation<SelfModel, Fk, Box<[M]>, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX, Spec>
+where
+    PgParam: From<Fk>,
+{
+    type Target = M;
+
+    fn subquery_from() -> String {
+        M::TABLE_NAME.to_string()
+    }
+
+    fn subquery_link() -> Vec<(String, String)> {
+        inverse_link::<SelfModel, M>(FORWARD_FIELD_INDEX, OWN_FIELD_INDEX)
+    }
+}
+
+impl<
+        SelfModel: ModelMeta,
+        Fk: AsPgType,
+        M: ModelMeta,
+        const FORWARD_FIELD_INDEX: usize,
+        const OWN_FIELD_INDEX: usize,
+        Spec: InverseRelationSpec,
+    > RelationSubquery
+    for InverseRelation<SelfModel, Fk, Option<Box<M>>, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX, Spec>
+where
+    PgParam: From<Fk>,
+{
+    type Target = M;
+
+    fn subquery_from() -> String {
+        M::TABLE_NAME.to_string()
+    }
+
+    fn subquery_link() -> Vec<(String, String)> {
+        inverse_link::<SelfModel, M>(FORWARD_FIELD_INDEX, OWN_FIELD_INDEX)
+    }
+}
+
+// the field a foreign key of the join table points to
+fn referenced_field(field: &'static FieldMeta) -> &'static FieldMeta {
+    match field.pg_type {
+        PgType::FOREIGN_KEY { target_field, .. } => target_field,
+        _ => panic!("invalid relation"),
+    }
+}
+
+impl<
+        SelfModel: ModelMeta,
+        Fk: AsPgType,
+        Through: ModelMeta,
+        ToFk: AsPgType,
+        Target: ModelMeta,
+        const FROM_FIELD_INDEX: usize,
+        const TO_FIELD_INDEX: usize,
+    > RelationSubquery
+    for ManyToMany<SelfModel, Fk, Through, ToFk, Target, FROM_FIELD_INDEX, TO_FIELD_INDEX>
+where
+    PgParam: From<Fk> + From<ToFk>,
+{
+    type Target = Target;
+
+    fn subquery_from() -> String {
+        let to_field = Through::FIELDS
+            .get(TO_FIELD_INDEX)
+            .expect("invalid relation");
+
+        format!(
+            "{} JOIN {} ON {}={}",
+            Through::TABLE_NAME,
+            Target::TABLE_NAME,
+            to_field.full_name,
+            referenced_field(to_field).full_name
+        )
+    }
+
+    fn subquery_link() -> Vec<(String, String)> {
+        let from_field = Through::FIELDS
+            .get(FROM_FIELD_INDEX)
+            .expect("invalid relation");
+
+        vec![(
+            from_field.full_name.to_string(),
+            referenced_field(from_field).full_name.to_string(),
+        )]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::eq::PgEq;
+    use crate::fixtures::*;
+    use crate::{ModelOps, PgRelationAny, PgRelationHas};
+
+    #[test]
+    pub fn test_relation_filters() {
+        let rendered = document::Model::query()
+            .filter(|| {
+                document::created_by.has(|| person::name.eq("X"))
+                    & (document::comments.any(|| comment::id.gt(10))
+                        | document::comments.none(|| comment::id.gt(0)))
+            })
+            .to_sql()
+            .unwrap();
+
+        assert_eq!(
+            rendered.sql,
+            "SELECT documents.id AS documents_id, documents.title AS documents_title, \
+             documents.created_by AS documents_created_by, documents.updated_by AS documents_updated_by \
+             FROM documents WHERE (EXISTS (SELECT 1 FROM (SELECT people.id AS __link0 FROM people \
+             WHERE people.name=$1) AS __related WHERE __related.__link0=documents.created_by)) \
+             AND ((EXISTS (SELECT 1 FROM (SELECT comments.document AS __link0 FROM comments \
+             WHERE comments.id>$2) AS __related WHERE __related.__link0=documents.id)) \
+             OR (NOT EXISTS (SELECT 1 FROM (SELECT comments.document AS __link0 FROM comments \
+             WHERE comments.id>$3) AS __related WHERE __related.__link0=documents.id)));"
+        );
+        assert_eq!(rendered.params.len(), 3);
+
+        let rendered = post::Model::query()
+            .filter(|| post::tags.any(|| tag::name.eq("rust")))
+            .to_sql()
+            .unwrap();
+
+        assert!(rendered.sql.ends_with(
+            "WHERE EXISTS (SELECT 1 FROM (SELECT post_tags.post AS __link0 \
+             FROM post_tags JOIN tags ON post_tags.tag=tags.id WHERE tags.name=$1) AS __related \
+             WHERE __related.__link0=posts.id);"
+        ));
+    }
+
+    #[test]
+    pub fn test_self_referential_filters() {
+        // people inside the subquery are the friends, the link has to reach the outer people
+        let rendered = person::Model::query()
+            .filter(|| person::friends.none(|| person::age.lt(18)))
+            .to_sql()
+            .unwrap();
+
+        assert!(rendered.sql.ends_with(
+            "FROM people WHERE NOT EXISTS (SELECT 1 FROM (SELECT friendships.person AS __link0 \
+             FROM friendships JOIN people ON friendships.friend=people.id WHERE people.age<$1) \
+             AS __related WHERE __related.__link0=people.id);"
+        ));
+
+        // friends of friends, every level links to the one right above it
+        let rendered = person::Model::query()
+            .filter(|| person::friends.any(|| person::friends.any(|| person::name.eq("X"))))
+            .to_sql()
+            .unwrap();
+
+        assert!(rendered.sql.ends_with(
+            "FROM people WHERE EXISTS (SELECT 1 FROM (SELECT friendships.person AS __link0 \
+             FROM friendships JOIN people ON friendships.friend=people.id \
+             WHERE EXISTS (SELECT 1 FROM (SELECT friendships.person AS __link0 \
+             FROM friendships JOIN people ON friendships.friend=people.id WHERE people.name=$1) \
+             AS __related WHERE __related.__link0=people.id)) \
+             AS __related WHERE __related.__link0=people.id);"
+        ));
+    }
+}
@@ -1,11 +1,12 @@
 use crate::connection::PgExecutor;
 use crate::error::GasError;
-use crate::internals::{AsPgType, IsOptional, NaiveDecodable, PgParam, PgType};
+use crate::internals::{AsPgType, IsOptional, PgParam, PgType};
 use crate::row::{FromRowNamed, ResponseCtx, Row};
 use crate::{Field, GasResult, ModelMeta, ModelOps};
 use std::marker::PhantomData;
 
-pub struct Relation<Fk: AsPgType + 'static, Model: ModelMeta> {
+// Fk is a tuple for composite relations, see CompositeRelationTypeOps
+pub struct Relation<Fk: 'static, Model: ModelMeta> {
     _fk_marker: PhantomData<Fk>,
     _model_marker: PhantomData<Model>,
 }
@@ -210,7 +211,7 @@ impl<Fk: AsPgType, Model: ModelMeta, const FIELD_INDEX: usize> Default
     }
 }
 
-impl<Fk: AsPgType + NaiveDecodable, Model: ModelMeta, const FIELD_INDEX: usize> AsPgType
+impl<Fk: AsPgType, Model: ModelMeta, const FIELD_INDEX: usize> AsPgType
     for FullRelation<Fk, Model, FIELD_INDEX>
 {
     // NOTE: resolved in compile time, array access should fail on time
@@ -238,22 +239,27 @@ where
     }
 }
 
-impl<Fk: AsPgType + NaiveDecodable, Model: ModelMeta, const FIELD_INDEX: usize> FromRowNamed
+impl<Fk: AsPgType, Model: ModelMeta, const FIELD_INDEX: usize> FromRowNamed
     for FullRelation<Fk, Model, FIELD_INDEX>
 {
     fn from_row_named(ctx: &ResponseCtx, row: &Row, name: &str) -> GasResult<Self> {
-        Model::from_row(ctx, row)
-            .map(|model| FullRelation::Loaded(model))
-            .or_else(|_| {
-                Ok(FullRelation::ForeignKey(Fk::from_row_named(
-                    ctx, row, name,
-                )?))
-            })
+        // only loaded if this exact relation was included, joins of the same table through
+        //  other relations (or at other depths) have their own columns
+        let loaded = ctx
+            .joined(name)
+            .and_then(|joined| Model::from_row(&joined, row).ok());
+
+        match loaded {
+            Some(model) => Ok(FullRelation::Loaded(model)),
+            None => Ok(FullRelation::ForeignKey(Fk::from_row_named(
+                ctx, row, name,
+            )?)),
+        }
     }
 }
 
 // optional
-impl<Fk: AsPgType + NaiveDecodable, Model: ModelMeta, const FIELD_INDEX: usize> AsPgType
+impl<Fk: AsPgType, Model: ModelMeta, const FIELD_INDEX: usize> AsPgType
     for Option<FullRelation<Fk, Model, FIELD_INDEX>>
 where
     Option<Fk>: AsPgType,
@@ -274,16 +280,17 @@ where
     }
 }
 
-impl<Fk: AsPgType + NaiveDecodable, Model: ModelMeta, const FIELD_INDEX: usize> FromRowNamed
+impl<Fk: AsPgType, Model: ModelMeta, const FIELD_INDEX: usize> FromRowNamed
     for Option<FullRelation<Fk, Model, FIELD_INDEX>>
 where
     Option<Fk>: AsPgType,
 {
     fn from_row_named(ctx: &ResponseCtx, row: &Row, name: &str) -> GasResult<Self> {
         Ok(Option::<Fk>::from_row_named(ctx, row, name)?.map(|fk| {
-            Model::from_row(ctx, row)
+            ctx.joined(name)
+                .and_then(|joined| Model::from_row(&joined, row).ok())
                 .map(|model| FullRelation::Loaded(model))
-                .unwrap_or_else(|_| FullRelation::ForeignKey(fk))
+                .unwrap_or_else(|| FullRelation::ForeignKey(fk))
         }))
     }
 }
@@ -305,3 +312,24 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::fixtures::*;
+
+    #[test]
+    pub fn test_foreign_key_actions() {
+        assert_eq!(
+            post_tag::post.meta.foreign_key_actions.as_sql(),
+            " ON DELETE CASCADE"
+        );
+        assert_eq!(
+            comment::document.meta.foreign_key_actions.as_sql(),
+            " ON DELETE CASCADE ON UPDATE RESTRICT"
+        );
+        assert_eq!(
+            document::created_by.meta.foreign_key_actions,
+            crate::ForeignKeyActions::NONE
+        );
+    }
+}
@@ -0,0 +1,373 @@
+use crate::connection::{PgExecutor, RelationExecutor};
+use crate::error::GasError;
+use crate::internals::{AsPgType, PgParam};
+use crate::ops::insert::InsertOp;
+use crate::relation::composite::{CompositeKey, CompositeKeySpec, CompositeRelation};
+use crate::relation::forward::FullRelation;
+use crate::relation::inverse::{
+    InverseRelation, InverseRelationSpec, ToManyContainer, ToOneContainer,
+};
+use crate::relation::many_to_many::ManyToMany;
+use crate::row::{RelationCtx, RelationFuture};
+use crate::{GasResult, ModelMeta};
+
+// order.insert_with_relations(&db), parents first, then the model, then its children
+//  runs in a transaction of its own unless it's given one, so nothing is left half inserted
+pub(crate) async fn insert_with_relations<M: ModelMeta, E: PgExecutor>(
+    model: &mut M,
+    ctx: E,
+) -> GasResult<()> {
+    let transaction = match ctx.relation_executor() {
+        RelationExecutor::Transaction(transaction) => {
+            return model
+                .insert_graph(&graph_ctx(RelationExecutor::Transaction(transaction)))
+                .await;
+        }
+        RelationExecutor::Connection(connection) => connection.transaction().await?,
+    };
+
+    let ctx = graph_ctx(RelationExecutor::Transaction(transaction.clone()));
+    match model.insert_graph(&ctx).await {
+        Ok(()) => transaction.save().await,
+        Err(err) => {
+            transaction.discard().await?;
+            Err(err)
+        }
+    }
+}
+
+fn graph_ctx(executor: RelationExecutor) -> RelationCtx {
+    RelationCtx {
+        executor,
+        includes: vec![],
+    }
+}
+
+// the model alone, the generated insert_graph calls this once the parents have their keys
+pub async fn insert_model<M: ModelMeta>(model: &mut M, ctx: &RelationCtx) -> GasResult<()> {
+    InsertOp::new(std::slice::from_mut(model))
+        .run(&ctx.executor)
+        .await
+}
+
+// implemented by the field types of forward relations
+pub trait ForwardGraphOps: Send + Sized {
+    // a loaded model is new, it's inserted (with its own relations) before the one pointing to it
+    fn insert_parent<'a>(&'a mut self, ctx: &'a RelationCtx) -> RelationFuture<'a>;
+
+    // the same relation without the model, what the model holds while it's being inserted
+    fn key_only(&self) -> Self;
+
+    // composite relations are only a view over columns of the model, those get the key
+    fn write_columns<M: ModelMeta>(&self, _model: &mut M) {}
+}
+
+impl<Fk: AsPgType + Send + 'static, Model: ModelMeta, const FIELD_INDEX: usize> ForwardGraphOps
+    for FullRelation<Fk, Model, FIELD_INDEX>
+where
+    PgParam: From<Fk>,
+{
+    fn insert_parent<'a>(&'a mut self, ctx: &'a RelationCtx) -> RelationFuture<'a> {
+        Box::pin(async move {
+            match self {
+                FullRelation::Loaded(model) => model.insert_graph(ctx).await,
+                FullRelation::ForeignKey(_) => Ok(()),
+            }
+        })
+    }
+
+    fn key_only(&self) -> Self {
+        FullRelation::ForeignKey(self.get_foreign_key())
+    }
+}
+
+impl<Fk: CompositeKey, Model: ModelMeta, Spec: CompositeKeySpec> ForwardGraphOps
+    for CompositeRelation<Fk, Model, Spec>
+{
+    fn insert_parent<'a>(&'a mut self, ctx: &'a RelationCtx) -> RelationFuture<'a> {
+        Box::pin(async move {
+            if let Some(model) = &mut self.model {
+                model.insert_graph(ctx).await?;
+                self.key =
+                    Fk::from_model(model, Spec::TARGET_COLUMNS).ok_or(GasError::InvalidRelation)?;
+            }
+
+            Ok(())
+        })
+    }
+
+    fn key_only(&self) -> Self {
+        Self::from_key(self.key.clone())
+    }
+
+    fn write_columns<M: ModelMeta>(&self, model: &mut M) {
+        self.key.write_columns(model, Spec::COLUMNS);
+    }
+}
+
+impl<T: ForwardGraphOps> ForwardGraphOps for Option<T> {
+    fn insert_parent<'a>(&'a mut self, ctx: &'a RelationCtx) -> RelationFuture<'a> {
+        match self {
+            Some(relation) => relation.insert_parent(ctx),
+            None => Box::pin(async { Ok(()) }),
+        }
+    }
+
+    fn key_only(&self) -> Self {
+        self.as_ref().map(T::key_only)
+    }
+
+    fn write_columns<M: ModelMeta>(&self, model: &mut M) {
+        if let Some(relation) = self {
+            relation.write_columns(model);
+        }
+    }
+}
+
+// implemented by the field types of inverse and many-to-many relations
+pub trait InverseGraphOps: Send + Sized {
+    // `previous` is the relation as it was before the parent got inserted (and reread),
+    //  its loaded items are new and inserted pointing to the parent
+    fn insert_children<'a>(
+        &'a mut self,
+        previous: Self,
+        ctx: &'a RelationCtx,
+    ) -> RelationFuture<'a>;
+}
+
+// the forward relation of the child gets the key of the parent
+fn point_to_parent<
+    SelfModel: ModelMeta,
+    Fk: AsPgType + 'static,
+    M: ModelMeta,
+    const FORWARD_FIELD_INDEX: usize,
+    const OWN_FIELD_INDEX: usize,
+>(
+    child: &mut M,
+    parent_fk: &Fk,
+) -> GasResult<()>
+where
+    PgParam: From<Fk>,
+{
+    let field = M::FIELDS
+        .get(FORWARD_FIELD_INDEX)
+        .ok_or(GasError::InvalidRelation)?;
+
+    if let Some(relation) =
+        child.get_mut_by_field::<FullRelation<Fk, SelfModel, OWN_FIELD_INDEX>>(field)
+    {
+        *relation = FullRelation::ForeignKey(parent_fk.clone());
+    } else if let Some(relation) =
+        child.get_mut_by_field::<Option<FullRelation<Fk, SelfModel, OWN_FIELD_INDEX>>>(field)
+    {
+        *relation = Some(FullRelation::ForeignKey(parent_fk.clone()));
+    } else {
+        return Err(GasError::InvalidRelation);
+    }
+
+    Ok(())
+}
+
+impl<
+        SelfModel: ModelMeta,
+        Fk: AsPgType + Send + 'static,
+        M: ModelMeta,
+        const FORWARD_FIELD_INDEX: usize,
+        const OWN_FIELD_INDEX: usize,
+        Spec: InverseRelationSpec,
+    > InverseGraphOps
+    for InverseRelation<
+        SelfModel,
+        Fk,
+        ToManyContainer<M>,
+        FORWARD_FIELD_INDEX,
+        OWN_FIELD_INDEX,
+        Spec,
+    >
+where
+    PgParam: From<Fk>,
+{
+    fn insert_children<'a>(
+        &'a mut self,
+        previous: Self,
+        ctx: &'a RelationCtx,
+    ) -> RelationFuture<'a> {
+        Box::pin(async move {
+            if !previous.loaded {
+                return Ok(());
+            }
+
+            // the children are kept, even when one of them fails
+            let mut children = previous.items.into_vec();
+            let mut result = Ok(());
+            for child in children.iter_mut() {
+                result = point_to_parent::<SelfModel, Fk, M, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX>(
+                    child,
+                    &self.parent_fk,
+                );
+                if result.is_ok() {
+                    result = child.insert_graph(ctx).await;
+                }
+                if result.is_err() {
+                    break;
+                }
+            }
+
+            self.items = children.into_boxed_slice();
+            self.loaded = true;
+            result
+        })
+    }
+}
+
+impl<
+        SelfModel: ModelMeta,
+        Fk: AsPgType + Send + 'static,
+        M: ModelMeta,
+        const FORWARD_FIELD_INDEX: usize,
+        const OWN_FIELD_INDEX: usize,
+        Spec: InverseRelationSpec,
+    > InverseGraphOps
+    for InverseRelation<
+        SelfModel,
+        Fk,
+        ToOneContainer<M>,
+        FORWARD_FIELD_INDEX,
+        OWN_FIELD_INDEX,
+        Spec,
+    >
+where
+    PgParam: From<Fk>,
+{
+    fn insert_children<'a>(
+        &'a mut self,
+        previous: Self,
+        ctx: &'a RelationCtx,
+    ) -> RelationFuture<'a> {
+        Box::pin(async move {
+            if !previous.loaded {
+                return Ok(());
+            }
+
+            let mut child = previous.items;
+            let mut result = Ok(());
+            if let Some(child) = &mut child {
+                result = point_to_parent::<SelfModel, Fk, M, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX>(
+                    child,
+                    &self.parent_fk,
+                );
+                if result.is_ok() {
+                    result = child.insert_graph(ctx).await;
+                }
+            }
+
+            self.items = child;
+            self.loaded = true;
+            result
+        })
+    }
+}
+
+// the targets are inserted first, then linked through the join table
+impl<
+        SelfModel: ModelMeta,
+        Fk: AsPgType + Send + 'static,
+        Through: ModelMeta,
+        ToFk: AsPgType + Send + 'static,
+        Target: ModelMeta,
+        const FROM_FIELD_INDEX: usize,
+        const TO_FIELD_INDEX: usize,
+    > InverseGraphOps
+    for ManyToMany<SelfModel, Fk, Through, ToFk, Target, FROM_FIELD_INDEX, TO_FIELD_INDEX>
+where
+    PgParam: From<Fk> + From<ToFk>,
+{
+    fn insert_children<'a>(
+        &'a mut self,
+        previous: Self,
+        ctx: &'a RelationCtx,
+    ) -> RelationFuture<'a> {
+        Box::pin(async move {
+            if !previous.loaded {
+                return Ok(());
+            }
+
+            let parent_key = PgParam::from(self.parent_key.clone());
+
+            let mut targets = previous.items.into_vec();
+            let result: GasResult<()> = async {
+                let target_field = Self::target_field()?;
+                for target in targets.iter_mut() {
+                    target.insert_graph(ctx).await?;
+
+                    let key = target
+                        .get_param_by_field(target_field)
+                        .ok_or(GasError::InvalidRelation)?;
+                    Self::insert_link(&ctx.executor, parent_key.clone(), key).await?;
+                }
+                Ok(())
+            }
+            .await;
+
+            self.items = targets.into_boxed_slice();
+            self.loaded = true;
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::connection::unreachable;
+    use crate::fixtures::*;
+    use crate::row::RelationCtx;
+    use crate::{ForwardGraphOps, ModelMeta, RelationOps};
+
+    #[test]
+    pub fn test_graph_keys() {
+        let order = order::Model {
+            tenant_id: 3,
+            order_no: 7,
+            note: String::new(),
+            status: OrderStatus::Pending,
+        };
+
+        // the columns of a composite relation are written from the (inserted) model
+        let mut line = order_line::Model {
+            order: crate::CompositeRelation::from_model(order),
+            ..Default::default()
+        };
+        assert_eq!(line.order.key(), &(3, 7));
+
+        let relation = line.order.clone();
+
+        ForwardGraphOps::write_columns(&relation, &mut line);
+        assert_eq!((line.tenant_id, line.order_no), (3, 7));
+        assert!(ForwardGraphOps::key_only(&relation).model().is_none());
+    }
+
+    #[tokio::test]
+    pub async fn test_failed_insert_keeps_relations() {
+        let ctx = RelationCtx {
+            executor: unreachable(),
+            includes: vec![],
+        };
+
+        // the document itself fails, after its children were taken out
+        let mut document = document::Model {
+            comments: vec![comment::Model::default()].into(),
+            ..Default::default()
+        };
+        assert!(document.insert_graph(&ctx).await.is_err());
+        assert!(document.comments.is_loaded());
+        assert_eq!(document.comments.len(), 1);
+
+        // the parent fails, it's still loaded
+        let mut line = order_line::Model {
+            order: crate::CompositeRelation::from_model(order::Model::default()),
+            ..Default::default()
+        };
+        assert!(line.insert_graph(&ctx).await.is_err());
+        assert!(line.order.model().is_some());
+    }
+}
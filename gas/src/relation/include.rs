@@ -0,0 +1,196 @@
+use crate::internals::AsPgType;
+use crate::relation::composite::{
+    CompositeField, CompositeKey, CompositeKeySpec, CompositeRelation,
+};
+use crate::relation::forward::FullRelation;
+use crate::{Field, FieldMeta, ModelMeta, ModelSidecar};
+use std::marker::PhantomData;
+
+// implemented by the field types of forward relations,
+//  so includes don't have to carry the key and model type parameters around
+pub trait ForwardRelationType {
+    type Model: ModelMeta;
+
+    // (own column, target column) pairs the join goes through
+    fn join_columns(own: &FieldMeta) -> Vec<(&'static str, &'static str)>;
+}
+
+fn single_join_column<Model: ModelMeta>(
+    own: &FieldMeta,
+    field_index: usize,
+) -> Vec<(&'static str, &'static str)> {
+    let target = Model::FIELDS
+        .get(field_index)
+        .expect("field relation is not correctly defined");
+
+    vec![(own.name, target.name)]
+}
+
+fn composite_join_columns<Spec: CompositeKeySpec>() -> Vec<(&'static str, &'static str)> {
+    Spec::COLUMNS
+        .iter()
+        .zip(Spec::TARGET_COLUMNS)
+        .map(|(own, target)| (own.name, target.name))
+        .collect()
+}
+
+impl<Fk: AsPgType + 'static, Model: ModelMeta, const FIELD_INDEX: usize> ForwardRelationType
+    for FullRelation<Fk, Model, FIELD_INDEX>
+{
+    type Model = Model;
+
+    fn join_columns(own: &FieldMeta) -> Vec<(&'static str, &'static str)> {
+        single_join_column::<Model>(own, FIELD_INDEX)
+    }
+}
+
+impl<Fk: AsPgType + 'static, Model: ModelMeta, const FIELD_INDEX: usize> ForwardRelationType
+    for Option<FullRelation<Fk, Model, FIELD_INDEX>>
+{
+    type Model = Model;
+
+    fn join_columns(own: &FieldMeta) -> Vec<(&'static str, &'static str)> {
+        single_join_column::<Model>(own, FIELD_INDEX)
+    }
+}
+
+impl<Fk: CompositeKey, Model: ModelMeta, Spec: CompositeKeySpec> ForwardRelationType
+    for CompositeRelation<Fk, Model, Spec>
+{
+    type Model = Model;
+
+    fn join_columns(_own: &FieldMeta) -> Vec<(&'static str, &'static str)> {
+        composite_join_columns::<Spec>()
+    }
+}
+
+impl<Fk: CompositeKey, Model: ModelMeta, Spec: CompositeKeySpec> ForwardRelationType
+    for Option<CompositeRelation<Fk, Model, Spec>>
+{
+    type Model = Model;
+
+    fn join_columns(_own: &FieldMeta) -> Vec<(&'static str, &'static str)> {
+        composite_join_columns::<Spec>()
+    }
+}
+
+// the field constants a join can start from, foreign keys and composite relations
+pub trait RelationField {
+    type Relation: ForwardRelationType;
+    type Sidecar: ModelSidecar;
+
+    fn meta(&self) -> &FieldMeta;
+}
+
+impl<Ty: AsPgType + ForwardRelationType, S: ModelSidecar> RelationField for Field<Ty, S> {
+    type Relation = Ty;
+    type Sidecar = S;
+
+    fn meta(&self) -> &FieldMeta {
+        &self.meta
+    }
+}
+
+impl<Ty: ForwardRelationType, S: ModelSidecar> RelationField for CompositeField<Ty, S> {
+    type Relation = Ty;
+    type Sidecar = S;
+
+    fn meta(&self) -> &FieldMeta {
+        &self.meta
+    }
+}
+
+type RelationModel<F> = <<F as RelationField>::Relation as ForwardRelationType>::Model;
+
+// a single join, from a foreign key to the field(s) it points to
+#[derive(Debug, Clone)]
+pub struct IncludeHop {
+    pub(crate) fk_table: &'static str,
+    pub(crate) fk_alias: &'static str,
+    // more than one pair for composite relations
+    pub(crate) columns: Vec<(&'static str, &'static str)>,
+    pub(crate) target_table: &'static str,
+    pub(crate) target_fields: &'static [FieldMeta],
+    // relation counts are selected with the fields
+    pub(crate) target_virtual_fields: &'static [FieldMeta],
+}
+
+impl IncludeHop {
+    fn new<F: RelationField>(field: &F) -> Self {
+        let meta = field.meta();
+
+        Self {
+            fk_table: meta.table_name,
+            fk_alias: meta.alias_name,
+            columns: F::Relation::join_columns(meta),
+            target_table: RelationModel::<F>::TABLE_NAME,
+            target_fields: RelationModel::<F>::FIELDS,
+            target_virtual_fields: RelationModel::<F>::VIRTUAL_FIELDS,
+        }
+    }
+}
+
+// review::book.then(book::author), every step has to start where the previous one ended
+#[derive(Debug, Clone)]
+pub struct IncludePath<Tail: ModelMeta> {
+    hops: Vec<IncludeHop>,
+    _marker: PhantomData<Tail>,
+}
+
+impl<Tail: ModelMeta> IncludePath<Tail> {
+    pub fn then<Next: RelationField<Sidecar = Tail::Id>>(
+        mut self,
+        next: Next,
+    ) -> IncludePath<RelationModel<Next>> {
+        self.hops.push(IncludeHop::new(&next));
+
+        IncludePath {
+            hops: self.hops,
+            _marker: PhantomData,
+        }
+    }
+}
+
+fn path_from<F: RelationField, Next: RelationField>(
+    first: F,
+    next: Next,
+) -> IncludePath<RelationModel<Next>> {
+    IncludePath {
+        hops: vec![IncludeHop::new(&first), IncludeHop::new(&next)],
+        _marker: PhantomData,
+    }
+}
+
+impl<Ty: AsPgType + ForwardRelationType, S: ModelSidecar> Field<Ty, S> {
+    pub fn then<Next: RelationField<Sidecar = <Ty::Model as ModelMeta>::Id>>(
+        self,
+        next: Next,
+    ) -> IncludePath<RelationModel<Next>> {
+        path_from(self, next)
+    }
+}
+
+impl<Ty: ForwardRelationType, S: ModelSidecar> CompositeField<Ty, S> {
+    pub fn then<Next: RelationField<Sidecar = <Ty::Model as ModelMeta>::Id>>(
+        self,
+        next: Next,
+    ) -> IncludePath<RelationModel<Next>> {
+        path_from(self, next)
+    }
+}
+
+pub trait IncludeTarget {
+    fn into_hops(self) -> Vec<IncludeHop>;
+}
+
+impl<F: RelationField> IncludeTarget for F {
+    fn into_hops(self) -> Vec<IncludeHop> {
+        vec![IncludeHop::new(&self)]
+    }
+}
+
+impl<Tail: ModelMeta> IncludeTarget for IncludePath<Tail> {
+    fn into_hops(self) -> Vec<IncludeHop> {
+        self.hops
+    }
+}
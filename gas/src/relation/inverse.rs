@@ -1,14 +1,17 @@
+use crate::condition::{one_of_conditions, MAX_PARAMS};
 use crate::connection::PgExecutor;
 use crate::error::GasError;
 use crate::internals::{AsPgType, IsOptional, PgParam, PgType};
 use crate::ops::select::SelectBuilder;
 use crate::row::FromRowNamed;
-use crate::row::{ResponseCtx, Row};
+use crate::row::{RelationCtx, ResponseCtx, Row};
+use crate::sort::SortDefinition;
 use crate::FieldMeta;
 use crate::{GasResult, ModelMeta, ModelOps};
+use std::collections::HashMap;
+use std::fmt::Debug;
 use std::marker::PhantomData;
 use std::ops::Deref;
-use tokio::runtime::Handle;
 
 #[derive(Debug, Clone, Default)]
 pub struct InverseRelation<
@@ -17,13 +20,40 @@ pub struct InverseRelation<
     Ret: Clone + Default,
     const FORWARD_FIELD_INDEX: usize,
     const OWN_FIELD_INDEX: usize,
+    Spec: InverseRelationSpec,
 > where
     PgParam: From<Fk>,
 {
-    parent_fk: Fk,
-    loaded: bool,
-    items: Ret,
-    _marker: PhantomData<SelfModel>,
+    pub(crate) parent_fk: Fk,
+    pub(crate) loaded: bool,
+    pub(crate) items: Ret,
+    _marker: PhantomData<(SelfModel, Spec)>,
+}
+
+// the sort and limit given at the declaration, the macro generates one for every relation that has them
+//  #[relation(inverse = review::book, sort = review::created_at.desc(), limit = 5)]
+pub trait InverseRelationSpec: Debug + Clone + Default + Send + Sync + 'static {
+    const LIMIT: Option<usize> = None;
+
+    fn sort() -> Option<SortDefinition> {
+        None
+    }
+}
+
+impl InverseRelationSpec for () {}
+
+fn customized<M: ModelMeta, Spec: InverseRelationSpec>() -> SelectBuilder<M> {
+    let mut select = M::query();
+
+    if let Some(sort) = Spec::sort() {
+        select = select.sort(sort);
+    }
+
+    if let Some(limit) = Spec::LIMIT {
+        select = select.limit(limit);
+    }
+
+    select
 }
 
 pub enum InverseRelationType {
@@ -38,8 +68,8 @@ pub trait InverseRelationTypeOps {
 }
 
 // 📦, maybe replace with Arc
-type ToManyContainer<M> = Box<[M]>;
-type ToOneContainer<M> = Option<Box<M>>;
+pub(crate) type ToManyContainer<M> = Box<[M]>;
+pub(crate) type ToOneContainer<M> = Option<Box<M>>;
 
 impl<M: ModelMeta> InverseRelationTypeOps for Vec<M> {
     type Inner = ToManyContainer<M>;
@@ -54,26 +84,28 @@ impl<M: ModelMeta> InverseRelationTypeOps for Option<M> {
 }
 
 impl<
-    SelfModel: ModelMeta,
-    Fk: AsPgType,
-    Ret: Clone + Default,
-    const FORWARD_FIELD_INDEX: usize,
-    const OWN_FIELD_INDEX: usize,
-> AsPgType for InverseRelation<SelfModel, Fk, Ret, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX>
+        SelfModel: ModelMeta,
+        Fk: AsPgType,
+        Ret: Clone + Default,
+        const FORWARD_FIELD_INDEX: usize,
+        const OWN_FIELD_INDEX: usize,
+        Spec: InverseRelationSpec,
+    > AsPgType for InverseRelation<SelfModel, Fk, Ret, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX, Spec>
 where
-    InverseRelation<SelfModel, Fk, Ret, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX>: FromRowNamed,
+    InverseRelation<SelfModel, Fk, Ret, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX, Spec>: FromRowNamed,
     PgParam: From<Fk>,
 {
     const PG_TYPE: PgType = PgType::IGNORED;
 }
 
 impl<
-    SelfModel: ModelMeta,
-    Fk: AsPgType,
-    Ret: Clone + Default,
-    const FORWARD_FIELD_INDEX: usize,
-    const OWN_FIELD_INDEX: usize,
-> IsOptional for InverseRelation<SelfModel, Fk, Ret, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX>
+        SelfModel: ModelMeta,
+        Fk: AsPgType,
+        Ret: Clone + Default,
+        const FORWARD_FIELD_INDEX: usize,
+        const OWN_FIELD_INDEX: usize,
+        Spec: InverseRelationSpec,
+    > IsOptional for InverseRelation<SelfModel, Fk, Ret, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX, Spec>
 where
     PgParam: From<Fk>,
 {
@@ -81,97 +113,128 @@ where
 }
 
 impl<
-    SelfModel: ModelMeta,
-    Fk: AsPgType,
-    Ret: Clone + Default,
-    const FORWARD_FIELD_INDEX: usize,
-    const OWN_FIELD_INDEX: usize,
-> From<InverseRelation<SelfModel, Fk, Ret, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX>> for PgParam
+        SelfModel: ModelMeta,
+        Fk: AsPgType,
+        Ret: Clone + Default,
+        const FORWARD_FIELD_INDEX: usize,
+        const OWN_FIELD_INDEX: usize,
+        Spec: InverseRelationSpec,
+    > From<InverseRelation<SelfModel, Fk, Ret, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX, Spec>>
+    for PgParam
 where
     PgParam: From<Fk>,
 {
     fn from(
-        _value: InverseRelation<SelfModel, Fk, Ret, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX>,
+        _value: InverseRelation<SelfModel, Fk, Ret, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX, Spec>,
     ) -> Self {
         PgParam::IGNORED
     }
 }
 
 impl<
-    SelfModel: ModelMeta,
-    Fk: AsPgType + 'static,
-    M: ModelMeta,
-    const FORWARD_FIELD_INDEX: usize,
-    const OWN_FIELD_INDEX: usize,
-> FromRowNamed
-    for InverseRelation<SelfModel, Fk, ToManyContainer<M>, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX>
+        SelfModel: ModelMeta,
+        Fk: AsPgType + 'static,
+        M: ModelMeta,
+        const FORWARD_FIELD_INDEX: usize,
+        const OWN_FIELD_INDEX: usize,
+        Spec: InverseRelationSpec,
+    > FromRowNamed
+    for InverseRelation<
+        SelfModel,
+        Fk,
+        ToManyContainer<M>,
+        FORWARD_FIELD_INDEX,
+        OWN_FIELD_INDEX,
+        Spec,
+    >
 where
     PgParam: From<Fk>,
 {
     fn from_row_named(ctx: &ResponseCtx, row: &Row, _name: &str) -> GasResult<Self> {
-        Self::new_from_row_slow(ctx, row)
+        Self::new_from_row(ctx, row)
     }
 }
 
 impl<
-    SelfModel: ModelMeta,
-    Fk: AsPgType + 'static,
-    M: ModelMeta,
-    const FORWARD_FIELD_INDEX: usize,
-    const OWN_FIELD_INDEX: usize,
-> FromRowNamed
-    for InverseRelation<SelfModel, Fk, ToOneContainer<M>, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX>
+        SelfModel: ModelMeta,
+        Fk: AsPgType + 'static,
+        M: ModelMeta,
+        const FORWARD_FIELD_INDEX: usize,
+        const OWN_FIELD_INDEX: usize,
+        Spec: InverseRelationSpec,
+    > FromRowNamed
+    for InverseRelation<
+        SelfModel,
+        Fk,
+        ToOneContainer<M>,
+        FORWARD_FIELD_INDEX,
+        OWN_FIELD_INDEX,
+        Spec,
+    >
 where
     PgParam: From<Fk>,
 {
     fn from_row_named(ctx: &ResponseCtx, row: &Row, _name: &str) -> GasResult<Self> {
-        Self::new_from_row_slow(ctx, row)
+        Self::new_from_row(ctx, row)
     }
 }
 
 impl<
-    SelfModel: ModelMeta,
-    Fk: AsPgType,
-    Ret: Clone + Default,
-    const FORWARD_FIELD_INDEX: usize,
-    const OWN_FIELD_INDEX: usize,
-> Deref for InverseRelation<SelfModel, Fk, Ret, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX>
+        SelfModel: ModelMeta,
+        Fk: AsPgType,
+        Ret: Clone + Default,
+        const FORWARD_FIELD_INDEX: usize,
+        const OWN_FIELD_INDEX: usize,
+        Spec: InverseRelationSpec,
+    > Deref for InverseRelation<SelfModel, Fk, Ret, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX, Spec>
 where
     PgParam: From<Fk>,
 {
     type Target = Ret;
 
+    // an empty Vec/None would be indistinguishable from "no children", so this is loud instead
     fn deref(&self) -> &Self::Target {
+        if !self.loaded {
+            panic!(
+                "inverse relation on {} is not loaded, call `load` first (or use `get`)",
+                SelfModel::TABLE_NAME
+            );
+        }
+
         &self.items
     }
 }
 
 impl<
-    SelfModel: ModelMeta,
-    Fk: AsPgType + 'static,
-    Ret: Clone + Default,
-    const FORWARD_FIELD_INDEX: usize,
-    const OWN_FIELD_INDEX: usize,
-> InverseRelation<SelfModel, Fk, Ret, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX>
+        SelfModel: ModelMeta,
+        Fk: AsPgType + 'static,
+        Ret: Clone + Default + 'static,
+        const FORWARD_FIELD_INDEX: usize,
+        const OWN_FIELD_INDEX: usize,
+        Spec: InverseRelationSpec,
+    > InverseRelation<SelfModel, Fk, Ret, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX, Spec>
 where
-    InverseRelation<SelfModel, Fk, Ret, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX>:
+    InverseRelation<SelfModel, Fk, Ret, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX, Spec>:
         InverseRelationOps<Ret>,
     PgParam: From<Fk>,
 {
-    fn new_from_row_slow(ctx: &ResponseCtx, row: &Row) -> GasResult<Self> {
-        let mut instance = Self {
+    // the items are filled in later by load_batch, once all the rows are parsed
+    fn new_from_row(ctx: &ResponseCtx, row: &Row) -> GasResult<Self> {
+        Ok(Self {
             parent_fk: FromRowNamed::from_row_named(ctx, row, Self::get_fk_own_field().alias_name)?,
             loaded: false,
             items: Ret::default(),
             _marker: PhantomData,
-        };
+        })
+    }
 
-        // crimes
-        Handle::current().block_on(async move {
-            instance.reload(&ctx.connection).await?;
+    pub fn is_loaded(&self) -> bool {
+        self.loaded
+    }
 
-            Ok(instance)
-        })
+    // None if the relation is lazy and wasn't loaded yet
+    pub fn get(&self) -> Option<&Ret> {
+        self.loaded.then_some(&self.items)
     }
 
     pub async fn load<E: PgExecutor>(&mut self, ctx: E) -> GasResult<&Ret> {
@@ -193,30 +256,162 @@ where
             .get_by_field(Self::get_fk_own_field())
             .expect("invalid relation");
     }
+
+    fn batch_keys(relations: &[&mut Self]) -> Vec<PgParam> {
+        relations
+            .iter()
+            .map(|relation| PgParam::from(relation.parent_fk.clone()))
+            .collect()
+    }
+
+    // hands out the grouped children to their parents
+    //  the same parent can show up more than once (e.g. with a join), those get a clone
+    fn assign_batch<M>(
+        mut relations: Vec<&mut Self>,
+        mut grouped: HashMap<String, Vec<M>>,
+        wrap: impl Fn(Vec<M>) -> Ret,
+    ) {
+        let mut assigned: HashMap<String, usize> = HashMap::new();
+
+        for index in 0..relations.len() {
+            let key = batch_key(&PgParam::from(relations[index].parent_fk.clone()));
+
+            let items = match (grouped.remove(&key), assigned.get(&key)) {
+                (Some(items), _) => wrap(items),
+                (None, Some(previous)) => relations[*previous].items.clone(),
+                (None, None) => Ret::default(),
+            };
+
+            assigned.insert(key, index);
+            relations[index].items = items;
+            relations[index].loaded = true;
+        }
+    }
 }
 
-pub trait InverseRelationOps<Ret> {
-    fn reload<'a, E: PgExecutor>(&'a mut self, ctx: E) -> impl Future<Output = GasResult<&'a Ret>>
+pub trait InverseRelationOps<Ret: 'static> {
+    fn reload<E: PgExecutor>(&mut self, ctx: E) -> impl Future<Output = GasResult<&Ret>>;
+
+    // loads the relation for a whole response at once, one query no matter the amount of parents
+    fn load_batch<'a>(
+        relations: Vec<&'a mut Self>,
+        ctx: &'a RelationCtx,
+    ) -> impl Future<Output = GasResult<()>> + Send + 'a
     where
-        Ret: 'a;
+        Self: 'a;
 }
 
 impl<
-    SelfModel: ModelMeta,
-    Fk: AsPgType,
-    M: ModelMeta,
-    const FORWARD_FIELD_INDEX: usize,
-    const OWN_FIELD_INDEX: usize,
-> InverseRelationOps<ToManyContainer<M>>
-    for InverseRelation<SelfModel, Fk, ToManyContainer<M>, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX>
+        SelfModel: ModelMeta,
+        Fk: AsPgType,
+        M: ModelMeta + 'static,
+        const FORWARD_FIELD_INDEX: usize,
+        const OWN_FIELD_INDEX: usize,
+        Spec: InverseRelationSpec,
+    > InverseRelationOps<ToManyContainer<M>>
+    for InverseRelation<
+        SelfModel,
+        Fk,
+        ToManyContainer<M>,
+        FORWARD_FIELD_INDEX,
+        OWN_FIELD_INDEX,
+        Spec,
+    >
+where
+    PgParam: From<Fk>,
+{
+    async fn reload<E: PgExecutor>(&mut self, ctx: E) -> GasResult<&ToManyContainer<M>> {
+        self.load_with(ctx, |select| select).await
+    }
+
+    async fn load_batch<'a>(relations: Vec<&'a mut Self>, ctx: &'a RelationCtx) -> GasResult<()>
+    where
+        Self: 'a,
+    {
+        if relations.is_empty() {
+            return Ok(());
+        }
+
+        let select = make_batch_inverse_query::<M, FORWARD_FIELD_INDEX>(
+            customized::<M, Spec>(),
+            Self::batch_keys(&relations),
+        )?;
+        let grouped =
+            group_by_key::<M, FORWARD_FIELD_INDEX>(find_all_batched(select, &ctx.executor).await?)?;
+
+        Self::assign_batch(relations, grouped, Vec::into_boxed_slice);
+        Ok(())
+    }
+}
+
+impl<
+        SelfModel: ModelMeta,
+        Fk: AsPgType,
+        M: ModelMeta + 'static,
+        const FORWARD_FIELD_INDEX: usize,
+        const OWN_FIELD_INDEX: usize,
+        Spec: InverseRelationSpec,
+    > InverseRelationOps<ToOneContainer<M>>
+    for InverseRelation<
+        SelfModel,
+        Fk,
+        ToOneContainer<M>,
+        FORWARD_FIELD_INDEX,
+        OWN_FIELD_INDEX,
+        Spec,
+    >
 where
     PgParam: From<Fk>,
 {
-    async fn reload<'a, E: PgExecutor>(&'a mut self, ctx: E) -> GasResult<&'a ToManyContainer<M>>
+    async fn reload<E: PgExecutor>(&mut self, ctx: E) -> GasResult<&ToOneContainer<M>> {
+        self.load_with(ctx, |select| select).await
+    }
+
+    async fn load_batch<'a>(relations: Vec<&'a mut Self>, ctx: &'a RelationCtx) -> GasResult<()>
     where
-        M: 'a,
+        Self: 'a,
     {
-        let select = make_lazy_inverse_query::<Fk, M, FORWARD_FIELD_INDEX>(self.parent_fk.clone())?;
+        if relations.is_empty() {
+            return Ok(());
+        }
+
+        let select = make_batch_inverse_query::<M, FORWARD_FIELD_INDEX>(
+            customized::<M, Spec>(),
+            Self::batch_keys(&relations),
+        )?;
+        let grouped =
+            group_by_key::<M, FORWARD_FIELD_INDEX>(find_all_batched(select, &ctx.executor).await?)?;
+
+        // the foreign key is unique, so there's at most one
+        Self::assign_batch(relations, grouped, |items| {
+            items.into_iter().next().map(Box::from)
+        });
+        Ok(())
+    }
+}
+
+impl<
+        SelfModel: ModelMeta,
+        Fk: AsPgType,
+        M: ModelMeta + 'static,
+        const FORWARD_FIELD_INDEX: usize,
+        const OWN_FIELD_INDEX: usize,
+        Spec: InverseRelationSpec,
+    > InverseRelation<SelfModel, Fk, ToManyContainer<M>, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX, Spec>
+where
+    PgParam: From<Fk>,
+{
+    // always queries, the declared sort and limit are applied first so they can be replaced
+    //  book.reviews.load_with(&db, |q| q.filter(|| review::stars.gt(3)).limit(3))
+    pub async fn load_with<E: PgExecutor, F: FnOnce(SelectBuilder<M>) -> SelectBuilder<M>>(
+        &mut self,
+        ctx: E,
+        customize: F,
+    ) -> GasResult<&ToManyContainer<M>> {
+        let select = make_lazy_inverse_query::<Fk, M, FORWARD_FIELD_INDEX>(
+            customize(customized::<M, Spec>()),
+            self.parent_fk.clone(),
+        )?;
 
         let resp = select.find_all(ctx).await?;
         self.loaded = true;
@@ -226,21 +421,25 @@ where
 }
 
 impl<
-    SelfModel: ModelMeta,
-    Fk: AsPgType,
-    M: ModelMeta,
-    const FORWARD_FIELD_INDEX: usize,
-    const OWN_FIELD_INDEX: usize,
-> InverseRelationOps<ToOneContainer<M>>
-    for InverseRelation<SelfModel, Fk, ToOneContainer<M>, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX>
+        SelfModel: ModelMeta,
+        Fk: AsPgType,
+        M: ModelMeta + 'static,
+        const FORWARD_FIELD_INDEX: usize,
+        const OWN_FIELD_INDEX: usize,
+        Spec: InverseRelationSpec,
+    > InverseRelation<SelfModel, Fk, ToOneContainer<M>, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX, Spec>
 where
     PgParam: From<Fk>,
 {
-    async fn reload<'a, E: PgExecutor>(&'a mut self, ctx: E) -> GasResult<&'a ToOneContainer<M>>
-    where
-        M: 'a,
-    {
-        let select = make_lazy_inverse_query::<Fk, M, FORWARD_FIELD_INDEX>(self.parent_fk.clone())?;
+    pub async fn load_with<E: PgExecutor, F: FnOnce(SelectBuilder<M>) -> SelectBuilder<M>>(
+        &mut self,
+        ctx: E,
+        customize: F,
+    ) -> GasResult<&ToOneContainer<M>> {
+        let select = make_lazy_inverse_query::<Fk, M, FORWARD_FIELD_INDEX>(
+            customize(customized::<M, Spec>()),
+            self.parent_fk.clone(),
+        )?;
 
         let resp = select.find_one(ctx).await?;
         self.loaded = true;
@@ -249,7 +448,9 @@ where
     }
 }
 
+// the key condition is added to the filter the query may already have
 fn make_lazy_inverse_query<Fk, M: ModelMeta, const FIELD_INDEX: usize>(
+    select: SelectBuilder<M>,
     parent_fk: Fk,
 ) -> GasResult<SelectBuilder<M>>
 where
@@ -259,25 +460,154 @@ where
         .get(FIELD_INDEX)
         .ok_or_else(|| GasError::InvalidRelation)?;
 
-    let mut select = M::query();
     unsafe {
-        select = select.raw_filter(
+        Ok(select.raw_and_filter(
             format!("{}=?", field.full_name),
             &[PgParam::from(parent_fk)],
-        );
+        ))
+    }
+}
+
+// a limit is per parent here, not for all of them
+fn make_batch_inverse_query<M: ModelMeta, const FIELD_INDEX: usize>(
+    select: SelectBuilder<M>,
+    parent_fks: Vec<PgParam>,
+) -> GasResult<Vec<SelectBuilder<M>>> {
+    let field = M::FIELDS
+        .get(FIELD_INDEX)
+        .ok_or_else(|| GasError::InvalidRelation)?;
+
+    Ok(filter_by_keys(select, field, parent_fks)
+        .into_iter()
+        .map(|select| select.limit_per(field))
+        .collect())
+}
+
+pub(crate) fn make_batch_query<M: ModelMeta>(
+    field: &FieldMeta,
+    parent_fks: Vec<PgParam>,
+) -> Vec<SelectBuilder<M>> {
+    filter_by_keys(M::query(), field, parent_fks)
+}
+
+// usually one query, keys without an array type are split over as many as the parameter limit needs
+fn filter_by_keys<M: ModelMeta>(
+    select: SelectBuilder<M>,
+    field: &FieldMeta,
+    parent_fks: Vec<PgParam>,
+) -> Vec<SelectBuilder<M>> {
+    one_of_conditions(field.full_name, parent_fks, MAX_PARAMS)
+        .into_iter()
+        .map(|condition| select.clone().and_filter(condition))
+        .collect()
+}
+
+pub(crate) async fn find_all_batched<M: ModelMeta, E: PgExecutor>(
+    selects: Vec<SelectBuilder<M>>,
+    ctx: E,
+) -> GasResult<Vec<M>> {
+    let mut items = Vec::new();
+    for select in selects {
+        items.extend(select.find_all(ctx).await?);
+    }
+
+    Ok(items)
+}
+
+fn group_by_key<M: ModelMeta, const FIELD_INDEX: usize>(
+    items: Vec<M>,
+) -> GasResult<HashMap<String, Vec<M>>> {
+    let field = M::FIELDS
+        .get(FIELD_INDEX)
+        .ok_or_else(|| GasError::InvalidRelation)?;
+
+    let mut grouped: HashMap<String, Vec<M>> = HashMap::new();
+    for item in items {
+        let key = item
+            .get_param_by_field(field)
+            .ok_or_else(|| GasError::InvalidRelation)?;
+
+        grouped.entry(batch_key(&key)).or_default().push(item);
+    }
+
+    Ok(grouped)
+}
+
+// PgParam can't be hashed (floats), but its display is unique per type and value
+pub(crate) fn batch_key(param: &PgParam) -> String {
+    param.to_string()
+}
+
+// new children, saved with insert_with_relations
+//  order.lines = vec![line].into();
+impl<
+        SelfModel: ModelMeta,
+        Fk: AsPgType,
+        M: ModelMeta,
+        const FORWARD_FIELD_INDEX: usize,
+        const OWN_FIELD_INDEX: usize,
+        Spec: InverseRelationSpec,
+    > From<Vec<M>>
+    for InverseRelation<
+        SelfModel,
+        Fk,
+        ToManyContainer<M>,
+        FORWARD_FIELD_INDEX,
+        OWN_FIELD_INDEX,
+        Spec,
+    >
+where
+    PgParam: From<Fk>,
+{
+    fn from(items: Vec<M>) -> Self {
+        Self {
+            parent_fk: Fk::default(),
+            loaded: true,
+            items: items.into_boxed_slice(),
+            _marker: PhantomData,
+        }
     }
+}
 
-    Ok(select)
+impl<
+        SelfModel: ModelMeta,
+        Fk: AsPgType,
+        M: ModelMeta,
+        const FORWARD_FIELD_INDEX: usize,
+        const OWN_FIELD_INDEX: usize,
+        Spec: InverseRelationSpec,
+    > From<M>
+    for InverseRelation<
+        SelfModel,
+        Fk,
+        ToOneContainer<M>,
+        FORWARD_FIELD_INDEX,
+        OWN_FIELD_INDEX,
+        Spec,
+    >
+where
+    PgParam: From<Fk>,
+{
+    fn from(item: M) -> Self {
+        Self {
+            parent_fk: Fk::default(),
+            loaded: true,
+            items: Some(Box::new(item)),
+            _marker: PhantomData,
+        }
+    }
 }
 
 #[cfg(feature = "serde")]
 impl<
-    SelfModel: ModelMeta,
-    Fk: AsPgType,
-    Ret: Clone + Default,
-    const FORWARD_FIELD_INDEX: usize,
-    const OWN_FIELD_INDEX: usize,
-> serde::Serialize for InverseRelation<SelfModel, Fk, Ret, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX>
+        SelfModel: ModelMeta,
+        Fk: AsPgType,
+        Ret: Clone + Default,
+        const FORWARD_FIELD_INDEX: usize,
+        const OWN_FIELD_INDEX: usize,
+        Spec: InverseRelationSpec,
+    > serde::Serialize
+    for InverseRelation<SelfModel, Fk, Ret, FORWARD_FIELD_INDEX, OWN_FIELD_INDEX, Spec>
 where
     Ret: serde::Serialize,
     PgParam: From<Fk>,
@@ -286,6 +616,154 @@ where
     where
         S: serde::Serializer,
     {
-        self.items.serialize(serializer)
+        // unloaded lazy relations are serialized as null
+        self.loaded.then_some(&self.items).serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::*;
+    use crate::internals::RenderedQuery;
+    use crate::FullRelation;
+
+    fn comment(id: i64, document: i64) -> comment::Model {
+        comment::Model {
+            id,
+            document: FullRelation::ForeignKey(document),
+        }
+    }
+
+    fn document(id: i64) -> document::Model {
+        let mut document = document::Model {
+            id,
+            ..Default::default()
+        };
+        let parent = document.clone();
+        document.comments.update_parent(&parent);
+        document
+    }
+
+    #[test]
+    pub fn test_assign_batch() {
+        assert_eq!(
+            batch_key(&PgParam::from(1i64)),
+            batch_key(&PgParam::from(1i64))
+        );
+        assert_ne!(
+            batch_key(&PgParam::from(1i64)),
+            batch_key(&PgParam::from(1i32))
+        );
+        assert_ne!(
+            batch_key(&PgParam::from(1i64)),
+            batch_key(&PgParam::from("1".to_string()))
+        );
+
+        // 1 is comment::document
+        let comments = vec![comment(1, 1), comment(2, 2), comment(3, 1)];
+        let grouped = group_by_key::<comment::Model, 1>(comments).unwrap();
+        assert_eq!(grouped.len(), 2);
+        assert_eq!(grouped[&batch_key(&PgParam::from(1i64))].len(), 2);
+
+        // the first document is in there twice, the third has no comments
+        let mut documents = [document(1), document(2), document(1), document(3)];
+        let relations = documents.iter_mut().map(|it| &mut it.comments).collect();
+
+        InverseRelation::assign_batch(relations, grouped, Vec::into_boxed_slice);
+
+        let ids = |document: &document::Model| {
+            document
+                .comments
+                .get()
+                .unwrap()
+                .iter()
+                .map(|it| it.id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&documents[0]), vec![1, 3]);
+        assert_eq!(ids(&documents[1]), vec![2]);
+        assert_eq!(ids(&documents[2]), vec![1, 3]);
+        assert!(documents[3].comments.is_loaded());
+        assert!(ids(&documents[3]).is_empty());
+    }
+
+    #[test]
+    pub fn test_load_states() {
+        // lazy, nothing loaded yet
+        let unloaded = document(1);
+        assert!(!unloaded.comments.is_loaded());
+        assert!(unloaded.comments.get().is_none());
+        let deref = std::panic::catch_unwind(|| unloaded.comments.len());
+        let message = deref.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.contains("call `load` first"));
+
+        let loaded = document::Model {
+            comments: vec![comment(1, 1)].into(),
+            ..Default::default()
+        };
+        assert!(loaded.comments.is_loaded());
+        assert_eq!(loaded.comments.get().map(|it| it.len()), Some(1));
+        assert_eq!(loaded.comments[0].id, 1);
+
+        // loaded, just without children
+        let mut empty = document(2);
+        InverseRelation::assign_batch(
+            vec![&mut empty.comments],
+            HashMap::new(),
+            Vec::into_boxed_slice,
+        );
+        assert!(empty.comments.is_loaded());
+        assert_eq!(empty.comments.get().map(|it| it.len()), Some(0));
+        assert!(empty.comments.is_empty());
+    }
+
+    #[test]
+    pub fn test_batch_query_keys() {
+        fn render<M: ModelMeta>(selects: Vec<SelectBuilder<M>>) -> Vec<RenderedQuery> {
+            selects
+                .into_iter()
+                .map(|select| select.to_sql().unwrap())
+                .collect()
+        }
+
+        let rendered = render(make_batch_query::<comment::Model>(
+            &comment::document.meta,
+            (0..MAX_PARAMS as i64 + 1).map(PgParam::from).collect(),
+        ));
+        assert_eq!(rendered.len(), 1);
+        assert!(rendered[0]
+            .sql
+            .ends_with("WHERE comments.document=ANY($1);"));
+        assert_eq!(rendered[0].params.len(), 1);
+
+        // enums have no array variant, a param each and a query per MAX_PARAMS of them
+        let rendered = render(make_batch_query::<order::Model>(
+            &order::status.meta,
+            vec![
+                PgParam::from(OrderStatus::Pending),
+                PgParam::from(OrderStatus::Shipped),
+            ],
+        ));
+        assert_eq!(rendered.len(), 1);
+        assert!(rendered[0]
+            .sql
+            .ends_with("WHERE orders.status IN ($1, $2);"));
+        assert_eq!(rendered[0].params.len(), 2);
+
+        let rendered = render(make_batch_query::<order::Model>(
+            &order::status.meta,
+            vec![PgParam::from(OrderStatus::Pending); MAX_PARAMS + 1],
+        ));
+        assert_eq!(rendered.len(), 2);
+        assert_eq!(rendered[0].params.len(), MAX_PARAMS);
+        assert!(rendered[1].sql.ends_with("WHERE orders.status IN ($1);"));
+
+        let rendered = render(make_batch_query::<comment::Model>(
+            &comment::document.meta,
+            vec![],
+        ));
+        assert_eq!(rendered.len(), 1);
+        assert!(rendered[0].sql.ends_with("WHERE FALSE;"));
     }
 }
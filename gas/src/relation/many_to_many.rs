@@ -0,0 +1,543 @@
+use crate::condition::{one_of_conditions, MAX_PARAMS};
+use crate::connection::PgExecutor;
+use crate::error::GasError;
+use crate::internals::{AsPgType, IsOptional, PgArrayParam, PgParam, PgType, SqlQuery};
+use crate::relation::inverse::{batch_key, find_all_batched, make_batch_query, InverseRelationOps};
+use crate::row::{FromRowNamed, RelationCtx, ResponseCtx, Row};
+use crate::{FieldMeta, GasResult, ModelMeta};
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+// Target models linked to SelfModel through rows of the Through model (the join table),
+//  FROM_FIELD_INDEX is the foreign key in Through pointing to SelfModel, TO_FIELD_INDEX the one pointing to Target
+#[derive(Debug, Clone, Default)]
+pub struct ManyToMany<
+    SelfModel: ModelMeta,
+    Fk: AsPgType,
+    Through: ModelMeta,
+    ToFk: AsPgType,
+    Target: ModelMeta,
+    const FROM_FIELD_INDEX: usize,
+    const TO_FIELD_INDEX: usize,
+> where
+    PgParam: From<Fk> + From<ToFk>,
+{
+    pub(crate) parent_key: Fk,
+    pub(crate) loaded: bool,
+    pub(crate) items: Box<[Target]>,
+    _marker: PhantomData<(SelfModel, Through, ToFk)>,
+}
+
+pub trait ManyToManyTypeOps {
+    type Model;
+}
+
+impl<M: ModelMeta> ManyToManyTypeOps for Vec<M> {
+    type Model = M;
+}
+
+type Links<Target> = HashMap<String, Vec<Target>>;
+
+impl<
+        SelfModel: ModelMeta,
+        Fk: AsPgType,
+        Through: ModelMeta,
+        ToFk: AsPgType,
+        Target: ModelMeta,
+        const FROM_FIELD_INDEX: usize,
+        const TO_FIELD_INDEX: usize,
+    > ManyToMany<SelfModel, Fk, Through, ToFk, Target, FROM_FIELD_INDEX, TO_FIELD_INDEX>
+where
+    PgParam: From<Fk> + From<ToFk>,
+{
+    fn from_field() -> &'static FieldMeta {
+        Through::FIELDS
+            .get(FROM_FIELD_INDEX)
+            .expect("invalid relation")
+    }
+
+    fn to_field() -> &'static FieldMeta {
+        Through::FIELDS
+            .get(TO_FIELD_INDEX)
+            .expect("invalid relation")
+    }
+
+    // the field a foreign key of the join table points to, has to be in the given table
+    fn referenced_field(
+        field: &'static FieldMeta,
+        table_name: &str,
+    ) -> GasResult<&'static FieldMeta> {
+        match field.pg_type {
+            PgType::FOREIGN_KEY { target_field, .. } if target_field.table_name == table_name => {
+                Ok(target_field)
+            }
+            _ => Err(GasError::InvalidRelation),
+        }
+    }
+
+    fn new_from_row(ctx: &ResponseCtx, row: &Row) -> GasResult<Self> {
+        let own_field = Self::referenced_field(Self::from_field(), SelfModel::TABLE_NAME)?;
+
+        Ok(Self {
+            parent_key: FromRowNamed::from_row_named(ctx, row, own_field.alias_name)?,
+            loaded: false,
+            items: Box::new([]),
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        self.loaded
+    }
+
+    // None if the relation is lazy and wasn't loaded yet
+    pub fn get(&self) -> Option<&[Target]> {
+        self.loaded.then_some(&self.items)
+    }
+
+    pub async fn load<E: PgExecutor>(&mut self, ctx: E) -> GasResult<&[Target]> {
+        if !self.loaded {
+            self.reload(ctx).await?;
+        }
+
+        Ok(&self.items)
+    }
+
+    // two queries, one for the join table rows and one for the targets
+    async fn fetch<E: PgExecutor>(ctx: E, parent_keys: Vec<PgParam>) -> GasResult<Links<Target>> {
+        let from_field = Self::from_field();
+        let to_field = Self::to_field();
+        let target_field = Self::target_field()?;
+
+        let links =
+            find_all_batched(make_batch_query::<Through>(from_field, parent_keys), ctx).await?;
+
+        let links = links
+            .iter()
+            .map(|link| {
+                Some((
+                    batch_key(&link.get_param_by_field(from_field)?),
+                    link.get_param_by_field(to_field)?,
+                ))
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| GasError::InvalidRelation)?;
+
+        if links.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let targets = find_all_batched(
+            make_batch_query::<Target>(
+                target_field,
+                links.iter().map(|(_, key)| key.clone()).collect(),
+            ),
+            ctx,
+        )
+        .await?
+        .into_iter()
+        .map(|target| {
+            let key = target.get_param_by_field(target_field)?;
+            Some((batch_key(&key), target))
+        })
+        .collect::<Option<HashMap<_, _>>>()
+        .ok_or_else(|| GasError::InvalidRelation)?;
+
+        let mut grouped: Links<Target> = HashMap::new();
+        for (parent, key) in links {
+            if let Some(target) = targets.get(&batch_key(&key)) {
+                grouped.entry(parent).or_default().push(target.clone());
+            }
+        }
+
+        Ok(grouped)
+    }
+
+    fn assign(&mut self, grouped: &Links<Target>) {
+        let key = batch_key(&PgParam::from(self.parent_key.clone()));
+
+        self.items = grouped
+            .get(&key)
+            .map(|items| items.clone().into_boxed_slice())
+            .unwrap_or_default();
+        self.loaded = true;
+    }
+
+    // only refetched if it was loaded before, lazy ones stay unloaded
+    async fn refresh<E: PgExecutor>(&mut self, ctx: E) -> GasResult<()> {
+        if self.loaded {
+            self.reload(ctx).await?;
+        }
+
+        Ok(())
+    }
+
+    // inserts a row into the join table, nothing happens if it's already there
+    //  (given the join table has a primary key or a unique constraint on both fields)
+    pub async fn attach<E: PgExecutor>(&mut self, ctx: E, key: ToFk) -> GasResult<()> {
+        Self::insert_link(
+            ctx,
+            PgParam::from(self.parent_key.clone()),
+            PgParam::from(key),
+        )
+        .await?;
+        self.refresh(ctx).await
+    }
+
+    pub(crate) async fn insert_link<E: PgExecutor>(
+        ctx: E,
+        parent_key: PgParam,
+        key: PgParam,
+    ) -> GasResult<()> {
+        let sql = format!(
+            "INSERT INTO {}({}, {}) VALUES (?, ?) ON CONFLICT DO NOTHING",
+            Through::TABLE_NAME,
+            Self::from_field().name,
+            Self::to_field().name
+        );
+
+        ctx.execute(SqlQuery::from(sql), &[parent_key, key]).await?;
+
+        Ok(())
+    }
+
+    // the field of Target the join table points to
+    pub(crate) fn target_field() -> GasResult<&'static FieldMeta> {
+        Self::referenced_field(Self::to_field(), Target::TABLE_NAME)
+    }
+
+    pub async fn detach<E: PgExecutor>(&mut self, ctx: E, key: ToFk) -> GasResult<()> {
+        let sql = format!(
+            "DELETE FROM {} WHERE {}=? AND {}=?",
+            Through::TABLE_NAME,
+            Self::from_field().name,
+            Self::to_field().name
+        );
+
+        ctx.execute(
+            SqlQuery::from(sql),
+            &[PgParam::from(self.parent_key.clone()), PgParam::from(key)],
+        )
+        .await?;
+
+        self.refresh(ctx).await
+    }
+
+    // makes the join table contain exactly the given keys for this parent,
+    //  more than one statement, so use a transaction if that matters
+    pub async fn sync_ids<E: PgExecutor>(&mut self, ctx: E, keys: Vec<ToFk>) -> GasResult<()> {
+        let from_name = Self::from_field().name;
+        let parent_key = PgParam::from(self.parent_key.clone());
+
+        if keys.is_empty() {
+            let sql = format!("DELETE FROM {} WHERE {}=?", Through::TABLE_NAME, from_name);
+            ctx.execute(SqlQuery::from(sql), &[parent_key]).await?;
+
+            return self.refresh(ctx).await;
+        }
+
+        let keys = keys.into_iter().map(PgParam::from).collect::<Vec<_>>();
+
+        match PgArrayParam::try_collect(keys) {
+            Ok(array) => Self::sync_array(ctx, parent_key, array).await?,
+            Err(keys) => Self::sync_rows(ctx, parent_key, keys).await?,
+        }
+
+        self.refresh(ctx).await
+    }
+
+    // two statements no matter the amount of keys
+    async fn sync_array<E: PgExecutor>(
+        ctx: E,
+        parent_key: PgParam,
+        keys: PgArrayParam,
+    ) -> GasResult<()> {
+        let sql = format!(
+            "DELETE FROM {} WHERE {}=? AND NOT ({}=ANY(?))",
+            Through::TABLE_NAME,
+            Self::from_field().name,
+            Self::to_field().name
+        );
+        let params = [parent_key.clone(), PgParam::ARRAY(keys.clone())];
+        ctx.execute(SqlQuery::from(sql), &params).await?;
+
+        let sql = format!(
+            "INSERT INTO {}({}, {}) SELECT ?, UNNEST(?) ON CONFLICT DO NOTHING",
+            Through::TABLE_NAME,
+            Self::from_field().name,
+            Self::to_field().name
+        );
+        ctx.execute(SqlQuery::from(sql), &[parent_key, PgParam::ARRAY(keys)])
+            .await?;
+
+        Ok(())
+    }
+
+    // keys without an array type get params of their own, a NOT IN (...) can't be split over statements,
+    //  so the links that go are looked up first and everything is chunked under the parameter limit
+    async fn sync_rows<E: PgExecutor>(
+        ctx: E,
+        parent_key: PgParam,
+        keys: Vec<PgParam>,
+    ) -> GasResult<()> {
+        let from_field = Self::from_field();
+        let to_field = Self::to_field();
+
+        let kept = keys.iter().map(batch_key).collect::<HashSet<_>>();
+        let removed = find_all_batched(
+            make_batch_query::<Through>(from_field, vec![parent_key.clone()]),
+            ctx,
+        )
+        .await?
+        .iter()
+        .map(|link| link.get_param_by_field(to_field))
+        .collect::<Option<Vec<_>>>()
+        .ok_or(GasError::InvalidRelation)?
+        .into_iter()
+        .filter(|key| !kept.contains(&batch_key(key)))
+        .collect::<Vec<_>>();
+
+        if !removed.is_empty() {
+            for removed in one_of_conditions(to_field.name, removed, MAX_PARAMS - 1) {
+                let sql = format!(
+                    "DELETE FROM {} WHERE {}=? AND {}",
+                    Through::TABLE_NAME,
+                    from_field.name,
+                    removed.condition.as_sql().as_str()
+                );
+                let params = [vec![parent_key.clone()], removed.params].concat();
+                ctx.execute(SqlQuery::from(sql), &params).await?;
+            }
+        }
+
+        for chunk in keys.chunks(MAX_PARAMS / 2) {
+            let sql = format!(
+                "INSERT INTO {}({}, {}) VALUES {} ON CONFLICT DO NOTHING",
+                Through::TABLE_NAME,
+                from_field.name,
+                to_field.name,
+                vec!["(?, ?)"; chunk.len()].join(", ")
+            );
+            let params = chunk
+                .iter()
+                .flat_map(|key| [parent_key.clone(), key.clone()])
+                .collect::<Vec<_>>();
+            ctx.execute(SqlQuery::from(sql), &params).await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<
+        SelfModel: ModelMeta,
+        Fk: AsPgType,
+        Through: ModelMeta,
+        ToFk: AsPgType,
+        Target: ModelMeta,
+        const FROM_FIELD_INDEX: usize,
+        const TO_FIELD_INDEX: usize,
+    > InverseRelationOps<Box<[Target]>>
+    for ManyToMany<SelfModel, Fk, Through, ToFk, Target, FROM_FIELD_INDEX, TO_FIELD_INDEX>
+where
+    PgParam: From<Fk> + From<ToFk>,
+{
+    async fn reload<E: PgExecutor>(&mut self, ctx: E) -> GasResult<&Box<[Target]>> {
+        let grouped = Self::fetch(ctx, vec![PgParam::from(self.parent_key.clone())]).await?;
+
+        self.assign(&grouped);
+        Ok(&self.items)
+    }
+
+    async fn load_batch<'a>(relations: Vec<&'a mut Self>, ctx: &'a RelationCtx) -> GasResult<()>
+    where
+        Self: 'a,
+    {
+        if relations.is_empty() {
+            return Ok(());
+        }
+
+        let parent_keys = relations
+            .iter()
+            .map(|relation| PgParam::from(relation.parent_key.clone()))
+            .collect();
+        let grouped = Self::fetch(&ctx.executor, parent_keys).await?;
+
+        for relation in relations {
+            relation.assign(&grouped);
+        }
+
+        Ok(())
+    }
+}
+
+impl<
+        SelfModel: ModelMeta,
+        Fk: AsPgType,
+        Through: ModelMeta,
+        ToFk: AsPgType,
+        Target: ModelMeta,
+        const FROM_FIELD_INDEX: usize,
+        const TO_FIELD_INDEX: usize,
+    > Deref for ManyToMany<SelfModel, Fk, Through, ToFk, Target, FROM_FIELD_INDEX, TO_FIELD_INDEX>
+where
+    PgParam: From<Fk> + From<ToFk>,
+{
+    type Target = Box<[Target]>;
+
+    fn deref(&self) -> &Self::Target {
+        if !self.loaded {
+            panic!(
+                "many to many relation on {} is not loaded, call `load` first (or use `get`)",
+                SelfModel::TABLE_NAME
+            );
+        }
+
+        &self.items
+    }
+}
+
+// things required for the type to be compatible with the gas::model macro
+
+impl<
+        SelfModel: ModelMeta,
+        Fk: AsPgType + 'static,
+        Through: ModelMeta,
+        ToFk: AsPgType + 'static,
+        Target: ModelMeta,
+        const FROM_FIELD_INDEX: usize,
+        const TO_FIELD_INDEX: usize,
+    > AsPgType
+    for ManyToMany<SelfModel, Fk, Through, ToFk, Target, FROM_FIELD_INDEX, TO_FIELD_INDEX>
+where
+    PgParam: From<Fk> + From<ToFk>,
+{
+    const PG_TYPE: PgType = PgType::IGNORED;
+}
+
+impl<
+        SelfModel: ModelMeta,
+        Fk: AsPgType,
+        Through: ModelMeta,
+        ToFk: AsPgType,
+        Target: ModelMeta,
+        const FROM_FIELD_INDEX: usize,
+        const TO_FIELD_INDEX: usize,
+    > IsOptional
+    for ManyToMany<SelfModel, Fk, Through, ToFk, Target, FROM_FIELD_INDEX, TO_FIELD_INDEX>
+where
+    PgParam: From<Fk> + From<ToFk>,
+{
+    const FACTOR: u8 = 0;
+}
+
+impl<
+        SelfModel: ModelMeta,
+        Fk: AsPgType,
+        Through: ModelMeta,
+        ToFk: AsPgType,
+        Target: ModelMeta,
+        const FROM_FIELD_INDEX: usize,
+        const TO_FIELD_INDEX: usize,
+    > From<ManyToMany<SelfModel, Fk, Through, ToFk, Target, FROM_FIELD_INDEX, TO_FIELD_INDEX>>
+    for PgParam
+where
+    PgParam: From<Fk> + From<ToFk>,
+{
+    fn from(
+        _value: ManyToMany<SelfModel, Fk, Through, ToFk, Target, FROM_FIELD_INDEX, TO_FIELD_INDEX>,
+    ) -> Self {
+        PgParam::IGNORED
+    }
+}
+
+impl<
+        SelfModel: ModelMeta,
+        Fk: AsPgType + 'static,
+        Through: ModelMeta,
+        ToFk: AsPgType + 'static,
+        Target: ModelMeta,
+        const FROM_FIELD_INDEX: usize,
+        const TO_FIELD_INDEX: usize,
+    > FromRowNamed
+    for ManyToMany<SelfModel, Fk, Through, ToFk, Target, FROM_FIELD_INDEX, TO_FIELD_INDEX>
+where
+    PgParam: From<Fk> + From<ToFk>,
+{
+    fn from_row_named(ctx: &ResponseCtx, row: &Row, _name: &str) -> GasResult<Self> {
+        Self::new_from_row(ctx, row)
+    }
+}
+
+// new targets, saved (and linked) with insert_with_relations
+impl<
+        SelfModel: ModelMeta,
+        Fk: AsPgType,
+        Through: ModelMeta,
+        ToFk: AsPgType,
+        Target: ModelMeta,
+        const FROM_FIELD_INDEX: usize,
+        const TO_FIELD_INDEX: usize,
+    > From<Vec<Target>>
+    for ManyToMany<SelfModel, Fk, Through, ToFk, Target, FROM_FIELD_INDEX, TO_FIELD_INDEX>
+where
+    PgParam: From<Fk> + From<ToFk>,
+{
+    fn from(items: Vec<Target>) -> Self {
+        Self {
+            parent_key: Fk::default(),
+            loaded: true,
+            items: items.into_boxed_slice(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<
+        SelfModel: ModelMeta,
+        Fk: AsPgType,
+        Through: ModelMeta,
+        ToFk: AsPgType,
+        Target: ModelMeta,
+        const FROM_FIELD_INDEX: usize,
+        const TO_FIELD_INDEX: usize,
+    > serde::Serialize
+    for ManyToMany<SelfModel, Fk, Through, ToFk, Target, FROM_FIELD_INDEX, TO_FIELD_INDEX>
+where
+    Target: serde::Serialize,
+    PgParam: From<Fk> + From<ToFk>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        // unloaded lazy relations are serialized as null
+        self.loaded.then_some(&self.items).serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::fixtures::*;
+    use crate::ModelMeta;
+
+    #[test]
+    pub fn test_many_to_many() {
+        assert!(matches!(
+            post::tags.field_type,
+            crate::VirtualFieldType::ManyToMany
+        ));
+        assert!(post::Model::default().tags.get().is_none());
+
+        // only primary keys, nothing to set
+        let link = post_tag::Model::default();
+        let (update, params) = link.gen_update_sql();
+        assert_eq!(
+            update.finish(&params).unwrap(),
+            "UPDATE post_tags SET post=post WHERE post=$1 AND tag=$2 \
+             RETURNING post_tags.post AS post_tags_post, post_tags.tag AS post_tags_tag;"
+        );
+    }
+}
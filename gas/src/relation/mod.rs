@@ -1,2 +1,9 @@
+pub mod aggregate;
+pub mod batch;
+pub mod composite;
+pub mod exists;
 pub mod forward;
+pub mod graph;
+pub mod include;
 pub mod inverse;
+pub mod many_to_many;
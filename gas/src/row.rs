@@ -1,8 +1,14 @@
-use crate::connection::PgConnection;
+use crate::connection::RelationExecutor;
+use crate::error::GasError;
 use crate::internals::{AsPgType, NaiveDecodable};
-use crate::GasResult;
+use crate::relation::aggregate::AggregateColumn;
+use crate::types::PgCustomType;
+use crate::{FieldMeta, GasResult};
 use sqlx::postgres::PgRow;
 use sqlx::Row as SqlxRow;
+use sqlx::{Decode, Postgres, Type};
+use std::borrow::Cow;
+use std::pin::Pin;
 
 pub struct Row {
     pg_row: PgRow,
@@ -21,33 +27,129 @@ impl Row {
     {
         Ok(self.pg_row.try_get::<T, &str>(index)?)
     }
+
+    // anything sqlx can decode, for PgCustomType implementations
+    pub fn try_decode<T>(&self, index: &str) -> GasResult<T>
+    where
+        T: for<'a> Decode<'a, Postgres> + Type<Postgres>,
+    {
+        Ok(self.pg_row.try_get::<T, &str>(index)?)
+    }
 }
 
+pub type RelationFuture<'a> = Pin<Box<dyn Future<Output = GasResult<()>> + Send + 'a>>;
+
 pub trait FromRow: Sized + Send + 'static {
     fn from_row(ctx: &ResponseCtx, row: &Row) -> GasResult<Self>;
+
+    // called once with every item of a response after all of them went through from_row,
+    //  this is where inverse relations get loaded, with one query per relation for all items
+    //  boxed since it recurses (children load their own relations)
+    fn load_relations<'a>(_items: Vec<&'a mut Self>, _ctx: &'a RelationCtx) -> RelationFuture<'a> {
+        Box::pin(std::future::ready(Ok(())))
+    }
 }
 
 pub struct ResponseCtx<'a> {
-    // a reference to a connection that initiated the request
-    //  if it was with a &PgConnection itself, it will be that
-    //  if it was with a &mut PgTransaction, it will be with a connection that made the transaction
-    //  This field is useful sometimes if additional selects are needed while handling the from_row,
-    //      concretely by the InverseRelation (currently implemented in a very bad way)
-    //  That being said, don't use the connection for anything other than selects (without side effects)
-    // NOTE: this currently has bug where when data changed in a transaction, the select that
-    //  queries the database will partially have new data, and partially not,
-    //  depending on if the query included an inverse field
-    // TODO (low priority): use the actual PgExecutor implementor
-    pub(crate) connection: PgConnection,
     pub all_rows: &'a [Row],
+    pub(crate) joins: &'a [JoinScope],
+    // the join the model currently being decoded came from, None for the selected model itself
+    pub(crate) scope: Option<usize>,
+    // with_count and friends, selected next to the model
+    pub(crate) aggregates: &'a [AggregateColumn],
+}
+
+impl<'a> ResponseCtx<'a> {
+    pub(crate) fn new(all_rows: &'a [Row], includes: &'a ResponseIncludes) -> Self {
+        Self {
+            all_rows,
+            joins: &includes.joins,
+            scope: None,
+            aggregates: &includes.aggregates,
+        }
+    }
+
+    // columns of aliased joins (same table joined more than once) are prefixed
+    pub fn column<'n>(&self, alias_name: &'n str) -> Cow<'n, str> {
+        match self
+            .scope
+            .and_then(|scope| self.joins[scope].column_prefix.as_deref())
+        {
+            Some(prefix) => Cow::Owned(format!("{}{}", prefix, alias_name)),
+            None => Cow::Borrowed(alias_name),
+        }
+    }
+
+    // the context of a model joined through the given foreign key, if it was included
+    pub(crate) fn joined(&self, fk_alias: &str) -> Option<ResponseCtx<'a>> {
+        let scope = self
+            .joins
+            .iter()
+            .position(|join| join.parent == self.scope && join.fk_alias == fk_alias)?;
+
+        Some(ResponseCtx {
+            all_rows: self.all_rows,
+            joins: self.joins,
+            scope: Some(scope),
+            aggregates: self.aggregates,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct JoinScope {
+    pub(crate) parent: Option<usize>,
+    pub(crate) fk_alias: &'static str,
+    pub(crate) column_prefix: Option<String>,
+}
+
+// everything a select asked for besides its own columns
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ResponseIncludes {
+    pub(crate) joins: Vec<JoinScope>,
+    // full names of inverse relations from include_many
+    pub(crate) relations: Vec<&'static str>,
+    pub(crate) aggregates: Vec<AggregateColumn>,
+}
+
+pub struct RelationCtx {
+    // the executor that ran the query, a transaction stays a transaction
+    //  That being said, don't use it for anything other than selects (without side effects),
+    //  insert_with_relations being the exception, it always runs in a transaction
+    pub(crate) executor: RelationExecutor,
+    pub(crate) includes: Vec<&'static str>,
+}
+
+impl RelationCtx {
+    // whether a lazy inverse relation was requested with include_many
+    pub fn is_included(&self, field: &FieldMeta) -> bool {
+        self.includes.contains(&field.full_name)
+    }
 }
 
 pub trait FromRowNamed: Sized + Send + 'static {
     fn from_row_named(ctx: &ResponseCtx, row: &Row, name: &str) -> GasResult<Self>;
 }
 
-impl<T: AsPgType + NaiveDecodable> FromRowNamed for T {
-    fn from_row_named(_ctx: &ResponseCtx, row: &Row, name: &str) -> GasResult<Self> {
-        row.try_get::<T>(name)
+// plain column types, see pg_type_impl!
+pub(crate) fn decode_naive<T: AsPgType + NaiveDecodable>(
+    ctx: &ResponseCtx,
+    row: &Row,
+    name: &str,
+) -> GasResult<T> {
+    row.try_get::<T>(&ctx.column(name))
+}
+
+impl<T: PgCustomType> FromRowNamed for T {
+    fn from_row_named(ctx: &ResponseCtx, row: &Row, name: &str) -> GasResult<Self> {
+        T::from_row(ctx, row, name)?.ok_or_else(|| {
+            GasError::UnexpectedResponse(format!("unexpected null for {}", name).into())
+        })
+    }
+}
+
+impl<T: PgCustomType> FromRowNamed for Option<T> {
+    fn from_row_named(ctx: &ResponseCtx, row: &Row, name: &str) -> GasResult<Self> {
+        T::from_row(ctx, row, name)
     }
 }
@@ -1,4 +1,5 @@
-use crate::internals::SqlQuery;
+use crate::internals::{PgParam, SqlQuery};
+use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
 use std::ops::Shr;
 
@@ -19,7 +20,10 @@ impl Display for SortDirection {
 
 #[derive(Debug, Clone)]
 pub struct SortOp {
-    pub(crate) field_full_name: &'static str,
+    // not necessarily a field, can be an expression like ts_rank(...)
+    pub(crate) field_full_name: Cow<'static, str>,
+    // parameters used by the expression in field_full_name
+    pub(crate) params: Vec<PgParam>,
     pub(crate) direction: SortDirection,
 }
 
@@ -52,6 +56,11 @@ impl SortDefinition {
             sql
         })
     }
+
+    // in the same order as the placeholders in as_sql
+    pub(crate) fn params(&self) -> impl Iterator<Item = &PgParam> {
+        self.sorts.iter().flat_map(|op| op.params.iter())
+    }
 }
 
 impl Shr for SortDefinition {
@@ -1,3 +0,0 @@
-pub use chrono::prelude::*;
-pub use chrono::TimeDelta;
-pub use rust_decimal::prelude::*;
@@ -0,0 +1,126 @@
+use crate::error::GasError;
+use crate::internals::{PgParam, PgType};
+use crate::row::{ResponseCtx, Row};
+use crate::GasResult;
+use chrono::TimeDelta;
+use sqlx::postgres::types::PgInterval;
+
+// column types defined outside of gas, stored as something postgres already knows
+//  #[derive(gas::Type)] #[gas(transparent)] implements it for newtypes over a supported type,
+//  anything sqlx can encode and decode works by hand with PgParam::custom and Row::try_decode
+pub trait PgCustomType: Clone + Default + Send + Sync + 'static {
+    const PG_TYPE: PgType;
+
+    // None is a NULL of the same type
+    fn to_param(value: Option<Self>) -> PgParam;
+
+    fn from_row(ctx: &ResponseCtx, row: &Row, name: &str) -> GasResult<Option<Self>>;
+}
+
+// sqlx only encodes TimeDelta, intervals come back as months, days and microseconds
+impl PgCustomType for TimeDelta {
+    const PG_TYPE: PgType = PgType::INTERVAL;
+
+    fn to_param(value: Option<Self>) -> PgParam {
+        PgParam::INTERVAL(value)
+    }
+
+    fn from_row(ctx: &ResponseCtx, row: &Row, name: &str) -> GasResult<Option<Self>> {
+        let Some(interval) = row.try_decode::<Option<PgInterval>>(&ctx.column(name))? else {
+            return Ok(None);
+        };
+
+        // a month has no fixed length
+        if interval.months != 0 {
+            return Err(GasError::UnexpectedResponse(
+                format!(
+                    "interval of {} months can't be a TimeDelta",
+                    interval.months
+                )
+                .into(),
+            ));
+        }
+
+        Ok(Some(
+            TimeDelta::days(interval.days.into()) + TimeDelta::microseconds(interval.microseconds),
+        ))
+    }
+}
+
+// stored as BYTEA, sqlx only knows Vec<u8>
+#[cfg(feature = "bytes")]
+impl PgCustomType for bytes::Bytes {
+    const PG_TYPE: PgType = PgType::BYTEA;
+
+    fn to_param(value: Option<Self>) -> PgParam {
+        PgParam::BYTEA(value.map(|it| it.to_vec()))
+    }
+
+    fn from_row(ctx: &ResponseCtx, row: &Row, name: &str) -> GasResult<Option<Self>> {
+        Ok(row
+            .try_decode::<Option<Vec<u8>>>(&ctx.column(name))?
+            .map(bytes::Bytes::from))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::eq::PgEq;
+    use crate::fixtures::*;
+    use crate::internals::{AsPgType, PgParam};
+    use crate::{ModelMeta, ModelOps};
+
+    #[test]
+    pub fn test_custom_types() {
+        use crate::eq::{PgEqNone, PgRelationEq};
+        use crate::internals::PgType;
+
+        assert_eq!(<AccountId as AsPgType>::PG_TYPE, PgType::BIGINT);
+        assert_eq!(<Option<Email> as AsPgType>::PG_TYPE, PgType::TEXT);
+
+        let email = Email {
+            address: "ferris@example.com".to_string(),
+        };
+        let rendered = account::Model::query()
+            .filter(|| {
+                account::id.one_of(&[AccountId(1), AccountId(2)])
+                    & (account::email.eq(email.clone()) | account::backup_email.is_null())
+            })
+            .to_sql()
+            .unwrap();
+
+        assert!(rendered.sql.ends_with(
+            "WHERE (accounts.id IN ($1, $2)) AND ((accounts.email=$3) OR (accounts.backup_email IS NULL));"
+        ));
+        assert!(matches!(
+            &rendered.params[..],
+            [
+                PgParam::BIGINT(Some(1)),
+                PgParam::BIGINT(Some(2)),
+                PgParam::TEXT(Some(address)),
+            ] if address == "ferris@example.com"
+        ));
+
+        // custom keys work for relations as well
+        let rendered = session::Model::query()
+            .filter(|| session::account.eq_key(AccountId(7)))
+            .to_sql()
+            .unwrap();
+        assert!(rendered.sql.ends_with("WHERE sessions.account=$1;"));
+        assert!(matches!(&rendered.params[..], [PgParam::BIGINT(Some(7))]));
+
+        let account = account::Model {
+            id: AccountId(3),
+            email,
+            backup_email: None,
+        };
+        assert!(matches!(
+            account.get_param_by_field(&account::backup_email),
+            Some(PgParam::TEXT(None))
+        ));
+
+        // anything sqlx can encode
+        let param = PgParam::custom(Some(vec![1u8, 2, 3]));
+        assert!(matches!(param, PgParam::CUSTOM(_)));
+    }
+}
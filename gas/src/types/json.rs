@@ -0,0 +1,98 @@
+use crate::internals::{PgParam, PgType};
+use crate::row::{ResponseCtx, Row};
+use crate::types::PgCustomType;
+use crate::GasResult;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo};
+use sqlx::{Encode, Postgres, Type};
+use std::ops::{Deref, DerefMut};
+
+// a JSONB column holding T, (de)serialized with serde on the way in and out
+//  see gas::json for filtering by the values inside
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Json<T>(pub T);
+
+impl<T> Json<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Json<T> {
+    fn from(value: T) -> Self {
+        Json(value)
+    }
+}
+
+impl<T> Deref for Json<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Json<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+// transparent, a model serializes its json columns as the value itself
+impl<T: Serialize> Serialize for Json<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Json<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Json)
+    }
+}
+
+impl<T: Serialize + DeserializeOwned + Clone + Default + Send + Sync + 'static> PgCustomType
+    for Json<T>
+{
+    const PG_TYPE: PgType = PgType::JSONB;
+
+    fn to_param(value: Option<Self>) -> PgParam {
+        match value {
+            Some(value) => json_param(&value.0),
+            None => PgParam::JSONB(None),
+        }
+    }
+
+    fn from_row(ctx: &ResponseCtx, row: &Row, name: &str) -> GasResult<Option<Self>> {
+        Ok(row
+            .try_decode::<Option<sqlx::types::Json<T>>>(&ctx.column(name))?
+            .map(|it| Json(it.0)))
+    }
+}
+
+// params can't fail, an error comes up once the query runs
+pub(crate) fn json_param<T: Serialize>(value: &T) -> PgParam {
+    match serde_json::to_value(value) {
+        Ok(value) => PgParam::JSONB(Some(sqlx::types::Json(value))),
+        Err(err) => PgParam::custom(Some(JsonError(err.to_string()))),
+    }
+}
+
+// T couldn't be turned into json (e.g. a map with non-string keys)
+#[derive(Debug)]
+struct JsonError(String);
+
+impl Type<Postgres> for JsonError {
+    fn type_info() -> PgTypeInfo {
+        <serde_json::Value as Type<Postgres>>::type_info()
+    }
+}
+
+impl Encode<'_, Postgres> for JsonError {
+    fn encode_by_ref(&self, _buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        Err(self.0.clone().into())
+    }
+}
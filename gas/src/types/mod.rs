@@ -0,0 +1,28 @@
+pub use chrono::prelude::*;
+pub use chrono::TimeDelta;
+pub use rust_decimal::prelude::*;
+#[cfg(feature = "uuid")]
+pub use uuid::Uuid;
+
+#[cfg(feature = "bytes")]
+pub use bytes::Bytes;
+#[cfg(feature = "ipnetwork")]
+pub use ipnetwork::IpNetwork;
+#[cfg(feature = "mac_address")]
+pub use mac_address::MacAddress;
+#[cfg(feature = "ipnetwork")]
+pub use std::net::IpAddr;
+
+mod custom;
+mod json;
+#[cfg(feature = "ipnetwork")]
+mod network;
+mod pg_enum;
+mod tsvector;
+
+pub use custom::*;
+pub use json::*;
+#[cfg(feature = "ipnetwork")]
+pub use network::*;
+pub use pg_enum::*;
+pub use tsvector::*;
@@ -0,0 +1,86 @@
+use crate::internals::{PgParam, PgType};
+use crate::row::{ResponseCtx, Row};
+use crate::types::PgCustomType;
+use crate::GasResult;
+use ipnetwork::IpNetwork;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::net::{IpAddr, Ipv4Addr};
+use std::ops::Deref;
+use std::str::FromStr;
+
+// newtypes since neither IpAddr nor IpNetwork have a Default, 0.0.0.0/32 here
+macro_rules! network_type_impl {
+    ($name:ident as $pg_type:expr) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(pub IpNetwork);
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name(IpNetwork::from(IpAddr::V4(Ipv4Addr::UNSPECIFIED)))
+            }
+        }
+
+        impl From<IpAddr> for $name {
+            fn from(value: IpAddr) -> Self {
+                $name(IpNetwork::from(value))
+            }
+        }
+
+        impl From<IpNetwork> for $name {
+            fn from(value: IpNetwork) -> Self {
+                $name(value)
+            }
+        }
+
+        impl From<$name> for IpNetwork {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl Deref for $name {
+            type Target = IpNetwork;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ipnetwork::IpNetworkError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                IpNetwork::from_str(s).map($name)
+            }
+        }
+
+        // sent as an INET either way, postgres casts it on the way into a CIDR column
+        impl PgCustomType for $name {
+            const PG_TYPE: PgType = $pg_type;
+
+            fn to_param(value: Option<Self>) -> PgParam {
+                PgParam::INET(value.map(|it| it.0))
+            }
+
+            fn from_row(ctx: &ResponseCtx, row: &Row, name: &str) -> GasResult<Option<Self>> {
+                Ok(row
+                    .try_decode::<Option<IpNetwork>>(&ctx.column(name))?
+                    .map($name))
+            }
+        }
+    };
+}
+
+// an INET column, an address with an optional prefix (10.1.2.3 or 10.1.2.3/8, host bits are kept)
+network_type_impl!(Inet as PgType::INET);
+
+// a CIDR column, a network, host bits are zeroed by postgres (10.1.2.3/8 is stored as 10.0.0.0/8)
+network_type_impl!(Cidr as PgType::CIDR);
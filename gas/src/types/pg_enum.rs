@@ -0,0 +1,138 @@
+use crate::error::GasError;
+use crate::internals::{PgParam, PgType};
+use crate::row::{ResponseCtx, Row};
+use crate::types::PgCustomType;
+use crate::GasResult;
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo, PgTypeKind, PgValueRef};
+use sqlx::{Decode, Encode, Postgres, Type};
+
+// implemented by #[derive(PgEnum)], one label per (unit) variant
+//  #[pg_enum(name = "order_status")]
+//  enum OrderStatus { Pending, Shipped } -> CREATE TYPE order_status AS ENUM ('pending', 'shipped')
+pub trait PgEnum: Copy + Default + Send + Sync + 'static {
+    const NAME: &'static str;
+    const VARIANTS: &'static [&'static str];
+
+    fn as_label(&self) -> &'static str;
+
+    fn from_label(label: &str) -> Option<Self>;
+}
+
+impl<T: PgEnum> PgCustomType for T {
+    const PG_TYPE: PgType = PgType::ENUM {
+        name: T::NAME,
+        variants: T::VARIANTS,
+    };
+
+    fn to_param(value: Option<Self>) -> PgParam {
+        PgParam::ENUM(PgEnumParam::new(value))
+    }
+
+    fn from_row(ctx: &ResponseCtx, row: &Row, name: &str) -> GasResult<Option<Self>> {
+        let Some(PgEnumLabel(label)) = row.try_decode::<Option<PgEnumLabel>>(&ctx.column(name))?
+        else {
+            return Ok(None);
+        };
+
+        T::from_label(&label).map(Some).ok_or_else(|| {
+            GasError::UnexpectedResponse(
+                format!("{:?} is not a variant of {}", label, T::NAME).into(),
+            )
+        })
+    }
+}
+
+// a label sent as the enum type itself, postgres won't cast text to an enum on its own
+#[derive(Debug, Clone)]
+pub struct PgEnumParam {
+    pub type_name: &'static str,
+    pub label: Option<&'static str>,
+}
+
+impl PgEnumParam {
+    pub fn new<T: PgEnum>(value: Option<T>) -> Self {
+        Self {
+            type_name: T::NAME,
+            label: value.as_ref().map(T::as_label),
+        }
+    }
+}
+
+impl Type<Postgres> for PgEnumParam {
+    // the actual type is reported by `produces`
+    fn type_info() -> PgTypeInfo {
+        <&str as Type<Postgres>>::type_info()
+    }
+}
+
+impl Encode<'_, Postgres> for PgEnumParam {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        match self.label {
+            Some(label) => <&str as Encode<Postgres>>::encode_by_ref(&label, buf),
+            None => Ok(IsNull::Yes),
+        }
+    }
+
+    fn produces(&self) -> Option<PgTypeInfo> {
+        Some(PgTypeInfo::with_name(self.type_name))
+    }
+}
+
+// what comes back from the database, enums are sent as their label in both formats
+struct PgEnumLabel(String);
+
+impl Type<Postgres> for PgEnumLabel {
+    fn type_info() -> PgTypeInfo {
+        <&str as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        matches!(ty.kind(), PgTypeKind::Enum(_)) || <&str as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl Decode<'_, Postgres> for PgEnumLabel {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        Ok(PgEnumLabel(value.as_str()?.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::eq::PgEq;
+    use crate::fixtures::*;
+    use crate::internals::{AsPgType, PgParam};
+    use crate::ModelOps;
+
+    #[test]
+    pub fn test_pg_enum() {
+        use crate::types::PgEnum;
+
+        let pg_type = <OrderStatus as AsPgType>::PG_TYPE;
+        assert_eq!(pg_type.as_sql_type(false), "order_status");
+        assert_eq!(
+            pg_type.as_create_type_sql().unwrap(),
+            "CREATE TYPE order_status AS ENUM ('pending', 'sent', 'delivered')"
+        );
+        assert_eq!(OrderStatus::from_label("sent"), Some(OrderStatus::Shipped));
+        assert_eq!(OrderStatus::from_label("Shipped"), None);
+
+        let rendered = order::Model::query()
+            .filter(|| {
+                order::status.eq(OrderStatus::Shipped)
+                    & order::status.one_of(&[OrderStatus::Pending, OrderStatus::Delivered])
+            })
+            .to_sql()
+            .unwrap();
+
+        assert!(rendered
+            .sql
+            .ends_with("WHERE (orders.status=$1) AND (orders.status IN ($2, $3));"));
+        assert!(matches!(
+            &rendered.params[0],
+            PgParam::ENUM(param) if param.type_name == "order_status" && param.label == Some("sent")
+        ));
+    }
+}
@@ -0,0 +1,258 @@
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef};
+use sqlx::{Decode, Encode, Postgres, Type};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+// postgres stores the position in the lower 14 bits and the weight in the upper 2
+const POSITION_MASK: u16 = (1 << 14) - 1;
+
+#[derive(Debug, Clone, Copy, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub enum TsWeight {
+    A,
+    B,
+    C,
+    #[default]
+    D,
+}
+
+impl TsWeight {
+    const fn from_bits(bits: u16) -> Self {
+        match bits {
+            3 => TsWeight::A,
+            2 => TsWeight::B,
+            1 => TsWeight::C,
+            _ => TsWeight::D,
+        }
+    }
+
+    const fn as_bits(&self) -> u16 {
+        match self {
+            TsWeight::A => 3,
+            TsWeight::B => 2,
+            TsWeight::C => 1,
+            TsWeight::D => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub struct TsPosition {
+    pub position: u16,
+    pub weight: TsWeight,
+}
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct TsLexeme {
+    pub word: String,
+    pub positions: Vec<TsPosition>,
+}
+
+// usually produced by the database, either as a generated column (see `#[tsvector]`)
+//  or through to_tsvector, rarely built by hand
+#[derive(Debug, Clone, Default, Hash, Eq, PartialEq)]
+pub struct TsVector {
+    pub lexemes: Vec<TsLexeme>,
+}
+
+impl TsVector {
+    pub fn contains(&self, word: &str) -> bool {
+        self.lexemes.iter().any(|it| it.word == word)
+    }
+}
+
+impl Display for TsVector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, lexeme) in self.lexemes.iter().enumerate() {
+            if index > 0 {
+                write!(f, " ")?;
+            }
+
+            write!(
+                f,
+                "'{}'",
+                lexeme.word.replace('\\', "\\\\").replace('\'', "''")
+            )?;
+
+            for (index, position) in lexeme.positions.iter().enumerate() {
+                write!(f, "{}", if index == 0 { ':' } else { ',' })?;
+                write!(f, "{}", position.position)?;
+
+                match position.weight {
+                    TsWeight::D => {}
+                    weight => write!(f, "{:?}", weight)?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// the text representation, e.g. 'cat':2,5B 'fat':3
+impl FromStr for TsVector {
+    type Err = BoxDynError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lexemes = Vec::new();
+        let mut chars = s.chars().peekable();
+
+        loop {
+            while chars.next_if(|it| it.is_whitespace()).is_some() {}
+
+            let Some(quote) = chars.next() else {
+                break;
+            };
+
+            if quote != '\'' {
+                return Err(format!("invalid tsvector {s:?}, expected a quoted lexeme").into());
+            }
+
+            let mut word = String::new();
+            loop {
+                match chars.next() {
+                    Some('\\') => word.extend(chars.next()),
+                    Some('\'') if chars.next_if_eq(&'\'').is_some() => word.push('\''),
+                    Some('\'') => break,
+                    Some(ch) => word.push(ch),
+                    None => return Err(format!("invalid tsvector {s:?}, unterminated").into()),
+                }
+            }
+
+            let mut positions = Vec::new();
+            if chars.next_if_eq(&':').is_some() {
+                loop {
+                    let mut number = String::new();
+                    while let Some(digit) = chars.next_if(|it| it.is_ascii_digit()) {
+                        number.push(digit);
+                    }
+
+                    let weight = match chars.next_if(|it| matches!(it, 'A'..='D' | 'a'..='d')) {
+                        Some('A' | 'a') => TsWeight::A,
+                        Some('B' | 'b') => TsWeight::B,
+                        Some('C' | 'c') => TsWeight::C,
+                        _ => TsWeight::D,
+                    };
+
+                    positions.push(TsPosition {
+                        position: number.parse()?,
+                        weight,
+                    });
+
+                    if chars.next_if_eq(&',').is_none() {
+                        break;
+                    }
+                }
+            }
+
+            lexemes.push(TsLexeme { word, positions });
+        }
+
+        Ok(TsVector { lexemes })
+    }
+}
+
+impl Type<Postgres> for TsVector {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("tsvector")
+    }
+}
+
+// binary format, see tsvector_send/tsvector_recv in postgres
+//  i32 lexeme count, then for each: null terminated word, u16 position count, u16 positions
+impl Decode<'_, Postgres> for TsVector {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        let mut bytes = match value.format() {
+            PgValueFormat::Binary => value.as_bytes()?,
+            PgValueFormat::Text => return value.as_str()?.parse(),
+        };
+
+        let mut take = |count: usize| -> Result<&[u8], BoxDynError> {
+            if bytes.len() < count {
+                return Err("unexpected end of tsvector".into());
+            }
+
+            let (head, tail) = bytes.split_at(count);
+            bytes = tail;
+            Ok(head)
+        };
+
+        let count = i32::from_be_bytes(take(4)?.try_into()?);
+        let mut lexemes = Vec::with_capacity(count.max(0) as usize);
+
+        for _ in 0..count {
+            let mut word = Vec::new();
+            loop {
+                match take(1)?[0] {
+                    0 => break,
+                    byte => word.push(byte),
+                }
+            }
+
+            let position_count = u16::from_be_bytes(take(2)?.try_into()?);
+            let positions = (0..position_count)
+                .map(|_| {
+                    let raw = u16::from_be_bytes(take(2)?.try_into()?);
+
+                    Ok(TsPosition {
+                        position: raw & POSITION_MASK,
+                        weight: TsWeight::from_bits(raw >> 14),
+                    })
+                })
+                .collect::<Result<Vec<_>, BoxDynError>>()?;
+
+            lexemes.push(TsLexeme {
+                word: String::from_utf8(word)?,
+                positions,
+            });
+        }
+
+        Ok(TsVector { lexemes })
+    }
+}
+
+impl Encode<'_, Postgres> for TsVector {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        buf.extend_from_slice(&i32::try_from(self.lexemes.len())?.to_be_bytes());
+
+        for lexeme in &self.lexemes {
+            if lexeme.word.contains('\0') {
+                return Err("tsvector lexeme can not contain a null byte".into());
+            }
+
+            buf.extend_from_slice(lexeme.word.as_bytes());
+            buf.push(0);
+
+            // postgres rejects positions that are not strictly ascending
+            let mut positions = lexeme.positions.clone();
+            positions.sort_by_key(|it| it.position);
+            positions.dedup_by_key(|it| it.position);
+
+            buf.extend_from_slice(&u16::try_from(positions.len())?.to_be_bytes());
+            for position in positions {
+                let raw = (position.position & POSITION_MASK) | (position.weight.as_bits() << 14);
+                buf.extend_from_slice(&raw.to_be_bytes());
+            }
+        }
+
+        Ok(IsNull::No)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::types::{TsVector, TsWeight};
+
+    #[test]
+    pub fn test_text_roundtrip() {
+        let text = "'cat':2,5B 'fat':3 'it''s' 'rat':1A";
+        let vector: TsVector = text.parse().unwrap();
+
+        assert_eq!(vector.lexemes.len(), 4);
+        assert_eq!(vector.lexemes[0].positions[1].weight, TsWeight::B);
+        assert_eq!(vector.lexemes[2].word, "it's");
+        assert!(vector.lexemes[2].positions.is_empty());
+        assert_eq!(vector.to_string(), text);
+    }
+}